@@ -50,45 +50,133 @@ export interface RouteNode {
   loadingPath?: string
   errorPath?: string
   notFoundPath?: string
+  defaultPath?: string
+  interception?: string
   children: Array<RouteNode>
+  /** @slot folders, rendered through this node's layout props */
+  parallelRoutes: Array<RouteNode>
+  /** (.)segment folders, matched only on client-side navigations */
+  interceptingRoutes: Array<RouteNode>
 }
 export declare function getRouteTree(appDir: string): RouteNode
+/** Route graph node for NAPI */
+export interface NapiRouteGraphNode {
+  segment: string
+  pattern: string
+  /**
+   * "static", "dynamic", "catch-all", "optional-catch-all", "group", "slot",
+   * "intercepting" or "reserved-internal"
+   */
+  kind: string
+  hasPage: boolean
+  hasLayout: boolean
+  children: Array<NapiRouteGraphNode>
+  filePath?: string
+}
+/** App structure validation result for NAPI */
+export interface NapiStructureValidation {
+  /** "ok" or "error" */
+  state: string
+  issues: Array<NapiDiagnostic>
+  routeGraph: Array<NapiRouteGraphNode>
+  timestamp: number
+}
+/** Validate the app directory structure and build its route graph */
+export declare function validateAppStructure(appDir: string, notFoundRoute?: string | undefined | null, options?: NapiScanOptions | undefined | null): NapiStructureValidation
 /** Version of vista-napi */
 export declare function version(): string
 /**
  * Check if source file has a static metadata export
- * Looks for: `export const metadata` or `export const metadata:`
+ * Looks for an exported `metadata` binding in the parsed module
  */
 export declare function hasMetadataExport(source: string): boolean
 /**
  * Check if source file has generateMetadata function
- * Looks for: `export function generateMetadata` or `export async function generateMetadata`
+ * Looks for an exported `generateMetadata` binding in the parsed module
  */
 export declare function hasGenerateMetadata(source: string): boolean
 /** Metadata information extracted from a source file */
 export interface MetadataInfo {
   hasStaticMetadata: boolean
   hasGenerateMetadata: boolean
+  /** Literal parts of an object `metadata` export */
+  staticMetadata?: any
 }
 /** Analyze source file for metadata exports */
 export declare function analyzeMetadata(source: string): MetadataInfo
+/**
+ * Render head HTML from a layout chain's metadata (root layout first, page last).
+ * Title templates are inherited through the chain.
+ */
+export declare function renderMetadataHead(metadata: Array<Record<string, any>>): string
+/** Export table entry for NAPI */
+export interface NapiExportEntry {
+  /** Exported name ("default" for the default export, "*" for star exports) */
+  name: string
+  /** "local", "re-export", "namespace" or "star" */
+  kind: string
+  local?: string
+  source?: string
+  line: number
+}
 /** Scanned component info for NAPI */
 export interface NapiScannedComponent {
   absolutePath: string
   relativePath: string
   isClient: boolean
   directiveLine: number
+  isServer: boolean
+  serverActions: Array<string>
   componentType: string
   exports: Array<string>
+  exportTable: Array<NapiExportEntry>
   clientHooksUsed: Array<string>
   hasMetadata: boolean
   hasGenerateMetadata: boolean
+  hasGenerateStaticParams: boolean
+}
+/** Client hook/API call or event handler prop location for NAPI */
+export interface NapiClientApiUsage {
+  name: string
+  line: number
+  column: number
+}
+/** Source location for NAPI (1-indexed line and column) */
+export interface NapiSourceSpan {
+  path: string
+  relativePath: string
+  line: number
+  column: number
+  label?: string
+}
+/** Diagnostic for NAPI */
+export interface NapiDiagnostic {
+  /** "error" or "warning" */
+  severity: string
+  /** Stable code, e.g. "CLIENT_API_IN_SERVER_COMPONENT" */
+  code: string
+  message: string
+  primarySpan?: NapiSourceSpan
+  secondarySpans: Array<NapiSourceSpan>
+  /** Source excerpt with a caret under the primary column */
+  codeFrame?: string
+  /** Suggested fix */
+  fix?: string
 }
 /** Server component error for NAPI */
 export interface NapiServerComponentError {
+  /**
+   * "parse-error", "directive-conflict", "server-action-in-client-component",
+   * "client-api-in-server-component", "server-only-in-client", "client-only-in-server"
+   * or "invalid-segment-config"
+   */
+  kind: string
   file: string
   message: string
   hooks: Array<string>
+  usages: Array<NapiClientApiUsage>
+  /** Import chain to the module using client-only APIs (empty for direct use) */
+  importChain: Array<string>
 }
 /** Scan result for NAPI */
 export interface NapiScanResult {
@@ -97,12 +185,66 @@ export interface NapiScanResult {
   pages: Array<NapiScannedComponent>
   layouts: Array<NapiScannedComponent>
   apiRoutes: Array<NapiScannedComponent>
+  serverActionModules: Array<NapiScannedComponent>
   errors: Array<NapiServerComponentError>
+  /** `errors` with stable codes, source spans and code frames */
+  diagnostics: Array<NapiDiagnostic>
   totalFiles: number
   scanTimeMs: number
 }
+/** Scan options for NAPI */
+export interface NapiScanOptions {
+  /** Gitignore-style patterns to skip (default: ["node_modules"]) */
+  ignore?: Array<string>
+  /** Respect .gitignore files (default: true) */
+  respectGitignore?: boolean
+  /** Reuse analysis from the persistent scan cache (default: true) */
+  cache?: boolean
+  /** Cache directory (default: .vista/cache in the project root) */
+  cacheDir?: string
+}
 /** Scan app directory and classify all components (Rust-powered, blazing fast) */
-export declare function rscScanApp(appDir: string): NapiScanResult
+export declare function rscScanApp(appDir: string, options?: NapiScanOptions | undefined | null): NapiScanResult
+/** Files added, changed or removed since the previous scan */
+export interface NapiScanDelta {
+  added: Array<string>
+  changed: Array<string>
+  removed: Array<string>
+}
+/** Incremental scan result for NAPI */
+export interface NapiIncrementalScan {
+  result: NapiScanResult
+  delta: NapiScanDelta
+}
+/** Scan app directory through the persistent scan cache and report what changed */
+export declare function rscScanAppIncremental(appDir: string, options?: NapiScanOptions | undefined | null): NapiIncrementalScan
+/** File change event for NAPI */
+export interface NapiWatchEvent {
+  /** "add", "change" or "remove" */
+  kind: string
+  absolutePath: string
+  relativePath: string
+}
+/** Watch options for NAPI */
+export interface NapiWatchOptions {
+  /** Gitignore-style patterns to skip (default: ["node_modules"]) */
+  ignore?: Array<string>
+  /** Respect .gitignore files (default: true) */
+  respectGitignore?: boolean
+  /** Files and directories to watch next to the app directory */
+  extraPaths?: Array<string>
+  /** Extensions of reported files, without the dot (default: ["ts", "tsx", "js", "jsx"]) */
+  extensions?: Array<string>
+  /** Quiet period before a batch is delivered (default: 120) */
+  debounceMs?: number
+  /** Longest a batch waits after its first change (default: 1000) */
+  maxWaitMs?: number
+}
+/**
+ * Watch the app directory (and any extra paths) and call `callback` with
+ * debounced, filtered change batches
+ */
+export declare function rscWatchApp(appDir: string, callback: (events: Array<NapiWatchEvent>) => void, options?: NapiWatchOptions | undefined | null): RscWatcher
 /** Client module entry for NAPI */
 export interface NapiClientModuleEntry {
   id: string
@@ -119,6 +261,22 @@ export interface NapiClientManifest {
 }
 /** Generate client manifest (Rust-powered) */
 export declare function rscGenerateClientManifest(appDir: string, buildId: string): NapiClientManifest
+/** Server action entry for NAPI */
+export interface NapiServerActionEntry {
+  id: string
+  path: string
+  absolutePath: string
+  moduleId: string
+  exportName: string
+  inline: boolean
+}
+/** Server action manifest for NAPI */
+export interface NapiServerActionManifest {
+  buildId: string
+  actions: Array<NapiServerActionEntry>
+}
+/** Generate server action manifest (Rust-powered) */
+export declare function rscGenerateServerActionManifest(appDir: string, buildId: string): NapiServerActionManifest
 /** Route entry for NAPI */
 export interface NapiRouteEntry {
   pattern: string
@@ -126,7 +284,60 @@ export interface NapiRouteEntry {
   layoutPaths: Array<string>
   loadingPath?: string
   errorPath?: string
+  notFoundPath?: string
   routeType: string
+  /** `@slot` this page renders into (slot pages are never matched on their own) */
+  slot?: string
+  /** Set for intercepting routes (client-side navigations only) */
+  interception?: NapiRouteInterception
+  slots: Array<NapiRouteSlot>
+  boundaries: NapiRouteBoundaries
+  hasGenerateStaticParams: boolean
+  /**
+   * Effective segment config: the shortest `revalidate` across the
+   * layouts and page, every other option from the innermost segment that sets it
+   */
+  segmentConfig: NapiSegmentConfig
+}
+/** Route segment config for NAPI */
+export interface NapiSegmentConfig {
+  dynamic?: string
+  dynamicParams?: boolean
+  /** `false` caches indefinitely, otherwise seconds */
+  revalidate?: boolean | number
+  fetchCache?: string
+  runtime?: string
+  preferredRegion?: Array<string>
+  maxDuration?: number
+}
+/**
+ * Per-segment boundary files for NAPI, outermost first. All lists are aligned
+ * with `segments`; a null entry means the segment has no such file.
+ */
+export interface NapiRouteBoundaries {
+  /** Segment directories relative to the app directory ("" is the root) */
+  segments: Array<string>
+  layouts: Array<string | undefined | null>
+  templates: Array<string | undefined | null>
+  loading: Array<string | undefined | null>
+  errors: Array<string | undefined | null>
+  notFound: Array<string | undefined | null>
+}
+/** Intercepting route info for NAPI */
+export interface NapiRouteInterception {
+  /** "(.)", "(..)", "(..)(..)" or "(...)" */
+  marker: string
+  /** Pattern of the level navigations are intercepted from */
+  sourcePattern: string
+}
+/** Parallel route slot for NAPI */
+export interface NapiRouteSlot {
+  /** Slot name without "@", also the layout prop name */
+  name: string
+  layoutPath?: string
+  pagePath?: string
+  /** Rendered when no slot page matches */
+  defaultPath?: string
 }
 /** Server module entry for NAPI */
 export interface NapiServerModuleEntry {
@@ -136,15 +347,110 @@ export interface NapiServerModuleEntry {
   componentType: string
   hasMetadata: boolean
   hasGenerateMetadata: boolean
+  /** Literal parts of the `metadata` export */
+  staticMetadata?: any
+  clientDependencies: Array<string>
 }
 /** Server manifest for NAPI */
 export interface NapiServerManifest {
   buildId: string
   serverModules: Array<NapiServerModuleEntry>
   routes: Array<NapiRouteEntry>
+  diagnostics: Array<NapiDiagnostic>
 }
-/** Generate server manifest (Rust-powered) */
+/** Generate server manifest (Rust-powered), failing when routes conflict */
 export declare function rscGenerateServerManifest(appDir: string, buildId: string): NapiServerManifest
+/** Matched route for NAPI */
+export interface NapiRouteMatch {
+  /** Index into the routes the matcher was created with */
+  index: number
+  route: NapiRouteEntry
+  /** Decoded params; catch-alls are arrays, empty optional catch-alls are omitted */
+  params: Record<string, string | Array<string>>
+}
+/** Concrete path to prerender for NAPI */
+export interface NapiStaticPath {
+  pattern: string
+  /** URL path with percent-encoded params */
+  path: string
+  pagePath: string
+  params: Record<string, string | Array<string>>
+}
+/** Static path plan for NAPI */
+export interface NapiStaticPathPlan {
+  paths: Array<NapiStaticPath>
+  /** Patterns with `dynamicParams = false`: params outside `paths` 404 */
+  staticOnlyPatterns: Array<string>
+  diagnostics: Array<NapiDiagnostic>
+}
+/**
+ * Expand routes into the paths to prerender. `staticParams` maps a route
+ * pattern to what its page's `generateStaticParams` returned.
+ */
+export declare function rscPlanStaticPaths(appDir: string, routes: Array<NapiRouteEntry>, staticParams: Record<string, Array<Record<string, string | Array<string>>>>): NapiStaticPathPlan
+/** All RSC manifests generated from a single scan */
+export interface NapiManifests {
+  client: NapiClientManifest
+  server: NapiServerManifest
+  serverActions: NapiServerActionManifest
+}
+/**
+ * Generate client, server and server action manifests from one (cached)
+ * scan, failing when routes conflict
+ */
+export declare function rscGenerateManifests(appDir: string, buildId: string, options?: NapiScanOptions | undefined | null): NapiManifests
+/**
+ * Write the manifests of one (cached) scan and their JSON Schemas to
+ * `.vista/manifests`, with paths relative to the project root, returning
+ * the written paths. Nothing is written when routes conflict.
+ */
+export declare function rscWriteManifests(appDir: string, buildId: string, options?: NapiScanOptions | undefined | null): Array<string>
+/**
+ * Read manifests written by `rscWriteManifests` from `manifestDir`,
+ * resolving their paths against `projectRoot` and failing on a schema
+ * version mismatch or invalid content
+ */
+export declare function rscReadManifests(manifestDir: string, projectRoot: string): NapiManifests
+/** Write `.vista/types/routes.d.ts` for the app's routes and return its path */
+export declare function rscGenerateRouteTypes(appDir: string, options?: NapiScanOptions | undefined | null): string
+/** Sitemap generation options for NAPI */
+export interface NapiSitemapOptions {
+  /** Site origin, e.g. `https://acme.dev` */
+  baseUrl: string
+  /** Paths from `rscPlanStaticPaths`, used to list dynamic routes */
+  staticPaths?: Array<NapiStaticPath>
+  /** Defaults to and is capped at 50000 */
+  maxUrlsPerFile?: number
+  /** Paths listed as `Disallow` in robots.txt */
+  disallow?: Array<string>
+  /** Directory to write sitemap.xml and robots.txt into */
+  outDir?: string
+}
+/** Generated sitemap or robots.txt file */
+export interface NapiSitemapFile {
+  name: string
+  content: string
+}
+/** Route left out of the sitemap */
+export interface NapiOmittedRoute {
+  pattern: string
+  reason: string
+}
+/** Sitemap generation result for NAPI */
+export interface NapiSitemapOutput {
+  /** `sitemap.xml`, or a sitemap index of that name followed by `sitemap-N.xml` files */
+  files: Array<NapiSitemapFile>
+  robotsTxt: string
+  omitted: Array<NapiOmittedRoute>
+  diagnostics: Array<NapiDiagnostic>
+  /** Files written when `outDir` is set */
+  written: Array<string>
+}
+/**
+ * Generate sitemap.xml and robots.txt for the app's routes, writing them
+ * to `outDir` when given
+ */
+export declare function rscGenerateSitemap(appDir: string, options: NapiSitemapOptions, scanOptions?: NapiScanOptions | undefined | null): NapiSitemapOutput
 /** Client reference for NAPI */
 export interface NapiClientReference {
   id: string
@@ -172,3 +478,15 @@ export declare function rscPrerenderComponent(filePath: string): NapiPrerendered
  * Returns a map of component_id -> placeholder_html
  */
 export declare function rscPrerenderAllComponents(appDir: string): Record<string, NapiPrerenderedComponent>
+/** Native app directory watcher */
+export declare class RscWatcher {
+  /** Stop watching. Safe to call more than once. */
+  close(): void
+}
+/** Compiled URL route matcher (static > dynamic > catch-all > optional catch-all) */
+export declare class RscRouteMatcher {
+  /** Compile server manifest routes. Slot and intercepting routes are never matched. */
+  constructor(routes: Array<NapiRouteEntry>)
+  /** Match a request pathname (query string and trailing slash ignored) */
+  match(pathname: string): NapiRouteMatch | null
+}
@@ -310,20 +310,33 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { getFrameworkIdentity, verifyIntegrity, isClientComponent, analyzeClientDirective, getRouteTree, version, hasMetadataExport, hasGenerateMetadata, analyzeMetadata, rscScanApp, rscGenerateClientManifest, rscGenerateServerManifest, rscGenerateMountId, rscResetMountCounter, rscPrerenderComponent, rscPrerenderAllComponents } = nativeBinding
+const { getFrameworkIdentity, verifyIntegrity, isClientComponent, analyzeClientDirective, getRouteTree, validateAppStructure, version, hasMetadataExport, hasGenerateMetadata, analyzeMetadata, renderMetadataHead, rscScanApp, rscScanAppIncremental, RscWatcher, rscWatchApp, rscGenerateClientManifest, rscGenerateServerActionManifest, rscGenerateServerManifest, rscPlanStaticPaths, rscGenerateManifests, rscWriteManifests, rscReadManifests, rscGenerateRouteTypes, rscGenerateSitemap, RscRouteMatcher, rscGenerateMountId, rscResetMountCounter, rscPrerenderComponent, rscPrerenderAllComponents } = nativeBinding
 
 module.exports.getFrameworkIdentity = getFrameworkIdentity
 module.exports.verifyIntegrity = verifyIntegrity
 module.exports.isClientComponent = isClientComponent
 module.exports.analyzeClientDirective = analyzeClientDirective
 module.exports.getRouteTree = getRouteTree
+module.exports.validateAppStructure = validateAppStructure
 module.exports.version = version
 module.exports.hasMetadataExport = hasMetadataExport
 module.exports.hasGenerateMetadata = hasGenerateMetadata
 module.exports.analyzeMetadata = analyzeMetadata
+module.exports.renderMetadataHead = renderMetadataHead
 module.exports.rscScanApp = rscScanApp
+module.exports.rscScanAppIncremental = rscScanAppIncremental
+module.exports.RscWatcher = RscWatcher
+module.exports.rscWatchApp = rscWatchApp
 module.exports.rscGenerateClientManifest = rscGenerateClientManifest
+module.exports.rscGenerateServerActionManifest = rscGenerateServerActionManifest
 module.exports.rscGenerateServerManifest = rscGenerateServerManifest
+module.exports.rscPlanStaticPaths = rscPlanStaticPaths
+module.exports.rscGenerateManifests = rscGenerateManifests
+module.exports.rscWriteManifests = rscWriteManifests
+module.exports.rscReadManifests = rscReadManifests
+module.exports.rscGenerateRouteTypes = rscGenerateRouteTypes
+module.exports.rscGenerateSitemap = rscGenerateSitemap
+module.exports.RscRouteMatcher = RscRouteMatcher
 module.exports.rscGenerateMountId = rscGenerateMountId
 module.exports.rscResetMountCounter = rscResetMountCounter
 module.exports.rscPrerenderComponent = rscPrerenderComponent
@@ -1,6 +1,12 @@
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{
+    ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+};
+use napi::JsFunction;
 use napi_derive::napi;
-use vista_transforms::{detect_client_directive_fast, has_client_directive};
+use vista_transforms::{analyze_source, detect_client_directive_fast, has_client_directive};
 use vista_transforms::naming;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
 // ============================================================================
@@ -87,7 +93,13 @@ pub struct RouteNode {
     pub loading_path: Option<String>, // loading.tsx
     pub error_path: Option<String>, // error.tsx
     pub not_found_path: Option<String>, // not-found.tsx
+    pub default_path: Option<String>, // default.tsx (unmatched slot fallback)
+    pub interception: Option<String>, // "(.)", "(..)", "(..)(..)" or "(...)"
     pub children: Vec<RouteNode>,
+    /// @slot folders, rendered through this node's layout props
+    pub parallel_routes: Vec<RouteNode>,
+    /// (.)segment folders, matched only on client-side navigations
+    pub intercepting_routes: Vec<RouteNode>,
 }
 
 #[napi]
@@ -99,13 +111,25 @@ pub fn get_route_tree(app_dir: String) -> RouteNode {
 fn build_route_node(dir_path: &Path, base_path: &Path) -> RouteNode {
     let dir_name = dir_path.file_name()
         .map(|n| n.to_string_lossy().to_string())
-        .unwrap_or_else(|| "".to_string());
+        .unwrap_or_default();
 
     let mut segment = dir_name.clone();
     let mut kind = "static".to_string();
+    let mut interception = None;
 
+    // Handle parallel route slots @slot - don't contribute to URL
+    if let Some(slot) = dir_name.strip_prefix('@').filter(|s| !s.is_empty()) {
+        kind = "slot".to_string();
+        segment = slot.to_string();
+    }
+    // Handle intercepting routes (.)segment, (..)segment, (...)segment
+    else if let Some((marker, rest)) = vista_transforms::rsc::split_interception(&dir_name) {
+        kind = "intercepting".to_string();
+        segment = rest.to_string();
+        interception = Some(marker.to_string());
+    }
     // Handle route groups (folder) - doesn't contribute to URL
-    if segment.starts_with('(') && segment.ends_with(')') {
+    else if segment.starts_with('(') && segment.ends_with(')') {
         kind = "group".to_string();
         segment = "".to_string(); // Groups don't add to the path
     }
@@ -135,7 +159,11 @@ fn build_route_node(dir_path: &Path, base_path: &Path) -> RouteNode {
         loading_path: None,
         error_path: None,
         not_found_path: None,
+        default_path: None,
+        interception,
         children: Vec::new(),
+        parallel_routes: Vec::new(),
+        intercepting_routes: Vec::new(),
     };
 
     if let Ok(entries) = std::fs::read_dir(dir_path) {
@@ -148,8 +176,18 @@ fn build_route_node(dir_path: &Path, base_path: &Path) -> RouteNode {
                 if !file_name.starts_with('.') && file_name != "node_modules" && file_name != "[not-found]" {
                     let child_node = build_route_node(&path, base_path);
                     // Only add child if it has some content or children
-                    if child_node.index_path.is_some() || child_node.layout_path.is_some() || !child_node.children.is_empty() {
-                         node.children.push(child_node);
+                    let has_content = child_node.index_path.is_some()
+                        || child_node.layout_path.is_some()
+                        || child_node.default_path.is_some()
+                        || !child_node.children.is_empty()
+                        || !child_node.parallel_routes.is_empty()
+                        || !child_node.intercepting_routes.is_empty();
+                    if has_content {
+                        match child_node.kind.as_str() {
+                            "slot" => node.parallel_routes.push(child_node),
+                            "intercepting" => node.intercepting_routes.push(child_node),
+                            _ => node.children.push(child_node),
+                        }
                     }
                 }
             } else {
@@ -168,6 +206,7 @@ fn build_route_node(dir_path: &Path, base_path: &Path) -> RouteNode {
                     "loading" => node.loading_path = Some(full_path),
                     "error" => node.error_path = Some(full_path),
                     "not-found" => node.not_found_path = Some(full_path),
+                    "default" => node.default_path = Some(full_path),
                     _ => {}
                 }
             }
@@ -183,10 +222,76 @@ fn build_route_node(dir_path: &Path, base_path: &Path) -> RouteNode {
         }
         a.segment.cmp(&b.segment)
     });
+    node.parallel_routes.sort_by(|a, b| a.segment.cmp(&b.segment));
+    node.intercepting_routes.sort_by(|a, b| a.segment.cmp(&b.segment));
 
     node
 }
 
+/// Route graph node for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiRouteGraphNode {
+    pub segment: String,
+    pub pattern: String,
+    /// "static", "dynamic", "catch-all", "optional-catch-all", "group", "slot",
+    /// "intercepting" or "reserved-internal"
+    pub kind: String,
+    pub has_page: bool,
+    pub has_layout: bool,
+    pub children: Vec<NapiRouteGraphNode>,
+    pub file_path: Option<String>,
+}
+
+/// App structure validation result for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiStructureValidation {
+    /// "ok" or "error"
+    pub state: String,
+    pub issues: Vec<NapiDiagnostic>,
+    pub route_graph: Vec<NapiRouteGraphNode>,
+    pub timestamp: i64,
+}
+
+fn convert_route_graph_node(node: &vista_transforms::rsc::RouteGraphNode) -> NapiRouteGraphNode {
+    NapiRouteGraphNode {
+        segment: node.segment.clone(),
+        pattern: node.pattern.clone(),
+        kind: node.kind.as_str().to_string(),
+        has_page: node.has_page,
+        has_layout: node.has_layout,
+        children: node.children.iter().map(convert_route_graph_node).collect(),
+        file_path: node.file_path.clone(),
+    }
+}
+
+/// Validate the app directory structure and build its route graph
+#[napi]
+pub fn validate_app_structure(
+    app_dir: String,
+    not_found_route: Option<String>,
+    options: Option<NapiScanOptions>,
+) -> NapiStructureValidation {
+    use vista_transforms::rsc::{StructureOptions, StructureState};
+
+    let options = StructureOptions {
+        not_found_route,
+        scan: convert_scan_options(options),
+    };
+    let result = vista_transforms::rsc::validate_app_structure(&app_dir, &options);
+
+    NapiStructureValidation {
+        state: match result.state {
+            StructureState::Ok => "ok",
+            StructureState::Error => "error",
+        }.to_string(),
+        issues: result.issues.iter().map(convert_diagnostic).collect(),
+        route_graph: result.route_graph.iter().map(convert_route_graph_node).collect(),
+        timestamp: result.timestamp as i64,
+    }
+}
+
 /// Version of vista-napi
 #[napi]
 pub fn version() -> String {
@@ -198,20 +303,17 @@ pub fn version() -> String {
 // ============================================================================
 
 /// Check if source file has a static metadata export
-/// Looks for: `export const metadata` or `export const metadata:`
+/// Looks for an exported `metadata` binding in the parsed module
 #[napi]
 pub fn has_metadata_export(source: String) -> bool {
-    // Simple regex-like pattern matching
-    source.contains("export const metadata") || source.contains("export let metadata")
+    analyze_source(&source).has_metadata
 }
 
 /// Check if source file has generateMetadata function
-/// Looks for: `export function generateMetadata` or `export async function generateMetadata`
+/// Looks for an exported `generateMetadata` binding in the parsed module
 #[napi]
 pub fn has_generate_metadata(source: String) -> bool {
-    source.contains("export function generateMetadata") || 
-    source.contains("export async function generateMetadata") ||
-    source.contains("export const generateMetadata")
+    analyze_source(&source).has_generate_metadata
 }
 
 /// Metadata information extracted from a source file
@@ -220,21 +322,45 @@ pub fn has_generate_metadata(source: String) -> bool {
 pub struct MetadataInfo {
     pub has_static_metadata: bool,
     pub has_generate_metadata: bool,
+    /// Literal parts of an object `metadata` export
+    pub static_metadata: Option<serde_json::Value>,
 }
 
 /// Analyze source file for metadata exports
 #[napi]
 pub fn analyze_metadata(source: String) -> MetadataInfo {
+    let analysis = analyze_source(&source);
     MetadataInfo {
-        has_static_metadata: has_metadata_export(source.clone()),
-        has_generate_metadata: has_generate_metadata(source),
+        has_static_metadata: analysis.has_metadata,
+        has_generate_metadata: analysis.has_generate_metadata,
+        static_metadata: analysis.static_metadata,
     }
 }
 
+/// Render head HTML from a layout chain's metadata (root layout first, page last).
+/// Title templates are inherited through the chain.
+#[napi(ts_args_type = "metadata: Array<Record<string, any>>")]
+pub fn render_metadata_head(metadata: Vec<serde_json::Value>) -> String {
+    vista_transforms::render_metadata_head(&metadata)
+}
+
 // ============================================================================
 // RSC (React Server Components) Functions
 // ============================================================================
 
+/// Export table entry for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiExportEntry {
+    /// Exported name ("default" for the default export, "*" for star exports)
+    pub name: String,
+    /// "local", "re-export", "namespace" or "star"
+    pub kind: String,
+    pub local: Option<String>,
+    pub source: Option<String>,
+    pub line: u32,
+}
+
 /// Scanned component info for NAPI
 #[napi(object)]
 #[derive(Clone, Debug)]
@@ -243,20 +369,90 @@ pub struct NapiScannedComponent {
     pub relative_path: String,
     pub is_client: bool,
     pub directive_line: u32,
+    pub is_server: bool,
+    pub server_actions: Vec<String>,
     pub component_type: String,
     pub exports: Vec<String>,
+    pub export_table: Vec<NapiExportEntry>,
     pub client_hooks_used: Vec<String>,
     pub has_metadata: bool,
     pub has_generate_metadata: bool,
+    pub has_generate_static_params: bool,
+}
+
+/// Client hook/API call or event handler prop location for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiClientApiUsage {
+    pub name: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+/// Source location for NAPI (1-indexed line and column)
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiSourceSpan {
+    pub path: String,
+    pub relative_path: String,
+    pub line: u32,
+    pub column: u32,
+    pub label: Option<String>,
+}
+
+/// Diagnostic for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiDiagnostic {
+    /// "error" or "warning"
+    pub severity: String,
+    /// Stable code, e.g. "CLIENT_API_IN_SERVER_COMPONENT"
+    pub code: String,
+    pub message: String,
+    pub primary_span: Option<NapiSourceSpan>,
+    pub secondary_spans: Vec<NapiSourceSpan>,
+    /// Source excerpt with a caret under the primary column
+    pub code_frame: Option<String>,
+    /// Suggested fix
+    pub fix: Option<String>,
+}
+
+fn convert_span(span: &vista_transforms::SourceSpan) -> NapiSourceSpan {
+    NapiSourceSpan {
+        path: span.path.clone(),
+        relative_path: span.relative_path.clone(),
+        line: span.line as u32,
+        column: span.column as u32,
+        label: span.label.clone(),
+    }
+}
+
+fn convert_diagnostic(diagnostic: &vista_transforms::Diagnostic) -> NapiDiagnostic {
+    NapiDiagnostic {
+        severity: diagnostic.severity.as_str().to_string(),
+        code: diagnostic.code.clone(),
+        message: diagnostic.message.clone(),
+        primary_span: diagnostic.primary_span.as_ref().map(convert_span),
+        secondary_spans: diagnostic.secondary_spans.iter().map(convert_span).collect(),
+        code_frame: diagnostic.code_frame.clone(),
+        fix: diagnostic.fix.clone(),
+    }
 }
 
 /// Server component error for NAPI
 #[napi(object)]
 #[derive(Clone, Debug)]
 pub struct NapiServerComponentError {
+    /// "parse-error", "directive-conflict", "server-action-in-client-component",
+    /// "client-api-in-server-component", "server-only-in-client", "client-only-in-server"
+    /// or "invalid-segment-config"
+    pub kind: String,
     pub file: String,
     pub message: String,
     pub hooks: Vec<String>,
+    pub usages: Vec<NapiClientApiUsage>,
+    /// Import chain to the module using client-only APIs (empty for direct use)
+    pub import_chain: Vec<String>,
 }
 
 /// Scan result for NAPI
@@ -268,7 +464,10 @@ pub struct NapiScanResult {
     pub pages: Vec<NapiScannedComponent>,
     pub layouts: Vec<NapiScannedComponent>,
     pub api_routes: Vec<NapiScannedComponent>,
+    pub server_action_modules: Vec<NapiScannedComponent>,
     pub errors: Vec<NapiServerComponentError>,
+    /// `errors` with stable codes, source spans and code frames
+    pub diagnostics: Vec<NapiDiagnostic>,
     pub total_files: u32,
     pub scan_time_ms: u32,
 }
@@ -279,35 +478,238 @@ fn convert_component(c: &vista_transforms::rsc::ScannedComponent) -> NapiScanned
         relative_path: c.relative_path.clone(),
         is_client: c.is_client,
         directive_line: c.directive_line as u32,
+        is_server: c.is_server,
+        server_actions: c.server_actions.iter().map(|a| a.export_name.clone()).collect(),
         component_type: format!("{:?}", c.component_type).to_lowercase(),
         exports: c.exports.clone(),
+        export_table: c.export_table.iter().map(|e| NapiExportEntry {
+            name: e.name.clone(),
+            kind: match e.kind {
+                vista_transforms::ExportKind::Local => "local",
+                vista_transforms::ExportKind::ReExport => "re-export",
+                vista_transforms::ExportKind::Namespace => "namespace",
+                vista_transforms::ExportKind::Star => "star",
+            }.to_string(),
+            local: e.local.clone(),
+            source: e.source.clone(),
+            line: e.line as u32,
+        }).collect(),
         client_hooks_used: c.client_hooks_used.clone(),
         has_metadata: c.has_metadata,
         has_generate_metadata: c.has_generate_metadata,
+        has_generate_static_params: c.has_generate_static_params,
     }
 }
 
-/// Scan app directory and classify all components (Rust-powered, blazing fast)
-#[napi]
-pub fn rsc_scan_app(app_dir: String) -> NapiScanResult {
-    let result = vista_transforms::rsc::scan_app_directory(&app_dir);
-    
+/// Scan options for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiScanOptions {
+    /// Gitignore-style patterns to skip (default: ["node_modules"])
+    pub ignore: Option<Vec<String>>,
+    /// Respect .gitignore files (default: true)
+    pub respect_gitignore: Option<bool>,
+    /// Reuse analysis from the persistent scan cache (default: true)
+    pub cache: Option<bool>,
+    /// Cache directory (default: .vista/cache in the project root)
+    pub cache_dir: Option<String>,
+}
+
+fn convert_scan_options(options: Option<NapiScanOptions>) -> vista_transforms::rsc::ScanOptions {
+    let mut scan_options = vista_transforms::rsc::ScanOptions::default();
+    if let Some(options) = options {
+        if let Some(ignore) = options.ignore {
+            scan_options.ignore = ignore;
+        }
+        if let Some(respect_gitignore) = options.respect_gitignore {
+            scan_options.respect_gitignore = respect_gitignore;
+        }
+        if let Some(cache) = options.cache {
+            scan_options.cache = cache;
+        }
+        scan_options.cache_dir = options.cache_dir;
+    }
+    scan_options
+}
+
+fn scan_error_kind(kind: vista_transforms::rsc::ScanErrorKind) -> &'static str {
+    use vista_transforms::rsc::ScanErrorKind;
+    match kind {
+        ScanErrorKind::ParseError => "parse-error",
+        ScanErrorKind::DirectiveConflict => "directive-conflict",
+        ScanErrorKind::ServerActionInClientComponent => "server-action-in-client-component",
+        ScanErrorKind::ClientApiInServerComponent => "client-api-in-server-component",
+        ScanErrorKind::ServerOnlyInClient => "server-only-in-client",
+        ScanErrorKind::ClientOnlyInServer => "client-only-in-server",
+        ScanErrorKind::InvalidSegmentConfig => "invalid-segment-config",
+    }
+}
+
+fn convert_scan_result(result: &vista_transforms::rsc::ScanResult) -> NapiScanResult {
     NapiScanResult {
         client_components: result.client_components.iter().map(convert_component).collect(),
         server_components: result.server_components.iter().map(convert_component).collect(),
         pages: result.pages.iter().map(convert_component).collect(),
         layouts: result.layouts.iter().map(convert_component).collect(),
         api_routes: result.api_routes.iter().map(convert_component).collect(),
+        server_action_modules: result.server_action_modules.iter().map(convert_component).collect(),
         errors: result.errors.iter().map(|e| NapiServerComponentError {
+            kind: scan_error_kind(e.kind).to_string(),
             file: e.file.clone(),
             message: e.message.clone(),
             hooks: e.hooks.clone(),
+            usages: e.usages.iter().map(|u| NapiClientApiUsage {
+                name: u.name.clone(),
+                line: u.line as u32,
+                column: u.column as u32,
+            }).collect(),
+            import_chain: e.import_chain.clone(),
         }).collect(),
+        diagnostics: result.diagnostics.iter().map(convert_diagnostic).collect(),
         total_files: result.total_files as u32,
         scan_time_ms: result.scan_time_ms as u32,
     }
 }
 
+/// Scan app directory and classify all components (Rust-powered, blazing fast)
+#[napi]
+pub fn rsc_scan_app(app_dir: String, options: Option<NapiScanOptions>) -> NapiScanResult {
+    let result = vista_transforms::rsc::scan_app_directory_with_options(
+        &app_dir,
+        &convert_scan_options(options),
+    );
+    
+    convert_scan_result(&result)
+}
+
+/// Files added, changed or removed since the previous scan
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiScanDelta {
+    pub added: Vec<String>,
+    pub changed: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// Incremental scan result for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiIncrementalScan {
+    pub result: NapiScanResult,
+    pub delta: NapiScanDelta,
+}
+
+/// Scan app directory through the persistent scan cache and report what changed
+#[napi]
+pub fn rsc_scan_app_incremental(app_dir: String, options: Option<NapiScanOptions>) -> NapiIncrementalScan {
+    let (result, delta) = vista_transforms::rsc::scan_app_directory_incremental(
+        &app_dir,
+        &convert_scan_options(options),
+    );
+    
+    NapiIncrementalScan {
+        result: convert_scan_result(&result),
+        delta: NapiScanDelta {
+            added: delta.added,
+            changed: delta.changed,
+            removed: delta.removed,
+        },
+    }
+}
+
+/// File change event for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiWatchEvent {
+    /// "add", "change" or "remove"
+    pub kind: String,
+    pub absolute_path: String,
+    pub relative_path: String,
+}
+
+/// Watch options for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiWatchOptions {
+    /// Gitignore-style patterns to skip (default: ["node_modules"])
+    pub ignore: Option<Vec<String>>,
+    /// Respect .gitignore files (default: true)
+    pub respect_gitignore: Option<bool>,
+    /// Files and directories to watch next to the app directory
+    pub extra_paths: Option<Vec<String>>,
+    /// Extensions of reported files, without the dot (default: ["ts", "tsx", "js", "jsx"])
+    pub extensions: Option<Vec<String>>,
+    /// Quiet period before a batch is delivered (default: 120)
+    pub debounce_ms: Option<u32>,
+    /// Longest a batch waits after its first change (default: 1000)
+    pub max_wait_ms: Option<u32>,
+}
+
+/// Native app directory watcher
+#[napi]
+pub struct RscWatcher {
+    inner: Option<vista_transforms::rsc::AppWatcher>,
+}
+
+#[napi]
+impl RscWatcher {
+    /// Stop watching. Safe to call more than once.
+    #[napi]
+    pub fn close(&mut self) {
+        if let Some(watcher) = self.inner.take() {
+            watcher.close();
+        }
+    }
+}
+
+/// Watch the app directory (and any extra paths) and call `callback` with
+/// debounced, filtered change batches
+#[napi(ts_args_type = "appDir: string, callback: (events: Array<NapiWatchEvent>) => void, options?: NapiWatchOptions | undefined | null")]
+pub fn rsc_watch_app(
+    app_dir: String,
+    callback: JsFunction,
+    options: Option<NapiWatchOptions>,
+) -> Result<RscWatcher> {
+    let mut watch_options = vista_transforms::rsc::WatchOptions::default();
+    if let Some(options) = options {
+        watch_options.scan = convert_scan_options(Some(NapiScanOptions {
+            ignore: options.ignore,
+            respect_gitignore: options.respect_gitignore,
+            cache: None,
+            cache_dir: None,
+        }));
+        if let Some(extra_paths) = options.extra_paths {
+            watch_options.extra_paths = extra_paths;
+        }
+        if let Some(extensions) = options.extensions {
+            watch_options.extensions = extensions;
+        }
+        if let Some(debounce_ms) = options.debounce_ms {
+            watch_options.debounce_ms = debounce_ms as u64;
+        }
+        if let Some(max_wait_ms) = options.max_wait_ms {
+            watch_options.max_wait_ms = max_wait_ms as u64;
+        }
+    }
+    
+    let tsfn: ThreadsafeFunction<Vec<NapiWatchEvent>, ErrorStrategy::Fatal> = callback
+        .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<Vec<NapiWatchEvent>>| {
+            Ok(vec![ctx.value])
+        })?;
+    
+    let watcher = vista_transforms::rsc::AppWatcher::start(&app_dir, &watch_options, move |batch| {
+        let events = batch.into_iter().map(|e| NapiWatchEvent {
+            kind: e.kind.as_str().to_string(),
+            absolute_path: e.absolute_path,
+            relative_path: e.relative_path,
+        }).collect();
+        tsfn.call(events, ThreadsafeFunctionCallMode::NonBlocking);
+    })
+    .map_err(|e| Error::from_reason(format!("Failed to watch {app_dir}: {e}")))?;
+    
+    Ok(RscWatcher { inner: Some(watcher) })
+}
+
 /// Client module entry for NAPI
 #[napi(object)]
 #[derive(Clone, Debug)]
@@ -331,8 +733,10 @@ pub struct NapiClientManifest {
 /// Generate client manifest (Rust-powered)
 #[napi]
 pub fn rsc_generate_client_manifest(app_dir: String, build_id: String) -> NapiClientManifest {
-    let manifest = vista_transforms::rsc::generate_client_manifest(&app_dir, &build_id);
-    
+    convert_client_manifest(vista_transforms::rsc::generate_client_manifest(&app_dir, &build_id))
+}
+
+fn convert_client_manifest(manifest: vista_transforms::rsc::ClientManifest) -> NapiClientManifest {
     NapiClientManifest {
         build_id: manifest.build_id,
         client_modules: manifest.client_modules.values().map(|e| NapiClientModuleEntry {
@@ -346,6 +750,46 @@ pub fn rsc_generate_client_manifest(app_dir: String, build_id: String) -> NapiCl
     }
 }
 
+/// Server action entry for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiServerActionEntry {
+    pub id: String,
+    pub path: String,
+    pub absolute_path: String,
+    pub module_id: String,
+    pub export_name: String,
+    pub inline: bool,
+}
+
+/// Server action manifest for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiServerActionManifest {
+    pub build_id: String,
+    pub actions: Vec<NapiServerActionEntry>,
+}
+
+/// Generate server action manifest (Rust-powered)
+#[napi]
+pub fn rsc_generate_server_action_manifest(app_dir: String, build_id: String) -> NapiServerActionManifest {
+    convert_server_action_manifest(vista_transforms::rsc::generate_server_action_manifest(&app_dir, &build_id))
+}
+
+fn convert_server_action_manifest(manifest: vista_transforms::rsc::ServerActionManifest) -> NapiServerActionManifest {
+    NapiServerActionManifest {
+        build_id: manifest.build_id,
+        actions: manifest.actions.values().map(|a| NapiServerActionEntry {
+            id: a.id.clone(),
+            path: a.path.clone(),
+            absolute_path: a.absolute_path.clone(),
+            module_id: a.module_id.clone(),
+            export_name: a.export_name.clone(),
+            inline: a.inline,
+        }).collect(),
+    }
+}
+
 /// Route entry for NAPI
 #[napi(object)]
 #[derive(Clone, Debug)]
@@ -355,7 +799,68 @@ pub struct NapiRouteEntry {
     pub layout_paths: Vec<String>,
     pub loading_path: Option<String>,
     pub error_path: Option<String>,
+    pub not_found_path: Option<String>,
     pub route_type: String,
+    /// `@slot` this page renders into (slot pages are never matched on their own)
+    pub slot: Option<String>,
+    /// Set for intercepting routes (client-side navigations only)
+    pub interception: Option<NapiRouteInterception>,
+    pub slots: Vec<NapiRouteSlot>,
+    pub boundaries: NapiRouteBoundaries,
+    pub has_generate_static_params: bool,
+    /// Effective segment config: the shortest `revalidate` across the
+    /// layouts and page, every other option from the innermost segment that sets it
+    pub segment_config: NapiSegmentConfig,
+}
+
+/// Route segment config for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiSegmentConfig {
+    pub dynamic: Option<String>,
+    pub dynamic_params: Option<bool>,
+    /// `false` caches indefinitely, otherwise seconds
+    pub revalidate: Option<Either<bool, i64>>,
+    pub fetch_cache: Option<String>,
+    pub runtime: Option<String>,
+    pub preferred_region: Option<Vec<String>>,
+    pub max_duration: Option<i64>,
+}
+
+/// Per-segment boundary files for NAPI, outermost first. All lists are aligned
+/// with `segments`; a null entry means the segment has no such file.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiRouteBoundaries {
+    /// Segment directories relative to the app directory ("" is the root)
+    pub segments: Vec<String>,
+    pub layouts: Vec<Option<String>>,
+    pub templates: Vec<Option<String>>,
+    pub loading: Vec<Option<String>>,
+    pub errors: Vec<Option<String>>,
+    pub not_found: Vec<Option<String>>,
+}
+
+/// Intercepting route info for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiRouteInterception {
+    /// "(.)", "(..)", "(..)(..)" or "(...)"
+    pub marker: String,
+    /// Pattern of the level navigations are intercepted from
+    pub source_pattern: String,
+}
+
+/// Parallel route slot for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiRouteSlot {
+    /// Slot name without "@", also the layout prop name
+    pub name: String,
+    pub layout_path: Option<String>,
+    pub page_path: Option<String>,
+    /// Rendered when no slot page matches
+    pub default_path: Option<String>,
 }
 
 /// Server module entry for NAPI
@@ -368,6 +873,9 @@ pub struct NapiServerModuleEntry {
     pub component_type: String,
     pub has_metadata: bool,
     pub has_generate_metadata: bool,
+    /// Literal parts of the `metadata` export
+    pub static_metadata: Option<serde_json::Value>,
+    pub client_dependencies: Vec<String>,
 }
 
 /// Server manifest for NAPI
@@ -377,13 +885,22 @@ pub struct NapiServerManifest {
     pub build_id: String,
     pub server_modules: Vec<NapiServerModuleEntry>,
     pub routes: Vec<NapiRouteEntry>,
+    pub diagnostics: Vec<NapiDiagnostic>,
 }
 
-/// Generate server manifest (Rust-powered)
+/// Generate server manifest (Rust-powered), failing when routes conflict
 #[napi]
-pub fn rsc_generate_server_manifest(app_dir: String, build_id: String) -> NapiServerManifest {
+pub fn rsc_generate_server_manifest(app_dir: String, build_id: String) -> Result<NapiServerManifest> {
     let manifest = vista_transforms::rsc::generate_server_manifest(&app_dir, &build_id);
-    
+    manifest.check_route_conflicts().map_err(route_conflict_error)?;
+    Ok(convert_server_manifest(manifest))
+}
+
+fn route_conflict_error(e: vista_transforms::rsc::RouteConflictError) -> Error {
+    Error::from_reason(format!("Cannot build routes, found {e}"))
+}
+
+fn convert_server_manifest(manifest: vista_transforms::rsc::ServerManifest) -> NapiServerManifest {
     NapiServerManifest {
         build_id: manifest.build_id,
         server_modules: manifest.server_modules.values().map(|e| NapiServerModuleEntry {
@@ -393,18 +910,404 @@ pub fn rsc_generate_server_manifest(app_dir: String, build_id: String) -> NapiSe
             component_type: e.component_type.clone(),
             has_metadata: e.has_metadata,
             has_generate_metadata: e.has_generate_metadata,
+            static_metadata: e.static_metadata.clone(),
+            client_dependencies: e.client_dependencies.clone(),
+        }).collect(),
+        routes: manifest.routes.iter().map(convert_route_entry).collect(),
+        diagnostics: manifest.diagnostics.iter().map(convert_diagnostic).collect(),
+    }
+}
+
+fn convert_route_entry(r: &vista_transforms::rsc::RouteEntry) -> NapiRouteEntry {
+    NapiRouteEntry {
+        pattern: r.pattern.clone(),
+        page_path: r.page_path.clone(),
+        layout_paths: r.layout_paths.clone(),
+        loading_path: r.loading_path.clone(),
+        error_path: r.error_path.clone(),
+        not_found_path: r.not_found_path.clone(),
+        route_type: r.route_type.clone(),
+        slot: r.slot.clone(),
+        interception: r.interception.as_ref().map(|i| NapiRouteInterception {
+            marker: i.marker.clone(),
+            source_pattern: i.source_pattern.clone(),
+        }),
+        slots: r.slots.iter().map(|slot| NapiRouteSlot {
+            name: slot.name.clone(),
+            layout_path: slot.layout_path.clone(),
+            page_path: slot.page_path.clone(),
+            default_path: slot.default_path.clone(),
         }).collect(),
-        routes: manifest.routes.iter().map(|r| NapiRouteEntry {
-            pattern: r.pattern.clone(),
-            page_path: r.page_path.clone(),
-            layout_paths: r.layout_paths.clone(),
-            loading_path: r.loading_path.clone(),
-            error_path: r.error_path.clone(),
-            route_type: r.route_type.clone(),
+        boundaries: NapiRouteBoundaries {
+            segments: r.boundaries.segments.clone(),
+            layouts: r.boundaries.layouts.clone(),
+            templates: r.boundaries.templates.clone(),
+            loading: r.boundaries.loading.clone(),
+            errors: r.boundaries.errors.clone(),
+            not_found: r.boundaries.not_found.clone(),
+        },
+        has_generate_static_params: r.has_generate_static_params,
+        segment_config: convert_segment_config(&r.segment_config),
+    }
+}
+
+fn convert_segment_config(config: &vista_transforms::rsc::SegmentConfig) -> NapiSegmentConfig {
+    use vista_transforms::rsc::Revalidate;
+
+    NapiSegmentConfig {
+        dynamic: config.dynamic.clone(),
+        dynamic_params: config.dynamic_params,
+        revalidate: config.revalidate.map(|revalidate| match revalidate {
+            Revalidate::Never(never) => Either::A(never),
+            Revalidate::Seconds(seconds) => Either::B(seconds as i64),
+        }),
+        fetch_cache: config.fetch_cache.clone(),
+        runtime: config.runtime.clone(),
+        preferred_region: config.preferred_region.clone(),
+        max_duration: config.max_duration.map(|seconds| seconds as i64),
+    }
+}
+
+fn segment_config_from_napi(config: NapiSegmentConfig) -> vista_transforms::rsc::SegmentConfig {
+    use vista_transforms::rsc::Revalidate;
+
+    vista_transforms::rsc::SegmentConfig {
+        dynamic: config.dynamic,
+        dynamic_params: config.dynamic_params,
+        revalidate: config.revalidate.map(|revalidate| match revalidate {
+            Either::A(never) => Revalidate::Never(never),
+            Either::B(seconds) => Revalidate::Seconds(seconds.max(0) as u64),
+        }),
+        fetch_cache: config.fetch_cache,
+        runtime: config.runtime,
+        preferred_region: config.preferred_region,
+        max_duration: config.max_duration.map(|seconds| seconds.max(0) as u64),
+    }
+}
+
+fn route_entry_from_napi(r: NapiRouteEntry) -> vista_transforms::rsc::RouteEntry {
+    vista_transforms::rsc::RouteEntry {
+        pattern: r.pattern,
+        page_path: r.page_path,
+        layout_paths: r.layout_paths,
+        loading_path: r.loading_path,
+        error_path: r.error_path,
+        not_found_path: r.not_found_path,
+        route_type: r.route_type,
+        slot: r.slot,
+        interception: r.interception.map(|i| vista_transforms::rsc::RouteInterception {
+            marker: i.marker,
+            source_pattern: i.source_pattern,
+        }),
+        slots: r.slots.into_iter().map(|slot| vista_transforms::rsc::RouteSlot {
+            name: slot.name,
+            layout_path: slot.layout_path,
+            page_path: slot.page_path,
+            default_path: slot.default_path,
         }).collect(),
+        boundaries: vista_transforms::rsc::RouteBoundaries {
+            segments: r.boundaries.segments,
+            layouts: r.boundaries.layouts,
+            templates: r.boundaries.templates,
+            loading: r.boundaries.loading,
+            errors: r.boundaries.errors,
+            not_found: r.boundaries.not_found,
+        },
+        has_generate_static_params: r.has_generate_static_params,
+        segment_config: segment_config_from_napi(r.segment_config),
     }
 }
 
+/// Matched route for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiRouteMatch {
+    /// Index into the routes the matcher was created with
+    pub index: u32,
+    pub route: NapiRouteEntry,
+    /// Decoded params; catch-alls are arrays, empty optional catch-alls are omitted
+    pub params: BTreeMap<String, Either<String, Vec<String>>>,
+}
+
+/// Compiled URL route matcher (static > dynamic > catch-all > optional catch-all)
+#[napi]
+pub struct RscRouteMatcher {
+    inner: vista_transforms::rsc::RouteMatcher,
+}
+
+#[napi]
+impl RscRouteMatcher {
+    /// Compile server manifest routes. Slot and intercepting routes are never matched.
+    #[napi(constructor)]
+    pub fn new(routes: Vec<NapiRouteEntry>) -> Self {
+        Self {
+            inner: vista_transforms::rsc::RouteMatcher::new(
+                routes.into_iter().map(route_entry_from_napi).collect(),
+            ),
+        }
+    }
+
+    /// Match a request pathname (query string and trailing slash ignored)
+    #[napi(js_name = "match")]
+    pub fn match_path(&self, pathname: String) -> Option<NapiRouteMatch> {
+        let matched = self.inner.match_path(&pathname)?;
+        Some(NapiRouteMatch {
+            index: matched.index as u32,
+            route: convert_route_entry(matched.route),
+            params: convert_route_params(matched.params),
+        })
+    }
+}
+
+fn convert_route_params(
+    params: BTreeMap<String, vista_transforms::rsc::RouteParam>,
+) -> BTreeMap<String, Either<String, Vec<String>>> {
+    use vista_transforms::rsc::RouteParam;
+
+    params.into_iter().map(|(name, value)| {
+        let value = match value {
+            RouteParam::Single(value) => Either::A(value),
+            RouteParam::Multiple(values) => Either::B(values),
+        };
+        (name, value)
+    }).collect()
+}
+
+/// Concrete path to prerender for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiStaticPath {
+    pub pattern: String,
+    /// URL path with percent-encoded params
+    pub path: String,
+    pub page_path: String,
+    pub params: BTreeMap<String, Either<String, Vec<String>>>,
+}
+
+/// Static path plan for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiStaticPathPlan {
+    pub paths: Vec<NapiStaticPath>,
+    /// Patterns with `dynamicParams = false`: params outside `paths` 404
+    pub static_only_patterns: Vec<String>,
+    pub diagnostics: Vec<NapiDiagnostic>,
+}
+
+/// Expand routes into the paths to prerender. `staticParams` maps a route
+/// pattern to what its page's `generateStaticParams` returned.
+#[napi(ts_args_type = "appDir: string, routes: Array<NapiRouteEntry>, staticParams: Record<string, Array<Record<string, string | Array<string>>>>")]
+pub fn rsc_plan_static_paths(
+    app_dir: String,
+    routes: Vec<NapiRouteEntry>,
+    static_params: HashMap<String, Vec<serde_json::Value>>,
+) -> NapiStaticPathPlan {
+    let routes: Vec<_> = routes.into_iter().map(route_entry_from_napi).collect();
+    let plan = vista_transforms::rsc::plan_static_paths(&app_dir, &routes, &static_params);
+    
+    NapiStaticPathPlan {
+        paths: plan.paths.into_iter().map(|p| NapiStaticPath {
+            pattern: p.pattern,
+            path: p.path,
+            page_path: p.page_path,
+            params: convert_route_params(p.params),
+        }).collect(),
+        static_only_patterns: plan.static_only_patterns,
+        diagnostics: plan.diagnostics.iter().map(convert_diagnostic).collect(),
+    }
+}
+
+/// All RSC manifests generated from a single scan
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiManifests {
+    pub client: NapiClientManifest,
+    pub server: NapiServerManifest,
+    pub server_actions: NapiServerActionManifest,
+}
+
+/// Generate client, server and server action manifests from one (cached)
+/// scan, failing when routes conflict
+#[napi]
+pub fn rsc_generate_manifests(
+    app_dir: String,
+    build_id: String,
+    options: Option<NapiScanOptions>,
+) -> Result<NapiManifests> {
+    let scan = vista_transforms::rsc::scan_app_directory_with_options(
+        &app_dir,
+        &convert_scan_options(options),
+    );
+    let server = vista_transforms::rsc::generate_server_manifest_from_scan(&scan, &build_id);
+    server.check_route_conflicts().map_err(route_conflict_error)?;
+    
+    Ok(NapiManifests {
+        client: convert_client_manifest(vista_transforms::rsc::generate_client_manifest_from_scan(&scan, &build_id)),
+        server: convert_server_manifest(server),
+        server_actions: convert_server_action_manifest(
+            vista_transforms::rsc::generate_server_action_manifest_from_scan(&scan, &build_id),
+        ),
+    })
+}
+
+/// Write the manifests of one (cached) scan and their JSON Schemas to
+/// `.vista/manifests`, with paths relative to the project root, returning
+/// the written paths. Nothing is written when routes conflict.
+#[napi]
+pub fn rsc_write_manifests(
+    app_dir: String,
+    build_id: String,
+    options: Option<NapiScanOptions>,
+) -> Result<Vec<String>> {
+    let scan = vista_transforms::rsc::scan_app_directory_with_options(
+        &app_dir,
+        &convert_scan_options(options),
+    );
+    let dir = vista_transforms::rsc::default_manifest_dir(Path::new(&app_dir));
+    let server = vista_transforms::rsc::generate_server_manifest_from_scan(&scan, &build_id);
+    server.check_route_conflicts().map_err(route_conflict_error)?;
+
+    vista_transforms::rsc::write_manifests(
+        &dir,
+        vista_transforms::rsc::project_root(Path::new(&app_dir)),
+        &vista_transforms::rsc::generate_client_manifest_from_scan(&scan, &build_id),
+        &server,
+        &vista_transforms::rsc::generate_server_action_manifest_from_scan(&scan, &build_id),
+    )
+    .map(|paths| paths.iter().map(|path| path.to_string_lossy().to_string()).collect())
+    .map_err(|e| Error::from_reason(format!("Failed to write manifests to {}: {e}", dir.display())))
+}
+
+/// Read manifests written by `rscWriteManifests` from `manifestDir`,
+/// resolving their paths against `projectRoot` and failing on a schema
+/// version mismatch or invalid content
+#[napi]
+pub fn rsc_read_manifests(manifest_dir: String, project_root: String) -> Result<NapiManifests> {
+    use vista_transforms::rsc::read_manifest;
+
+    let (dir, root) = (Path::new(&manifest_dir), Path::new(&project_root));
+    let to_napi_error = |e: vista_transforms::rsc::ManifestError| Error::from_reason(e.to_string());
+
+    Ok(NapiManifests {
+        client: convert_client_manifest(read_manifest(dir, root).map_err(to_napi_error)?),
+        server: convert_server_manifest(read_manifest(dir, root).map_err(to_napi_error)?),
+        server_actions: convert_server_action_manifest(read_manifest(dir, root).map_err(to_napi_error)?),
+    })
+}
+
+/// Write `.vista/types/routes.d.ts` for the app's routes and return its path
+#[napi]
+pub fn rsc_generate_route_types(app_dir: String, options: Option<NapiScanOptions>) -> Result<String> {
+    let scan = vista_transforms::rsc::scan_app_directory_with_options(
+        &app_dir,
+        &convert_scan_options(options),
+    );
+    let manifest = vista_transforms::rsc::generate_server_manifest_from_scan(&scan, "types");
+    
+    vista_transforms::rsc::generate_route_types(&app_dir, &manifest)
+        .map(|path| path.to_string_lossy().to_string())
+        .map_err(|e| Error::from_reason(format!("Failed to write route types for {app_dir}: {e}")))
+}
+
+/// Sitemap generation options for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiSitemapOptions {
+    /// Site origin, e.g. `https://acme.dev`
+    pub base_url: String,
+    /// Paths from `rscPlanStaticPaths`, used to list dynamic routes
+    pub static_paths: Option<Vec<NapiStaticPath>>,
+    /// Defaults to and is capped at 50000
+    pub max_urls_per_file: Option<u32>,
+    /// Paths listed as `Disallow` in robots.txt
+    pub disallow: Option<Vec<String>>,
+    /// Directory to write sitemap.xml and robots.txt into
+    pub out_dir: Option<String>,
+}
+
+/// Generated sitemap or robots.txt file
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiSitemapFile {
+    pub name: String,
+    pub content: String,
+}
+
+/// Route left out of the sitemap
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiOmittedRoute {
+    pub pattern: String,
+    pub reason: String,
+}
+
+/// Sitemap generation result for NAPI
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct NapiSitemapOutput {
+    /// `sitemap.xml`, or a sitemap index of that name followed by `sitemap-N.xml` files
+    pub files: Vec<NapiSitemapFile>,
+    pub robots_txt: String,
+    pub omitted: Vec<NapiOmittedRoute>,
+    pub diagnostics: Vec<NapiDiagnostic>,
+    /// Files written when `outDir` is set
+    pub written: Vec<String>,
+}
+
+/// Generate sitemap.xml and robots.txt for the app's routes, writing them
+/// to `outDir` when given
+#[napi]
+pub fn rsc_generate_sitemap(
+    app_dir: String,
+    options: NapiSitemapOptions,
+    scan_options: Option<NapiScanOptions>,
+) -> Result<NapiSitemapOutput> {
+    use vista_transforms::rsc::{RouteParam, StaticPath};
+
+    let scan = vista_transforms::rsc::scan_app_directory_with_options(
+        &app_dir,
+        &convert_scan_options(scan_options),
+    );
+    let manifest = vista_transforms::rsc::generate_server_manifest_from_scan(&scan, "sitemap");
+
+    let static_paths: Vec<StaticPath> = options.static_paths.unwrap_or_default().into_iter().map(|p| StaticPath {
+        pattern: p.pattern,
+        path: p.path,
+        page_path: p.page_path,
+        params: p.params.into_iter().map(|(name, value)| {
+            let value = match value {
+                Either::A(value) => RouteParam::Single(value),
+                Either::B(values) => RouteParam::Multiple(values),
+            };
+            (name, value)
+        }).collect(),
+    }).collect();
+
+    let mut sitemap_options = vista_transforms::rsc::SitemapOptions::new(&options.base_url);
+    if let Some(max) = options.max_urls_per_file {
+        sitemap_options.max_urls_per_file = max as usize;
+    }
+    sitemap_options.disallow = options.disallow.unwrap_or_default();
+
+    let output = vista_transforms::rsc::generate_sitemap(&manifest, &static_paths, &sitemap_options);
+    let written = match &options.out_dir {
+        Some(out_dir) => vista_transforms::rsc::write_sitemap(Path::new(out_dir), &output)
+            .map_err(|e| Error::from_reason(format!("Failed to write sitemap to {out_dir}: {e}")))?
+            .into_iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect(),
+        None => Vec::new(),
+    };
+
+    Ok(NapiSitemapOutput {
+        files: output.files.into_iter().map(|f| NapiSitemapFile { name: f.name, content: f.content }).collect(),
+        robots_txt: output.robots_txt,
+        omitted: output.omitted.into_iter().map(|o| NapiOmittedRoute { pattern: o.pattern, reason: o.reason }).collect(),
+        diagnostics: output.diagnostics.iter().map(convert_diagnostic).collect(),
+        written,
+    })
+}
+
 /// Client reference for NAPI
 #[napi(object)]
 #[derive(Clone, Debug)]
@@ -450,7 +1353,7 @@ pub fn rsc_prerender_component(file_path: String) -> Option<NapiPrerenderedCompo
 /// Pre-render all client components in an app directory
 /// Returns a map of component_id -> placeholder_html
 #[napi]
-pub fn rsc_prerender_all_components(app_dir: String) -> std::collections::HashMap<String, NapiPrerenderedComponent> {
+pub fn rsc_prerender_all_components(app_dir: String) -> BTreeMap<String, NapiPrerenderedComponent> {
     vista_transforms::rsc::prerender_all_client_components(&app_dir)
         .into_iter()
         .map(|(k, v)| (k, NapiPrerenderedComponent {
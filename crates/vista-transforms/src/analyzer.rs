@@ -0,0 +1,1432 @@
+//! Module Analyzer
+//!
+//! Parses TS/TSX/JS/JSX sources with SWC and extracts the facts the RSC
+//! scanner relies on: directives, exported names, imports, server actions
+//! and client-only API usage.
+//! Working on the AST means comments, strings and template literals can no
+//! longer cause false positives.
+
+use std::path::Path;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+use swc_core::{
+    common::{sync::Lrc, FileName, SourceMap, Span, Spanned},
+    ecma::{
+        ast::*,
+        parser::{parse_file_as_expr, parse_file_as_module, EsSyntax, Syntax, TsSyntax},
+        visit::{Visit, VisitWith},
+    },
+};
+
+/// Client-only hooks that require 'use client' directive
+pub const CLIENT_HOOKS: &[&str] = &[
+    "useState", "useEffect", "useLayoutEffect", "useReducer", "useRef",
+    "useImperativeHandle", "useCallback", "useMemo", "useContext",
+    "useDebugValue", "useDeferredValue", "useTransition", "useId",
+    "useSyncExternalStore", "useInsertionEffect",
+];
+
+/// Client-only APIs
+pub const CLIENT_APIS: &[&str] = &[
+    "createContext", "forwardRef", "memo", "lazy", "startTransition",
+    "useFormStatus", "useFormState", "useOptimistic",
+];
+
+/// JSX attributes that only make sense on the client
+const EVENT_HANDLER_ATTRS: &[&str] = &["onClick", "onChange", "onSubmit", "onFocus"];
+
+/// Label used for event handler props in `client_hooks_used`
+pub const EVENT_HANDLERS_LABEL: &str = "event handlers";
+
+/// An exported server action
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerActionExport {
+    /// Export name (`"default"` for the default export)
+    pub export_name: String,
+    /// Line of the exported declaration
+    pub line: usize,
+    /// Marked by a function-level `'use server'` directive rather than the file-level one
+    pub inline: bool,
+}
+
+/// How an import pulls in another module
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImportKind {
+    /// `import ... from 'x'` or `import 'x'`
+    Static,
+    /// `export ... from 'x'`
+    ReExport,
+    /// `import('x')`
+    Dynamic,
+}
+
+/// A runtime import of another module (type-only imports are skipped)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportRecord {
+    /// Module specifier as written
+    pub specifier: String,
+    pub kind: ImportKind,
+    /// Line of the import
+    pub line: usize,
+}
+
+/// A call to a client-only hook/API, or a client-only JSX prop
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientApiUsage {
+    /// Hook/API name, or the event handler prop name
+    pub name: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// How a name is exported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportKind {
+    /// Declared or bound in this module
+    Local,
+    /// `export { a as b } from 'x'`
+    ReExport,
+    /// `export * as ns from 'x'`
+    Namespace,
+    /// `export * from 'x'` (names come from the source module)
+    Star,
+}
+
+/// An entry in a module's export table (type-only exports are skipped)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportEntry {
+    /// Exported name (`"default"` for the default export, `"*"` for star exports)
+    pub name: String,
+    pub kind: ExportKind,
+    /// Local binding, or the imported name for re-exports
+    pub local: Option<String>,
+    /// Source module specifier for re-exports
+    pub source: Option<String>,
+    /// Line of the export
+    pub line: usize,
+}
+
+/// Route segment config options a page or layout can export
+pub const SEGMENT_CONFIG_KEYS: &[&str] = &[
+    "dynamic", "dynamicParams", "revalidate", "fetchCache", "runtime",
+    "preferredRegion", "maxDuration",
+];
+
+/// An exported route segment config option (`export const revalidate = 60`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SegmentConfigExport {
+    /// Option name, one of `SEGMENT_CONFIG_KEYS`
+    pub name: String,
+    /// Literal value, `None` when it would need evaluation
+    pub value: Option<serde_json::Value>,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A part of `export const metadata` that can't be evaluated statically
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetadataIssue {
+    /// Property path, e.g. `metadata.openGraph.images[0]`
+    pub path: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Facts extracted from a single module
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModuleAnalysis {
+    /// Module starts with a `'use client'` directive
+    pub is_client: bool,
+    /// Line number of the directive (0 if not client)
+    pub directive_line: usize,
+    /// Module starts with a `'use server'` directive
+    pub is_server: bool,
+    /// Exported server actions (file-level or function-level `'use server'`)
+    pub server_actions: Vec<ServerActionExport>,
+    /// Exported names, in source order (`"default"` for the default export).
+    /// Names behind `export *` are not included.
+    pub exports: Vec<String>,
+    /// Full export table, in source order
+    pub export_table: Vec<ExportEntry>,
+    /// Local name of a named default function/class export
+    pub default_export_name: Option<String>,
+    /// Runtime imports, static ones first, in source order
+    pub imports: Vec<ImportRecord>,
+    /// Client hooks/APIs used, in `CLIENT_HOOKS`/`CLIENT_APIS` order
+    pub client_hooks_used: Vec<String>,
+    /// Every client hook/API call and event handler prop, in source order
+    pub client_api_usages: Vec<ClientApiUsage>,
+    /// Has metadata export
+    pub has_metadata: bool,
+    /// `export const metadata` is initialized with a string, number or
+    /// template literal instead of an object
+    pub metadata_is_primitive: bool,
+    /// The literal parts of an object `metadata` export as JSON
+    pub static_metadata: Option<serde_json::Value>,
+    /// Parts of the `metadata` export left out of `static_metadata`
+    pub metadata_issues: Vec<MetadataIssue>,
+    /// Has generateMetadata export
+    pub has_generate_metadata: bool,
+    /// Has generateStaticParams export
+    pub has_generate_static_params: bool,
+    /// Route segment config options, in source order
+    pub segment_config: Vec<SegmentConfigExport>,
+    /// Parse failure, if the module could not be parsed
+    pub parse_error: Option<ParseError>,
+}
+
+/// Where and why a module failed to parse
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+/// A successfully parsed module together with its source map
+pub struct ParsedModule {
+    pub source_map: Lrc<SourceMap>,
+    pub module: Module,
+}
+
+impl ParsedModule {
+    /// 1-indexed line number of the start of a span
+    pub fn line_of(&self, span: Span) -> usize {
+        self.source_map.lookup_char_pos(span.lo).line
+    }
+
+    /// 1-indexed line and column of the start of a span
+    pub fn line_col_of(&self, span: Span) -> (usize, usize) {
+        let loc = self.source_map.lookup_char_pos(span.lo);
+        (loc.line, loc.col_display + 1)
+    }
+}
+
+/// Pick the SWC syntax for a file based on its extension
+pub fn syntax_for_path(file_name: &str) -> Syntax {
+    match Path::new(file_name).extension().and_then(|e| e.to_str()) {
+        Some("ts") | Some("mts") | Some("cts") => Syntax::Typescript(TsSyntax {
+            tsx: false,
+            decorators: true,
+            ..Default::default()
+        }),
+        Some("js") | Some("jsx") | Some("mjs") | Some("cjs") => Syntax::Es(EsSyntax {
+            jsx: true,
+            decorators: true,
+            ..Default::default()
+        }),
+        _ => Syntax::Typescript(TsSyntax {
+            tsx: true,
+            decorators: true,
+            ..Default::default()
+        }),
+    }
+}
+
+/// Parse a module. The file name is only used to pick the syntax and for messages.
+pub fn parse_module(source: &str, file_name: &str) -> Result<ParsedModule, ParseError> {
+    let source_map: Lrc<SourceMap> = Default::default();
+    let fm = source_map.new_source_file(
+        Lrc::new(FileName::Custom(file_name.to_string())),
+        source.to_string(),
+    );
+
+    let mut recovered = Vec::new();
+    let result = parse_file_as_module(
+        &fm,
+        syntax_for_path(file_name),
+        EsVersion::latest(),
+        None,
+        &mut recovered,
+    );
+    // Recovered errors still leave a partial AST behind; don't classify from it
+    match result.and_then(|module| match recovered.into_iter().next() {
+        Some(err) => Err(err),
+        None => Ok(module),
+    }) {
+        Ok(module) => Ok(ParsedModule { source_map, module }),
+        Err(err) => {
+            let loc = source_map.lookup_char_pos(err.span().lo);
+            Err(ParseError {
+                line: loc.line,
+                column: loc.col_display + 1,
+                message: err.into_kind().msg().to_string(),
+            })
+        }
+    }
+}
+
+/// Analyze a module source. The file name selects TS/TSX/JS/JSX parsing.
+pub fn analyze_module(source: &str, file_name: &str) -> ModuleAnalysis {
+    match parse_module(source, file_name) {
+        Ok(parsed) => analyze_parsed(&parsed),
+        Err(error) => ModuleAnalysis {
+            parse_error: Some(error),
+            ..Default::default()
+        },
+    }
+}
+
+/// Analyze a source string of unknown type.
+/// Tries TSX first and falls back to plain TypeScript (for `<T>value` type assertions).
+pub fn analyze_source(source: &str) -> ModuleAnalysis {
+    let analysis = analyze_module(source, "module.tsx");
+    if analysis.parse_error.is_none() {
+        return analysis;
+    }
+    let fallback = analyze_module(source, "module.ts");
+    if fallback.parse_error.is_none() {
+        fallback
+    } else {
+        analysis
+    }
+}
+
+/// Analyze an already-parsed module
+pub fn analyze_parsed(parsed: &ParsedModule) -> ModuleAnalysis {
+    let mut analysis = ModuleAnalysis::default();
+
+    if let Some(span) = find_directive(&parsed.module, "use client") {
+        analysis.is_client = true;
+        analysis.directive_line = parsed.line_of(span);
+    }
+    analysis.is_server = find_directive(&parsed.module, "use server").is_some();
+    analysis.server_actions = collect_server_actions(parsed, analysis.is_server);
+
+    collect_exports(parsed, &mut analysis);
+    analysis.has_metadata = analysis.exports.iter().any(|e| e == "metadata");
+    analysis.metadata_is_primitive = metadata_is_primitive(&parsed.module);
+    (analysis.static_metadata, analysis.metadata_issues) = extract_static_metadata(parsed);
+    analysis.has_generate_metadata = analysis.exports.iter().any(|e| e == "generateMetadata");
+    analysis.has_generate_static_params = analysis.exports.iter().any(|e| e == "generateStaticParams");
+    analysis.segment_config = collect_segment_config(parsed);
+
+    analysis.imports = collect_imports(parsed);
+
+    let mut collector = ClientApiCollector::new(parsed);
+    parsed.module.visit_with(&mut collector);
+    analysis.client_hooks_used = collector.used_names();
+    analysis.client_api_usages = collector.usages;
+
+    analysis
+}
+
+/// Parse JSON with comments and trailing commas (`tsconfig.json` style)
+pub fn parse_jsonc(source: &str) -> Option<serde_json::Value> {
+    let source_map: Lrc<SourceMap> = Default::default();
+    let fm = source_map.new_source_file(Lrc::new(FileName::Anon), format!("({source})"));
+    let mut recovered = Vec::new();
+    let expr = parse_file_as_expr(
+        &fm,
+        Syntax::Es(Default::default()),
+        EsVersion::latest(),
+        None,
+        &mut recovered,
+    )
+    .ok()?;
+    if !recovered.is_empty() {
+        return None;
+    }
+    literal_to_json(&expr)
+}
+
+/// Convert a literal expression (objects, arrays, strings, numbers, booleans,
+/// null) to JSON. Returns `None` for anything that would need evaluation.
+pub fn literal_to_json(expr: &Expr) -> Option<serde_json::Value> {
+    use serde_json::Value;
+
+    match expr {
+        Expr::Paren(p) => literal_to_json(&p.expr),
+        Expr::TsAs(e) => literal_to_json(&e.expr),
+        Expr::TsSatisfies(e) => literal_to_json(&e.expr),
+        Expr::TsConstAssertion(e) => literal_to_json(&e.expr),
+        Expr::Lit(Lit::Str(s)) => Some(Value::String(s.value.as_str()?.to_string())),
+        Expr::Lit(Lit::Num(n)) => number_to_json(n.value),
+        Expr::Lit(Lit::Bool(b)) => Some(Value::Bool(b.value)),
+        Expr::Lit(Lit::Null(_)) => Some(Value::Null),
+        Expr::Unary(UnaryExpr { op: UnaryOp::Minus, arg, .. }) => match &**arg {
+            Expr::Lit(Lit::Num(n)) => number_to_json(-n.value),
+            _ => None,
+        },
+        Expr::Tpl(tpl) if tpl.exprs.is_empty() => {
+            let cooked = tpl.quasis.first()?.cooked.as_ref()?;
+            Some(Value::String(cooked.as_str()?.to_string()))
+        }
+        Expr::Array(array) => array
+            .elems
+            .iter()
+            .map(|elem| match elem {
+                Some(ExprOrSpread { spread: None, expr }) => literal_to_json(expr),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+            .map(Value::Array),
+        Expr::Object(object) => {
+            let mut map = serde_json::Map::new();
+            for prop in &object.props {
+                let PropOrSpread::Prop(prop) = prop else {
+                    return None;
+                };
+                let Prop::KeyValue(kv) = &**prop else {
+                    return None;
+                };
+                let key = match &kv.key {
+                    PropName::Ident(i) => i.sym.to_string(),
+                    PropName::Str(s) => s.value.as_str()?.to_string(),
+                    PropName::Num(n) => n.value.to_string(),
+                    _ => return None,
+                };
+                map.insert(key, literal_to_json(&kv.value)?);
+            }
+            Some(Value::Object(map))
+        }
+        _ => None,
+    }
+}
+
+fn number_to_json(value: f64) -> Option<serde_json::Value> {
+    // Keep integers as integers so `60` round-trips as `60`, not `60.0`
+    if value.fract() == 0.0 && value.abs() < 9_007_199_254_740_992.0 {
+        Some(serde_json::Value::from(value as i64))
+    } else {
+        serde_json::Number::from_f64(value).map(serde_json::Value::Number)
+    }
+}
+
+/// Find a directive in the module prologue and return its span
+pub fn find_directive(module: &Module, directive: &str) -> Option<Span> {
+    let prologue = module.body.iter().map_while(|item| match item {
+        ModuleItem::Stmt(stmt) => Some(stmt),
+        ModuleItem::ModuleDecl(_) => None,
+    });
+    prologue_directive(prologue, directive)
+}
+
+fn prologue_directive<'a>(
+    stmts: impl IntoIterator<Item = &'a Stmt>,
+    directive: &str,
+) -> Option<Span> {
+    for stmt in stmts {
+        let Stmt::Expr(ExprStmt { expr, .. }) = stmt else {
+            break;
+        };
+        let Expr::Lit(Lit::Str(s)) = &**expr else {
+            break;
+        };
+        if s.value.as_str() == Some(directive) {
+            return Some(s.span);
+        }
+    }
+    None
+}
+
+fn function_has_directive(function: &Function, directive: &str) -> bool {
+    function
+        .body
+        .as_ref()
+        .is_some_and(|body| prologue_directive(&body.stmts, directive).is_some())
+}
+
+fn expr_has_directive(expr: &Expr, directive: &str) -> bool {
+    match expr {
+        Expr::Fn(f) => function_has_directive(&f.function, directive),
+        Expr::Arrow(arrow) => match &*arrow.body {
+            BlockStmtOrExpr::BlockStmt(body) => {
+                prologue_directive(&body.stmts, directive).is_some()
+            }
+            _ => false,
+        },
+        Expr::Paren(p) => expr_has_directive(&p.expr, directive),
+        _ => false,
+    }
+}
+
+/// Collect exported server actions. With a file-level `'use server'` every
+/// exported function is an action, including local functions exported by
+/// name; otherwise only exported functions whose body starts with
+/// `'use server'`. Exports that aren't functions are never actions.
+fn collect_server_actions(parsed: &ParsedModule, file_level: bool) -> Vec<ServerActionExport> {
+    let functions = local_functions(&parsed.module);
+    let mut actions = Vec::new();
+    let mut push = |export_name: String, span: Span, inline: bool| {
+        if file_level || inline {
+            actions.push(ServerActionExport {
+                export_name,
+                line: parsed.line_of(span),
+                inline: inline && !file_level,
+            });
+        }
+    };
+
+    for item in &parsed.module.body {
+        let ModuleItem::ModuleDecl(decl) = item else {
+            continue;
+        };
+        match decl {
+            ModuleDecl::ExportDecl(ExportDecl { decl: Decl::Fn(f), span }) => {
+                let inline = function_has_directive(&f.function, "use server");
+                push(f.ident.sym.to_string(), *span, inline);
+            }
+            ModuleDecl::ExportDecl(ExportDecl { decl: Decl::Var(v), span }) => {
+                for d in &v.decls {
+                    let Pat::Ident(name) = &d.name else {
+                        continue;
+                    };
+                    if let Some(inline) = d.init.as_deref().and_then(function_expr) {
+                        push(name.id.sym.to_string(), *span, inline);
+                    }
+                }
+            }
+            ModuleDecl::ExportDefaultDecl(ExportDefaultDecl { decl: DefaultDecl::Fn(f), span }) => {
+                let inline = function_has_directive(&f.function, "use server");
+                push("default".to_string(), *span, inline);
+            }
+            ModuleDecl::ExportDefaultExpr(e) => {
+                let inline = match unwrap_type_expr(&e.expr) {
+                    Expr::Ident(ident) => functions.get(&*ident.sym).copied(),
+                    expr => function_expr(expr),
+                };
+                if let Some(inline) = inline {
+                    push("default".to_string(), e.span, inline);
+                }
+            }
+            ModuleDecl::ExportNamed(named) if named.src.is_none() && !named.type_only => {
+                for specifier in &named.specifiers {
+                    let ExportSpecifier::Named(n) = specifier else {
+                        continue;
+                    };
+                    let ModuleExportName::Ident(orig) = &n.orig else {
+                        continue;
+                    };
+                    let Some(&inline) = functions.get(&*orig.sym).filter(|_| !n.is_type_only) else {
+                        continue;
+                    };
+                    let name = n
+                        .exported
+                        .as_ref()
+                        .map(module_export_name)
+                        .unwrap_or_else(|| orig.sym.to_string());
+                    push(name, n.span, inline);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    actions
+}
+
+/// Whether `expr` is a function, and if so whether its body starts with `'use server'`
+fn function_expr(expr: &Expr) -> Option<bool> {
+    match unwrap_type_expr(expr) {
+        expr @ (Expr::Fn(_) | Expr::Arrow(_)) => Some(expr_has_directive(expr, "use server")),
+        _ => None,
+    }
+}
+
+/// Top-level functions by binding name, mapped to whether their body starts
+/// with `'use server'`
+fn local_functions(module: &Module) -> FxHashMap<String, bool> {
+    let mut functions = FxHashMap::default();
+    for item in &module.body {
+        let decl = match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => &export.decl,
+            _ => continue,
+        };
+        match decl {
+            Decl::Fn(f) if !f.declare => {
+                functions.insert(f.ident.sym.to_string(), function_has_directive(&f.function, "use server"));
+            }
+            Decl::Var(v) => {
+                for d in &v.decls {
+                    if let (Pat::Ident(name), Some(inline)) = (&d.name, d.init.as_deref().and_then(function_expr)) {
+                        functions.insert(name.id.sym.to_string(), inline);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    functions
+}
+
+fn metadata_is_primitive(module: &Module) -> bool {
+    module.body.iter().any(|item| {
+        let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl: Decl::Var(v), .. })) = item else {
+            return false;
+        };
+        v.decls.iter().any(|d| {
+            let is_metadata = matches!(&d.name, Pat::Ident(name) if &*name.id.sym == "metadata");
+            is_metadata
+                && matches!(
+                    d.init.as_deref(),
+                    Some(Expr::Lit(Lit::Str(_) | Lit::Num(_)) | Expr::Tpl(_))
+                )
+        })
+    })
+}
+
+/// Evaluate `export const metadata = { ... }`. Properties that need
+/// evaluation are left out and reported, the rest is kept. An array with
+/// such an element is left out as a whole.
+fn extract_static_metadata(parsed: &ParsedModule) -> (Option<serde_json::Value>, Vec<MetadataIssue>) {
+    let mut issues = Vec::new();
+    for item in &parsed.module.body {
+        let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl: Decl::Var(v), .. })) = item else {
+            continue;
+        };
+        for decl in &v.decls {
+            let is_metadata = matches!(&decl.name, Pat::Ident(name) if &*name.id.sym == "metadata");
+            let Some(init) = decl.init.as_deref().filter(|_| is_metadata) else {
+                continue;
+            };
+            if !matches!(unwrap_type_expr(init), Expr::Object(_)) {
+                return (None, issues);
+            }
+            let metadata = static_value(init, "metadata", parsed, &mut issues);
+            return (metadata, issues);
+        }
+    }
+    (None, issues)
+}
+
+/// Strip parentheses and TS-only wrappers (`as`, `satisfies`, `as const`)
+fn unwrap_type_expr(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Paren(p) => unwrap_type_expr(&p.expr),
+        Expr::TsAs(e) => unwrap_type_expr(&e.expr),
+        Expr::TsSatisfies(e) => unwrap_type_expr(&e.expr),
+        Expr::TsConstAssertion(e) => unwrap_type_expr(&e.expr),
+        _ => expr,
+    }
+}
+
+fn static_value(
+    expr: &Expr,
+    path: &str,
+    parsed: &ParsedModule,
+    issues: &mut Vec<MetadataIssue>,
+) -> Option<serde_json::Value> {
+    use serde_json::Value;
+
+    let issue = |path: String, span: Span| {
+        let (line, column) = parsed.line_col_of(span);
+        MetadataIssue { path, line, column }
+    };
+
+    match unwrap_type_expr(expr) {
+        Expr::Object(object) => {
+            let mut map = serde_json::Map::new();
+            for prop in &object.props {
+                let PropOrSpread::Prop(prop) = prop else {
+                    issues.push(issue(format!("{path}.<spread>"), prop.span()));
+                    continue;
+                };
+                let key = match &**prop {
+                    Prop::KeyValue(kv) => prop_name_key(&kv.key),
+                    Prop::Shorthand(ident) => Some(ident.sym.to_string()),
+                    Prop::Method(m) => prop_name_key(&m.key),
+                    Prop::Getter(g) => prop_name_key(&g.key),
+                    Prop::Setter(s) => prop_name_key(&s.key),
+                    _ => None,
+                };
+                let key_path = format!("{path}.{}", key.as_deref().unwrap_or("<computed>"));
+                match (&**prop, key) {
+                    (Prop::KeyValue(kv), Some(key)) => {
+                        if let Some(value) = static_value(&kv.value, &key_path, parsed, issues) {
+                            map.insert(key, value);
+                        }
+                    }
+                    _ => issues.push(issue(key_path, prop.span())),
+                }
+            }
+            Some(Value::Object(map))
+        }
+        // Dropping an element would shift the rest, so the array is only
+        // kept when every element evaluates (holes become null)
+        Expr::Array(array) => {
+            let mut values = Some(Vec::new());
+            for (index, elem) in array.elems.iter().enumerate() {
+                let elem_path = format!("{path}[{index}]");
+                let value = match elem {
+                    Some(ExprOrSpread { spread: None, expr }) => static_value(expr, &elem_path, parsed, issues),
+                    Some(elem) => {
+                        issues.push(issue(elem_path, elem.span()));
+                        None
+                    }
+                    None => Some(Value::Null),
+                };
+                match (&mut values, value) {
+                    (Some(values), Some(value)) => values.push(value),
+                    _ => values = None,
+                }
+            }
+            values.map(Value::Array)
+        }
+        other => {
+            let value = literal_to_json(other);
+            if value.is_none() {
+                issues.push(issue(path.to_string(), other.span()));
+            }
+            value
+        }
+    }
+}
+
+fn prop_name_key(key: &PropName) -> Option<String> {
+    match key {
+        PropName::Ident(i) => Some(i.sym.to_string()),
+        PropName::Str(s) => s.value.as_str().map(str::to_string),
+        PropName::Num(n) => Some(n.value.to_string()),
+        _ => None,
+    }
+}
+
+fn collect_segment_config(parsed: &ParsedModule) -> Vec<SegmentConfigExport> {
+    let mut config = Vec::new();
+    for item in &parsed.module.body {
+        let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl: Decl::Var(v), .. })) = item else {
+            continue;
+        };
+        for decl in &v.decls {
+            let Pat::Ident(name) = &decl.name else {
+                continue;
+            };
+            if !SEGMENT_CONFIG_KEYS.contains(&&*name.id.sym) {
+                continue;
+            }
+            let (line, column) = parsed.line_col_of(decl.span);
+            config.push(SegmentConfigExport {
+                name: name.id.sym.to_string(),
+                value: decl.init.as_deref().and_then(literal_to_json),
+                line,
+                column,
+            });
+        }
+    }
+    config
+}
+
+fn collect_imports(parsed: &ParsedModule) -> Vec<ImportRecord> {
+    let mut imports = Vec::new();
+    let mut push = |src: &Str, kind: ImportKind| {
+        if let Some(specifier) = src.value.as_str() {
+            imports.push(ImportRecord {
+                specifier: specifier.to_string(),
+                kind,
+                line: parsed.line_of(src.span),
+            });
+        }
+    };
+
+    for item in &parsed.module.body {
+        let ModuleItem::ModuleDecl(decl) = item else {
+            continue;
+        };
+        match decl {
+            ModuleDecl::Import(import) => {
+                // TS elides imports whose specifiers are all type-only
+                let all_types = !import.specifiers.is_empty()
+                    && import.specifiers.iter().all(|s| {
+                        matches!(s, ImportSpecifier::Named(named) if named.is_type_only)
+                    });
+                if !import.type_only && !all_types {
+                    push(&import.src, ImportKind::Static);
+                }
+            }
+            ModuleDecl::ExportAll(export) if !export.type_only => {
+                push(&export.src, ImportKind::ReExport);
+            }
+            ModuleDecl::ExportNamed(NamedExport { src: Some(src), type_only: false, .. }) => {
+                push(src, ImportKind::ReExport);
+            }
+            _ => {}
+        }
+    }
+
+    let mut dynamic = DynamicImportCollector::default();
+    parsed.module.visit_with(&mut dynamic);
+    for src in &dynamic.sources {
+        push(src, ImportKind::Dynamic);
+    }
+
+    imports
+}
+
+/// Collects `import('x')` calls with a string literal argument
+#[derive(Default)]
+struct DynamicImportCollector {
+    sources: Vec<Str>,
+}
+
+impl Visit for DynamicImportCollector {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Import(_) = call.callee {
+            if let Some(ExprOrSpread { spread: None, expr }) = call.args.first() {
+                if let Expr::Lit(Lit::Str(src)) = &**expr {
+                    self.sources.push(src.clone());
+                }
+            }
+        }
+        call.visit_children_with(self);
+    }
+}
+
+fn collect_exports(parsed: &ParsedModule, analysis: &mut ModuleAnalysis) {
+    let (type_names, value_names) = collect_binding_kinds(&parsed.module);
+    let is_type_only = |name: &str| type_names.contains(name) && !value_names.contains(name);
+
+    let mut table = Vec::new();
+    let local_export = |name: String, local: Option<String>, span: Span| ExportEntry {
+        name,
+        kind: ExportKind::Local,
+        local,
+        source: None,
+        line: parsed.line_of(span),
+    };
+
+    for item in &parsed.module.body {
+        let ModuleItem::ModuleDecl(decl) = item else {
+            continue;
+        };
+        match decl {
+            ModuleDecl::ExportDecl(ExportDecl { decl, span }) => {
+                let mut names = Vec::new();
+                match decl {
+                    Decl::Fn(f) if !f.declare => names.push(f.ident.sym.to_string()),
+                    Decl::Class(c) if !c.declare => names.push(c.ident.sym.to_string()),
+                    Decl::Var(v) if !v.declare => {
+                        for d in &v.decls {
+                            binding_names(&d.name, &mut names);
+                        }
+                    }
+                    Decl::TsEnum(e) if !e.declare => names.push(e.id.sym.to_string()),
+                    Decl::TsModule(m) if !m.declare => {
+                        if let TsModuleName::Ident(id) = &m.id {
+                            names.push(id.sym.to_string());
+                        }
+                    }
+                    _ => {}
+                }
+                for name in names {
+                    table.push(local_export(name.clone(), Some(name), *span));
+                }
+            }
+            ModuleDecl::ExportDefaultDecl(d) => {
+                let name = match &d.decl {
+                    DefaultDecl::Fn(f) => f.ident.as_ref().map(|i| i.sym.to_string()),
+                    DefaultDecl::Class(c) => c.ident.as_ref().map(|i| i.sym.to_string()),
+                    DefaultDecl::TsInterfaceDecl(_) => continue,
+                };
+                analysis.default_export_name = name.clone();
+                table.push(local_export("default".to_string(), name, d.span));
+            }
+            ModuleDecl::ExportDefaultExpr(e) => {
+                let name = match &*e.expr {
+                    Expr::Ident(ident) => Some(ident.sym.to_string()),
+                    _ => None,
+                };
+                if name.as_deref().is_some_and(is_type_only) {
+                    continue;
+                }
+                table.push(local_export("default".to_string(), name, e.span));
+            }
+            ModuleDecl::ExportNamed(named) if !named.type_only => {
+                let source = named.src.as_ref().and_then(|s| s.value.as_str()).map(str::to_string);
+                for specifier in &named.specifiers {
+                    let (name, kind, orig) = match specifier {
+                        ExportSpecifier::Named(n) if !n.is_type_only => {
+                            let orig = module_export_name(&n.orig);
+                            let name = n
+                                .exported
+                                .as_ref()
+                                .map(module_export_name)
+                                .unwrap_or_else(|| orig.clone());
+                            let kind = match source {
+                                Some(_) => ExportKind::ReExport,
+                                None => ExportKind::Local,
+                            };
+                            (name, kind, Some(orig))
+                        }
+                        ExportSpecifier::Namespace(n) => {
+                            (module_export_name(&n.name), ExportKind::Namespace, None)
+                        }
+                        _ => continue,
+                    };
+                    if kind == ExportKind::Local && orig.as_deref().is_some_and(is_type_only) {
+                        continue;
+                    }
+                    table.push(ExportEntry {
+                        name,
+                        kind,
+                        local: orig,
+                        source: source.clone(),
+                        line: parsed.line_of(specifier.span()),
+                    });
+                }
+            }
+            ModuleDecl::ExportAll(export) if !export.type_only => {
+                table.push(ExportEntry {
+                    name: "*".to_string(),
+                    kind: ExportKind::Star,
+                    local: None,
+                    source: export.src.value.as_str().map(str::to_string),
+                    line: parsed.line_of(export.span),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    analysis.exports = table
+        .iter()
+        .filter(|e| e.kind != ExportKind::Star)
+        .map(|e| e.name.clone())
+        .collect();
+    analysis.export_table = table;
+}
+
+fn module_export_name(name: &ModuleExportName) -> String {
+    match name {
+        ModuleExportName::Ident(ident) => ident.sym.to_string(),
+        ModuleExportName::Str(s) => s.value.as_str().unwrap_or_default().to_string(),
+    }
+}
+
+/// Names bound by a (possibly destructuring) pattern
+fn binding_names(pat: &Pat, names: &mut Vec<String>) {
+    match pat {
+        Pat::Ident(ident) => names.push(ident.id.sym.to_string()),
+        Pat::Array(array) => {
+            for elem in array.elems.iter().flatten() {
+                binding_names(elem, names);
+            }
+        }
+        Pat::Object(object) => {
+            for prop in &object.props {
+                match prop {
+                    ObjectPatProp::KeyValue(kv) => binding_names(&kv.value, names),
+                    ObjectPatProp::Assign(assign) => names.push(assign.key.sym.to_string()),
+                    ObjectPatProp::Rest(rest) => binding_names(&rest.arg, names),
+                }
+            }
+        }
+        Pat::Rest(rest) => binding_names(&rest.arg, names),
+        Pat::Assign(assign) => binding_names(&assign.left, names),
+        _ => {}
+    }
+}
+
+/// Top-level names declared as types only vs. as values, so `export { T }`
+/// of an interface or type alias can be left out
+fn collect_binding_kinds(module: &Module) -> (FxHashSet<String>, FxHashSet<String>) {
+    let mut types = FxHashSet::default();
+    let mut values = FxHashSet::default();
+
+    for item in &module.body {
+        let decl = match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => &export.decl,
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                for specifier in &import.specifiers {
+                    let (local, type_only) = match specifier {
+                        ImportSpecifier::Named(n) => (&n.local, n.is_type_only),
+                        ImportSpecifier::Default(d) => (&d.local, false),
+                        ImportSpecifier::Namespace(n) => (&n.local, false),
+                    };
+                    let set = if import.type_only || type_only { &mut types } else { &mut values };
+                    set.insert(local.sym.to_string());
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        match decl {
+            Decl::TsInterface(i) => {
+                types.insert(i.id.sym.to_string());
+            }
+            Decl::TsTypeAlias(t) => {
+                types.insert(t.id.sym.to_string());
+            }
+            Decl::Fn(f) => {
+                values.insert(f.ident.sym.to_string());
+            }
+            Decl::Class(c) => {
+                values.insert(c.ident.sym.to_string());
+            }
+            Decl::TsEnum(e) => {
+                values.insert(e.id.sym.to_string());
+            }
+            Decl::Var(v) => {
+                let mut names = Vec::new();
+                for d in &v.decls {
+                    binding_names(&d.name, &mut names);
+                }
+                values.extend(names);
+            }
+            _ => {}
+        }
+    }
+
+    (types, values)
+}
+
+/// Modules whose hooks and APIs are client-only
+const REACT_MODULES: &[&str] = &["react", "react-dom"];
+
+/// Local bindings of React imports
+#[derive(Default)]
+struct ReactBindings {
+    /// Names bound to the React namespace (`React` plus default/namespace imports)
+    namespaces: FxHashSet<String>,
+    /// Named imports by local name, mapped to the imported name
+    named: FxHashMap<String, String>,
+}
+
+impl ReactBindings {
+    fn collect(module: &Module) -> Self {
+        let mut bindings = Self::default();
+        bindings.namespaces.insert("React".to_string());
+        for item in &module.body {
+            let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item else {
+                continue;
+            };
+            let from_react = import.src.value.as_str().is_some_and(|s| REACT_MODULES.contains(&s));
+            if import.type_only || !from_react {
+                continue;
+            }
+            for specifier in &import.specifiers {
+                match specifier {
+                    ImportSpecifier::Default(d) => {
+                        bindings.namespaces.insert(d.local.sym.to_string());
+                    }
+                    ImportSpecifier::Namespace(n) => {
+                        bindings.namespaces.insert(n.local.sym.to_string());
+                    }
+                    ImportSpecifier::Named(n) if !n.is_type_only => {
+                        let local = n.local.sym.to_string();
+                        let imported = n.imported.as_ref().map(module_export_name).unwrap_or_else(|| local.clone());
+                        bindings.named.insert(local, imported);
+                    }
+                    ImportSpecifier::Named(_) => {}
+                }
+            }
+        }
+        bindings
+    }
+}
+
+/// Collects calls to client-only hooks/APIs and client-only JSX props
+struct ClientApiCollector<'a> {
+    parsed: &'a ParsedModule,
+    react: ReactBindings,
+    usages: Vec<ClientApiUsage>,
+}
+
+impl<'a> ClientApiCollector<'a> {
+    fn new(parsed: &'a ParsedModule) -> Self {
+        Self {
+            parsed,
+            react: ReactBindings::collect(&parsed.module),
+            usages: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, name: &str, span: Span) {
+        let (line, column) = self.parsed.line_col_of(span);
+        self.usages.push(ClientApiUsage {
+            name: name.to_string(),
+            line,
+            column,
+        });
+    }
+
+    /// Hook/API names in `CLIENT_HOOKS`/`CLIENT_APIS` order, then the event handler label
+    fn used_names(&self) -> Vec<String> {
+        let found: FxHashSet<&str> = self.usages.iter().map(|u| u.name.as_str()).collect();
+        let mut used: Vec<String> = CLIENT_HOOKS
+            .iter()
+            .chain(CLIENT_APIS)
+            .filter(|name| found.contains(**name))
+            .map(|name| name.to_string())
+            .collect();
+        if EVENT_HANDLER_ATTRS.iter().any(|attr| found.contains(attr)) {
+            used.push(EVENT_HANDLERS_LABEL.to_string());
+        }
+        used
+    }
+}
+
+fn is_client_api(name: &str) -> bool {
+    CLIENT_HOOKS.contains(&name) || CLIENT_APIS.contains(&name)
+}
+
+impl Visit for ClientApiCollector<'_> {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Expr(callee) = &call.callee {
+            match &**callee {
+                // Only React's own hooks and APIs: a local `memo` or a `lazy`
+                // from another library is not client-only
+                Expr::Ident(ident) => {
+                    if let Some(imported) = self.react.named.get(ident.sym.as_ref()) {
+                        if is_client_api(imported) {
+                            let imported = imported.clone();
+                            self.record(&imported, ident.span);
+                        }
+                    }
+                }
+                // `React.useState(...)`
+                Expr::Member(MemberExpr { obj, prop: MemberProp::Ident(prop), .. })
+                    if is_client_api(&prop.sym) =>
+                {
+                    if let Expr::Ident(obj) = &**obj {
+                        if self.react.namespaces.contains(obj.sym.as_ref()) {
+                            self.record(&prop.sym, prop.span);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        call.visit_children_with(self);
+    }
+
+    fn visit_jsx_attr(&mut self, attr: &JSXAttr) {
+        if let JSXAttrName::Ident(name) = &attr.name {
+            if EVENT_HANDLER_ATTRS.contains(&name.sym.as_ref()) {
+                self.record(&name.sym, name.span);
+            }
+        }
+        attr.visit_children_with(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directive_after_comment() {
+        let analysis = analyze_module("// Counter\n/* docs */\n'use client';\n", "a.tsx");
+        assert!(analysis.is_client);
+        assert_eq!(analysis.directive_line, 3);
+    }
+
+    #[test]
+    fn test_directive_must_be_in_prologue() {
+        let analysis = analyze_module("import x from 'y';\n'use client';\n", "a.ts");
+        assert!(!analysis.is_client);
+    }
+
+    #[test]
+    fn test_file_level_use_server() {
+        let source = r#"
+            'use server';
+            export async function createPost(data) {}
+            export const deletePost = async (id) => {};
+            export default async function () {}
+        "#;
+        let analysis = analyze_module(source, "actions.ts");
+        assert!(analysis.is_server);
+        let names: Vec<_> = analysis.server_actions.iter().map(|a| a.export_name.as_str()).collect();
+        assert_eq!(names, vec!["createPost", "deletePost", "default"]);
+        assert!(analysis.server_actions.iter().all(|a| !a.inline));
+        assert_eq!(analysis.server_actions[0].line, 3);
+    }
+
+    #[test]
+    fn test_file_level_use_server_named_exports() {
+        let source = r#"
+            'use server';
+            async function create() {}
+            const update = async () => {};
+            async function remove() {}
+            export { create, update as save };
+            export const limit = 10;
+            export const config = { retries: 3 };
+            export default remove;
+        "#;
+        let analysis = analyze_module(source, "actions.ts");
+        let names: Vec<_> = analysis.server_actions.iter().map(|a| a.export_name.as_str()).collect();
+        assert_eq!(names, vec!["create", "save", "default"]);
+        assert_eq!(analysis.server_actions[1].line, 6);
+    }
+
+    #[test]
+    fn test_function_level_use_server() {
+        let source = r#"
+            export async function save() {
+                'use server';
+            }
+            export const remove = async () => { "use server"; };
+            export async function notAnAction() {
+                const s = 'use server';
+            }
+        "#;
+        let analysis = analyze_module(source, "page.tsx");
+        assert!(!analysis.is_server);
+        let names: Vec<_> = analysis.server_actions.iter().map(|a| a.export_name.as_str()).collect();
+        assert_eq!(names, vec!["save", "remove"]);
+        assert!(analysis.server_actions.iter().all(|a| a.inline));
+    }
+
+    #[test]
+    fn test_collect_imports() {
+        let source = r#"
+            import Button from './Button';
+            import type { Props } from './types';
+            import { type Theme } from '@/theme';
+            import './globals.css';
+            export { helper } from '../lib/helper';
+            export type { Shape } from './shape';
+            export * from '@/components';
+            const Chart = () => import('./Chart');
+        "#;
+        let analysis = analyze_module(source, "page.tsx");
+        let imports: Vec<_> = analysis
+            .imports
+            .iter()
+            .map(|i| (i.specifier.as_str(), i.kind))
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("./Button", ImportKind::Static),
+                ("./globals.css", ImportKind::Static),
+                ("../lib/helper", ImportKind::ReExport),
+                ("@/components", ImportKind::ReExport),
+                ("./Chart", ImportKind::Dynamic),
+            ]
+        );
+        assert_eq!(analysis.imports[0].line, 2);
+    }
+
+    #[test]
+    fn test_parse_jsonc() {
+        let source = r#"{
+            // path aliases
+            "compilerOptions": {
+                "baseUrl": ".", /* project root */
+                "paths": { "@/*": ["./src/*"], },
+                "strict": true,
+                "target": -1,
+            },
+        }"#;
+        let value = parse_jsonc(source).unwrap();
+        assert_eq!(value["compilerOptions"]["paths"]["@/*"][0], "./src/*");
+        assert_eq!(value["compilerOptions"]["strict"], true);
+        assert_eq!(value["compilerOptions"]["target"], -1);
+        assert!(parse_jsonc("{ a: foo() }").is_none());
+    }
+
+    #[test]
+    fn test_hooks_in_comments_and_strings_are_ignored() {
+        let source = r#"
+            // useState(0) is not allowed here
+            const doc = `call useEffect() on the client`;
+            const label = "onClick=";
+            export default function Page() {
+                return <p title="useRef(">{myuseState(1)}</p>;
+            }
+        "#;
+        let analysis = analyze_module(source, "page.tsx");
+        assert!(analysis.client_hooks_used.is_empty());
+    }
+
+    #[test]
+    fn test_client_apis_must_come_from_react() {
+        let source = r#"
+            import { lazy } from './lazy';
+            import { useState as useLocalState, type Dispatch } from 'react';
+            import { useFormStatus } from 'react-dom';
+            function memo(fn) { return fn; }
+            const format = memo((x) => x);
+            const load = lazy(() => import('./chart'));
+            const [a] = useLocalState(0);
+            const status = useFormStatus();
+            const value = useCounter();
+        "#;
+        let analysis = analyze_module(source, "page.tsx");
+        assert_eq!(analysis.client_hooks_used, vec!["useState", "useFormStatus"]);
+        assert_eq!(analysis.client_api_usages[0].line, 8);
+    }
+
+    #[test]
+    fn test_member_hook_calls_with_locations() {
+        let source = "import * as R from 'react';\n\
+            export default function Page() {\n\
+            const [a] = React.useState(0);\n\
+            R.useEffect(() => {});\n\
+            store.useState();\n\
+            return <button onClick={go}>{a}</button>;\n\
+            }";
+        let analysis = analyze_module(source, "page.tsx");
+        assert_eq!(
+            analysis.client_hooks_used,
+            vec!["useState", "useEffect", EVENT_HANDLERS_LABEL]
+        );
+        let usages: Vec<_> = analysis
+            .client_api_usages
+            .iter()
+            .map(|u| (u.name.as_str(), u.line, u.column))
+            .collect();
+        assert_eq!(
+            usages,
+            vec![("useState", 3, 19), ("useEffect", 4, 3), ("onClick", 6, 16)]
+        );
+    }
+
+    #[test]
+    fn test_multiline_exports() {
+        let source = r#"
+            export const metadata = {
+                title: 'Test',
+            };
+            export async function
+                generateMetadata() {}
+            export default class Page {}
+        "#;
+        let analysis = analyze_module(source, "page.tsx");
+        assert_eq!(analysis.exports, vec!["metadata", "generateMetadata", "default"]);
+        assert_eq!(analysis.default_export_name.as_deref(), Some("Page"));
+        assert!(analysis.has_metadata);
+        assert!(analysis.has_generate_metadata);
+        assert!(!analysis.metadata_is_primitive);
+        assert!(analyze_module("export const metadata = 'Home';", "page.tsx").metadata_is_primitive);
+    }
+
+    #[test]
+    fn test_export_table() {
+        let source = r#"
+            import type { Props } from './types';
+            import { helper } from './helper';
+            interface Local {}
+            type Alias = string;
+            const a = 1, { b, c: [d, ...e] } = obj;
+            export let counter = 0;
+            export { a as renamed, helper, Local, Alias, Props };
+            export type { Other } from './other';
+            export { default as Button, type T } from './button';
+            export * from './utils';
+            export * as icons from './icons';
+            export type * from './types';
+            export const { b: bb, ...rest } = obj;
+            export enum Color { Red }
+            export declare const ambient: number;
+            export default async function Page() {}
+        "#;
+        let analysis = analyze_module(source, "page.tsx");
+        assert_eq!(
+            analysis.exports,
+            vec!["counter", "renamed", "helper", "Button", "icons", "bb", "rest", "Color", "default"]
+        );
+
+        let entry = |name: &str| analysis.export_table.iter().find(|e| e.name == name).unwrap();
+        assert_eq!(entry("renamed").local.as_deref(), Some("a"));
+        assert_eq!(entry("Button").kind, ExportKind::ReExport);
+        assert_eq!(entry("Button").local.as_deref(), Some("default"));
+        assert_eq!(entry("Button").source.as_deref(), Some("./button"));
+        assert_eq!(entry("icons").kind, ExportKind::Namespace);
+        assert_eq!(entry("*").kind, ExportKind::Star);
+        assert_eq!(entry("*").source.as_deref(), Some("./utils"));
+        assert_eq!(analysis.default_export_name.as_deref(), Some("Page"));
+        assert_eq!(analysis.export_table.iter().filter(|e| e.kind == ExportKind::Star).count(), 1);
+    }
+
+    #[test]
+    fn test_typescript_generics() {
+        let source = "'use client';\nimport { useState } from 'react';\nconst n = useState<number>(0);\n";
+        let analysis = analyze_module(source, "hooks.ts");
+        assert!(analysis.is_client);
+        assert_eq!(analysis.client_hooks_used, vec!["useState"]);
+    }
+
+    #[test]
+    fn test_analyze_source_falls_back_to_typescript() {
+        let source = "declare const input: unknown;\nexport const id = <string>input;\n";
+        assert!(analyze_module(source, "module.tsx").parse_error.is_some());
+
+        let analysis = analyze_source(source);
+        assert!(analysis.parse_error.is_none());
+        assert_eq!(analysis.exports, vec!["id"]);
+    }
+
+    #[test]
+    fn test_segment_config_exports() {
+        let analysis = analyze_source(
+            "export const dynamic = 'force-static';\nexport const revalidate = 60, runtime = getRuntime();\nexport const other = 1;",
+        );
+        let config: Vec<_> = analysis.segment_config.iter()
+            .map(|c| (c.name.as_str(), c.value.clone(), c.line))
+            .collect();
+        assert_eq!(config, vec![
+            ("dynamic", Some(serde_json::json!("force-static")), 1),
+            ("revalidate", Some(serde_json::json!(60)), 2),
+            ("runtime", None, 2),
+        ]);
+    }
+
+    #[test]
+    fn test_static_metadata_extraction() {
+        let analysis = analyze_source(r#"
+            export const metadata = {
+                title: { default: 'Docs', template: `%s | Docs` },
+                description: 'Guides',
+                openGraph: { images: ['/og.png', ogImage(), { url: '/b.png', alt }] },
+                robots: { index: true, follow: false },
+                keywords: [...base, 'vista'],
+                metadataBase: new URL(process.env.SITE_URL),
+            } satisfies Metadata;
+        "#);
+        assert_eq!(analysis.static_metadata, Some(serde_json::json!({
+            "title": { "default": "Docs", "template": "%s | Docs" },
+            "description": "Guides",
+            "openGraph": {},
+            "robots": { "index": true, "follow": false },
+        })));
+        let issues: Vec<_> = analysis.metadata_issues.iter().map(|i| (i.path.as_str(), i.line)).collect();
+        assert_eq!(issues, vec![
+            ("metadata.openGraph.images[1]", 5),
+            ("metadata.openGraph.images[2].alt", 5),
+            ("metadata.keywords[0]", 7),
+            ("metadata.metadataBase", 8),
+        ]);
+
+        let analysis = analyze_source("export const metadata = buildMetadata();");
+        assert_eq!((analysis.static_metadata, analysis.metadata_issues.len()), (None, 0));
+    }
+
+    #[test]
+    fn test_static_metadata_arrays_are_all_or_nothing() {
+        let analysis = analyze_source(r#"
+            export const metadata = {
+                keywords: ['docs', , 'vista'],
+                authors: [{ name: 'Ada' }, [1, 2]],
+                icons: ['/a.png', icon, '/c.png'],
+                alternates: [['/en', '/fr'], [locale]],
+            };
+        "#);
+        assert_eq!(analysis.static_metadata, Some(serde_json::json!({
+            "keywords": ["docs", null, "vista"],
+            "authors": [{ "name": "Ada" }, [1, 2]],
+        })));
+        let issues: Vec<_> = analysis.metadata_issues.iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(issues, vec!["metadata.icons[1]", "metadata.alternates[1][0]"]);
+    }
+
+    #[test]
+    fn test_parse_error_is_reported() {
+        let analysis = analyze_module("const ok = 1;\nexport default function ( {", "page.tsx");
+        let error = analysis.parse_error.unwrap();
+        assert_eq!(error.line, 2);
+        assert!(!analysis.is_client);
+    }
+}
@@ -1,11 +1,12 @@
 //! Client Directive Transform
 //! 
 //! Detects `'use client'` directive at the top of files
-//! and marks them as client components.
+//! and marks them as client components. Backed by the SWC analyzer.
 
-use rustc_hash::FxHashSet;
 use serde::{Deserialize, Serialize};
 
+use crate::analyzer::analyze_source;
+
 /// Configuration for client directive detection
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ClientDirectiveConfig {
@@ -22,124 +23,57 @@ impl ClientDirectiveConfig {
 }
 
 /// Result of parsing a file for client directive
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ClientDirectiveResult {
     /// Whether the file has the client directive
     pub is_client: bool,
     /// The line number where directive was found (0 if not found)
     pub directive_line: usize,
-    /// List of exported component names (populated by full parsing)
+    /// List of exported component names (populated by `analyze_file`)
     pub exports: Vec<String>,
 }
 
-impl Default for ClientDirectiveResult {
-    fn default() -> Self {
-        Self {
-            is_client: false,
-            directive_line: 0,
-            exports: Vec::new(),
-        }
-    }
-}
-
-/// Check if a source string contains the client directive
-/// This is a fast string-based check without full parsing
+/// Check if a source string contains the client directive.
+/// The directive must be part of the module prologue; leading comments are allowed.
 pub fn has_client_directive(source: &str) -> bool {
-    let trimmed = source.trim_start();
-    
-    // Check for string literal directive at the start
-    if trimmed.starts_with("'use client'") || trimmed.starts_with("\"use client\"") {
-        return true;
-    }
-    
-    // Also check first significant line.
-    // The directive is valid only when it is the first non-empty line.
-    for line in source.lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
-        return line.starts_with("'use client'") || line.starts_with("\"use client\"");
-    }
-    
-    false
+    analyze_source(source).is_client
 }
 
-/// Quick check without full AST parsing
+/// Detect the client directive and the line it appears on
 pub fn detect_client_directive_fast(source: &str) -> ClientDirectiveResult {
-    let is_client = has_client_directive(source);
-    let directive_line = if is_client {
-        // Find the actual line number
-        for (idx, line) in source.lines().enumerate() {
-            let line = line.trim();
-            if line.starts_with("'use client'") || line.starts_with("\"use client\"") {
-                return ClientDirectiveResult {
-                    is_client: true,
-                    directive_line: idx + 1, // 1-indexed
-                    exports: Vec::new(),
-                };
-            }
-        }
-        1
-    } else {
-        0
-    };
-    
+    let analysis = analyze_source(source);
     ClientDirectiveResult {
-        is_client,
-        directive_line,
+        is_client: analysis.is_client,
+        directive_line: analysis.directive_line,
         exports: Vec::new(),
     }
 }
 
-/// Scan a source file and extract information about exports
-/// This uses simple regex-like matching, not full AST parsing
+/// Scan a source file and extract information about exports.
+/// Named default exports are reported by their local name.
 pub fn analyze_file(source: &str) -> ClientDirectiveResult {
-    let mut result = detect_client_directive_fast(source);
-    
-    // Simple export detection using string matching
-    let mut exports = FxHashSet::default();
-    
-    for line in source.lines() {
-        let trimmed = line.trim();
-        
-        // export default function Name
-        if trimmed.starts_with("export default function ") {
-            if let Some(name) = trimmed
-                .strip_prefix("export default function ")
-                .and_then(|s| s.split(['(', ' ', '<']).next())
-            {
-                if !name.is_empty() {
-                    exports.insert(name.to_string());
-                }
-            }
-        }
-        // export function Name
-        else if trimmed.starts_with("export function ") {
-            if let Some(name) = trimmed
-                .strip_prefix("export function ")
-                .and_then(|s| s.split(['(', ' ', '<']).next())
-            {
-                if !name.is_empty() {
-                    exports.insert(name.to_string());
-                }
-            }
-        }
-        // export const Name
-        else if trimmed.starts_with("export const ") {
-            if let Some(name) = trimmed
-                .strip_prefix("export const ")
-                .and_then(|s| s.split(['=', ':', ' ']).next())
-            {
-                if !name.is_empty() {
-                    exports.insert(name.to_string());
-                }
+    let analysis = analyze_source(source);
+
+    let mut exports = Vec::new();
+    for name in analysis.exports {
+        let name = if name == "default" {
+            match &analysis.default_export_name {
+                Some(local) => local.clone(),
+                None => continue,
             }
+        } else {
+            name
+        };
+        if !exports.contains(&name) {
+            exports.push(name);
         }
     }
-    
-    result.exports = exports.into_iter().collect();
-    result
+
+    ClientDirectiveResult {
+        is_client: analysis.is_client,
+        directive_line: analysis.directive_line,
+        exports,
+    }
 }
 
 #[cfg(test)]
@@ -152,7 +86,9 @@ mod tests {
         assert!(has_client_directive("\"use client\";\nexport default function() {}"));
         assert!(has_client_directive("  'use client'\n"));
         assert!(!has_client_directive("export default function() {}"));
-        assert!(!has_client_directive("// comment\n'use client'")); // directive not on first significant line
+        assert!(has_client_directive("// comment\n'use client'")); // comments may precede the directive
+        assert!(!has_client_directive("import a from 'a';\n'use client'")); // not in the prologue
+        assert!(!has_client_directive("const s = `\n'use client'\n`;"));
     }
 
     #[test]
@@ -0,0 +1,190 @@
+//! Diagnostics
+//!
+//! Structured errors and warnings with stable codes, source spans and a
+//! rendered code frame, shared by the scanner, manifests and validators.
+
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+/// Lines of context shown above and below the primary line in a code frame
+const CODE_FRAME_CONTEXT: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        }
+    }
+}
+
+/// A location in a source file (1-indexed line and column)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceSpan {
+    /// Absolute file path
+    pub path: String,
+    /// Path relative to the app directory
+    pub relative_path: String,
+    pub line: usize,
+    pub column: usize,
+    /// Short explanation shown next to the location
+    pub label: Option<String>,
+}
+
+impl SourceSpan {
+    pub fn new(path: &str, relative_path: &str, line: usize, column: usize) -> Self {
+        Self {
+            path: path.to_string(),
+            relative_path: relative_path.to_string(),
+            line,
+            column,
+            label: None,
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
+
+/// An error or warning with a stable code
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Stable identifier, e.g. `CLIENT_API_IN_SERVER_COMPONENT`
+    pub code: String,
+    pub message: String,
+    /// Where the problem is reported
+    pub primary_span: Option<SourceSpan>,
+    /// Related locations (e.g. the hook calls behind an import)
+    pub secondary_spans: Vec<SourceSpan>,
+    /// Source excerpt around the primary span with a caret under the column
+    pub code_frame: Option<String>,
+    /// Suggested fix
+    pub fix: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, code: &str, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            code: code.to_string(),
+            message: message.into(),
+            primary_span: None,
+            secondary_spans: Vec::new(),
+            code_frame: None,
+            fix: None,
+        }
+    }
+
+    pub fn error(code: &str, message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, code, message)
+    }
+
+    pub fn warning(code: &str, message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, code, message)
+    }
+
+    pub fn with_primary_span(mut self, span: SourceSpan) -> Self {
+        self.primary_span = Some(span);
+        self
+    }
+
+    pub fn with_secondary_spans(mut self, spans: impl IntoIterator<Item = SourceSpan>) -> Self {
+        self.secondary_spans.extend(spans);
+        self
+    }
+
+    pub fn with_fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+
+    /// Render the code frame from the given source of the primary span's file
+    pub fn with_code_frame(mut self, source: &str) -> Self {
+        if let Some(span) = &self.primary_span {
+            self.code_frame = render_code_frame(source, span.line, span.column);
+        }
+        self
+    }
+
+    /// Render the code frame by reading the primary span's file from disk
+    pub fn with_code_frame_from_disk(self) -> Self {
+        let source = self
+            .primary_span
+            .as_ref()
+            .and_then(|span| fs::read_to_string(&span.path).ok());
+        match source {
+            Some(source) => self.with_code_frame(&source),
+            None => self,
+        }
+    }
+}
+
+/// Render lines around `line` with a gutter and a caret under `column`:
+///
+/// ```text
+///   2 | export default function Page() {
+/// > 3 |   const [a] = useState(0);
+///     |               ^
+///   4 | }
+/// ```
+pub fn render_code_frame(source: &str, line: usize, column: usize) -> Option<String> {
+    let lines: Vec<&str> = source.lines().collect();
+    if line == 0 || line > lines.len() {
+        return None;
+    }
+    let first = line.saturating_sub(CODE_FRAME_CONTEXT).max(1);
+    let last = (line + CODE_FRAME_CONTEXT).min(lines.len());
+    let width = last.to_string().len();
+
+    let mut frame = Vec::new();
+    for number in first..=last {
+        let text = lines[number - 1];
+        let marker = if number == line { '>' } else { ' ' };
+        frame.push(format!("{marker} {number:>width$} | {text}").trim_end().to_string());
+        if number == line {
+            // Keep tabs so the caret lines up with the source
+            let padding: String = text
+                .chars()
+                .take(column.saturating_sub(1))
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            frame.push(format!("  {:width$} | {padding}^", ""));
+        }
+    }
+    Some(frame.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_code_frame() {
+        let source = "import x from 'y';\nexport default function Page() {\n  const [a] = useState(0);\n}\n";
+        let frame = render_code_frame(source, 3, 15).unwrap();
+        assert_eq!(
+            frame,
+            "  1 | import x from 'y';\n  2 | export default function Page() {\n> 3 |   const [a] = useState(0);\n    |               ^\n  4 | }"
+        );
+        assert_eq!(render_code_frame(source, 9, 1), None);
+    }
+
+    #[test]
+    fn test_diagnostic_builder() {
+        let diagnostic = Diagnostic::error("PARSE_ERROR", "Unexpected token")
+            .with_primary_span(SourceSpan::new("/app/page.tsx", "page.tsx", 1, 5))
+            .with_code_frame("let = 1;");
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.code_frame.as_deref(), Some("> 1 | let = 1;\n    |     ^"));
+    }
+}
@@ -0,0 +1,32 @@
+//! Stable Hashing
+//!
+//! Hashes that end up in build artifacts (action IDs, cache keys) must not
+//! change between Rust versions or platforms, so they use SHA-256 rather
+//! than `std`'s `DefaultHasher`.
+
+use sha2::{Digest, Sha256};
+
+/// Hex-encoded SHA-256 of `bytes`, truncated to `len` hex characters
+pub fn stable_hash_hex(bytes: &[u8], len: usize) -> String {
+    let digest = Sha256::digest(bytes);
+    let mut hex = String::with_capacity(digest.len() * 2);
+    for byte in digest {
+        hex.push_str(&format!("{byte:02x}"));
+    }
+    hex.truncate(len);
+    hex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stable_hash_hex() {
+        assert_eq!(
+            stable_hash_hex(b"vista", 64),
+            "527bc96e01d06a60a1157700059ea2d66906584a2f760f87c6e371a4bf5dc252"
+        );
+        assert_eq!(stable_hash_hex(b"vista", 8).len(), 8);
+    }
+}
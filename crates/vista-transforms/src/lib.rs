@@ -3,15 +3,25 @@
 //! This crate provides Rust-based transforms for Vista framework.
 //!
 //! Features:
+//! - SWC-based module analysis (directives, exports, client-only APIs)
+//! - Diagnostics with stable codes, spans and code frames
 //! - `'use client'` directive detection
 //! - Component categorization (server vs client)
 //! - RSC (React Server Components) build system
 //! - Manifest generation
+//! - Metadata to `<head>` HTML rendering
 //! - RSC payload serialization
 
 pub mod naming;
+pub mod hash;
+pub mod analyzer;
+pub mod diagnostics;
 pub mod client_directive;
+pub mod metadata;
 pub mod rsc;
 
+pub use analyzer::*;
+pub use diagnostics::*;
 pub use client_directive::*;
+pub use metadata::*;
 pub use rsc::*;
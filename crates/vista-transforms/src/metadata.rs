@@ -0,0 +1,429 @@
+//! Metadata Head Rendering
+//!
+//! Renders `Metadata` JSON (the `metadata` exports of layouts and pages, or
+//! what `generateMetadata` returned) to `<head>` HTML: title, basic meta
+//! tags, authors, robots, OpenGraph, Twitter, icons, verification,
+//! alternates and the web manifest, in the same order as the JS renderer.
+//! All text goes through a single escaping path.
+
+use serde_json::{Map, Value};
+
+/// Merge a layout chain's metadata, outermost first, into one object.
+///
+/// Top-level fields of nested segments replace their parent's (so a page's
+/// `openGraph` replaces the layout's entirely). `title` is resolved to a
+/// string: a segment's `title.template` applies to the titles of its
+/// descendants, not its own `title.default`, and `title.absolute` skips
+/// inherited templates.
+pub fn merge_metadata(chain: &[Value]) -> Value {
+    let mut merged = Map::new();
+    let mut template: Option<String> = None;
+    let mut title: Option<String> = None;
+
+    for metadata in chain {
+        let Value::Object(metadata) = metadata else {
+            continue;
+        };
+        for (key, value) in metadata {
+            if key == "title" {
+                if let Some(resolved) = resolve_title(value, template.as_deref()) {
+                    title = Some(resolved);
+                }
+                if let Some(own) = value.get("template").and_then(Value::as_str) {
+                    template = Some(own.to_string());
+                }
+            } else {
+                merged.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    if let Some(title) = title {
+        merged.insert("title".to_string(), Value::String(title));
+    }
+    Value::Object(merged)
+}
+
+/// Render a layout chain's metadata (outermost first) to head HTML
+pub fn render_metadata_head(chain: &[Value]) -> String {
+    render_metadata(&merge_metadata(chain))
+}
+
+/// Render one metadata object to head HTML. Identical tags are emitted once.
+pub fn render_metadata(metadata: &Value) -> String {
+    let mut head = Head::default();
+    let Value::Object(metadata) = metadata else {
+        return String::new();
+    };
+    let base = metadata.get("metadataBase").and_then(text);
+
+    if let Some(title) = metadata.get("title").and_then(|t| resolve_title(t, None)) {
+        head.push(format!("<title>{}</title>", escape_text(&title)));
+    }
+
+    for (key, name) in [
+        ("description", "description"),
+        ("applicationName", "application-name"),
+        ("generator", "generator"),
+        ("keywords", "keywords"),
+        ("referrer", "referrer"),
+        ("creator", "creator"),
+        ("publisher", "publisher"),
+        ("category", "category"),
+        ("abstract", "abstract"),
+    ] {
+        let content = match metadata.get(key) {
+            Some(Value::Array(values)) => {
+                Some(values.iter().filter_map(text).collect::<Vec<_>>().join(", "))
+            }
+            Some(value) => text(value),
+            None => None,
+        };
+        if let Some(content) = content.filter(|c| !c.is_empty()) {
+            head.meta("name", name, &content);
+        }
+    }
+
+    for author in list(metadata.get("authors")) {
+        if let Some(name) = author.get("name").and_then(text) {
+            head.meta("name", "author", &name);
+        }
+        if let Some(url) = author.get("url").and_then(text) {
+            head.link(&[("rel", "author"), ("href", &url)]);
+        }
+    }
+
+    match metadata.get("robots") {
+        Some(Value::Object(robots)) => {
+            if let Some(content) = robots_directives(robots) {
+                head.meta("name", "robots", &content);
+            }
+            let google_bot = match robots.get("googleBot") {
+                Some(Value::Object(google_bot)) => robots_directives(google_bot),
+                Some(value) => text(value),
+                None => None,
+            };
+            if let Some(content) = google_bot {
+                head.meta("name", "googlebot", &content);
+            }
+        }
+        Some(value) => {
+            if let Some(content) = text(value) {
+                head.meta("name", "robots", &content);
+            }
+        }
+        None => {}
+    }
+
+    if let Some(Value::Object(og)) = metadata.get("openGraph") {
+        let og_type = og.get("type").and_then(text).unwrap_or_else(|| "website".to_string());
+        head.meta("property", "og:type", &og_type);
+        for (key, property) in [("title", "og:title"), ("description", "og:description")] {
+            if let Some(content) = og.get(key).and_then(text) {
+                head.meta("property", property, &content);
+            }
+        }
+        if let Some(url) = og.get("url").and_then(text) {
+            head.meta("property", "og:url", &resolve_url(&url, base.as_deref()));
+        }
+        for (key, property) in [("siteName", "og:site_name"), ("locale", "og:locale")] {
+            if let Some(content) = og.get(key).and_then(text) {
+                head.meta("property", property, &content);
+            }
+        }
+        for image in list(og.get("images")) {
+            let url = match image {
+                Value::Object(image) => image.get("url").and_then(text),
+                other => text(other),
+            };
+            let Some(url) = url else {
+                continue;
+            };
+            head.meta("property", "og:image", &resolve_url(&url, base.as_deref()));
+            for (key, property) in [
+                ("width", "og:image:width"),
+                ("height", "og:image:height"),
+                ("alt", "og:image:alt"),
+            ] {
+                if let Some(content) = image.get(key).and_then(text) {
+                    head.meta("property", property, &content);
+                }
+            }
+        }
+    }
+
+    if let Some(Value::Object(twitter)) = metadata.get("twitter") {
+        for key in ["card", "site", "creator", "title", "description"] {
+            if let Some(content) = twitter.get(key).and_then(text) {
+                head.meta("name", &format!("twitter:{key}"), &content);
+            }
+        }
+        for image in list(twitter.get("images")) {
+            let url = match image {
+                Value::Object(image) => image.get("url").and_then(text),
+                other => text(other),
+            };
+            if let Some(url) = url {
+                head.meta("name", "twitter:image", &resolve_url(&url, base.as_deref()));
+            }
+        }
+    }
+
+    match metadata.get("icons") {
+        Some(Value::Object(icons)) if !icons.contains_key("url") => {
+            for (key, rel) in [("icon", "icon"), ("shortcut", "shortcut icon"), ("apple", "apple-touch-icon")] {
+                for icon in list(icons.get(key)) {
+                    head.icon(icon, rel);
+                }
+            }
+        }
+        icons => {
+            for icon in list(icons) {
+                head.icon(icon, "icon");
+            }
+        }
+    }
+
+    if let Some(Value::Object(verification)) = metadata.get("verification") {
+        for (key, name) in [
+            ("google", "google-site-verification"),
+            ("yandex", "yandex-verification"),
+            ("bing", "msvalidate.01"),
+        ] {
+            for value in list(verification.get(key)).filter_map(text) {
+                head.meta("name", name, &value);
+            }
+        }
+    }
+
+    if let Some(Value::Object(alternates)) = metadata.get("alternates") {
+        if let Some(canonical) = alternates.get("canonical").and_then(text) {
+            head.link(&[("rel", "canonical"), ("href", &resolve_url(&canonical, base.as_deref()))]);
+        }
+        if let Some(Value::Object(languages)) = alternates.get("languages") {
+            for (lang, urls) in languages {
+                for url in list(Some(urls)).filter_map(text) {
+                    let href = resolve_url(&url, base.as_deref());
+                    head.link(&[("rel", "alternate"), ("hreflang", lang), ("href", &href)]);
+                }
+            }
+        }
+    }
+
+    if let Some(manifest) = metadata.get("manifest").and_then(text) {
+        head.link(&[("rel", "manifest"), ("href", &resolve_url(&manifest, base.as_deref()))]);
+    }
+
+    head.html
+}
+
+/// Collected tags, skipping exact duplicates
+#[derive(Default)]
+struct Head {
+    html: String,
+    seen: Vec<String>,
+}
+
+impl Head {
+    fn push(&mut self, tag: String) {
+        if !self.seen.contains(&tag) {
+            self.html.push_str(&tag);
+            self.seen.push(tag);
+        }
+    }
+
+    fn meta(&mut self, attr: &str, name: &str, content: &str) {
+        self.push(format!(
+            "<meta {attr}=\"{}\" content=\"{}\"/>",
+            escape_attr(name),
+            escape_attr(content)
+        ));
+    }
+
+    fn link(&mut self, attrs: &[(&str, &str)]) {
+        let attrs: String = attrs
+            .iter()
+            .map(|(name, value)| format!(" {name}=\"{}\"", escape_attr(value)))
+            .collect();
+        self.push(format!("<link{attrs}/>"));
+    }
+
+    fn icon(&mut self, icon: &Value, rel: &str) {
+        let (url, rel, kind, sizes) = match icon {
+            Value::Object(icon) => (
+                icon.get("url").and_then(text),
+                icon.get("rel").and_then(text).unwrap_or_else(|| rel.to_string()),
+                icon.get("type").and_then(text),
+                icon.get("sizes").and_then(text),
+            ),
+            other => (text(other), rel.to_string(), None, None),
+        };
+        let Some(url) = url else {
+            return;
+        };
+        let mut attrs = vec![("rel", rel.as_str()), ("href", url.as_str())];
+        attrs.extend(kind.as_deref().map(|kind| ("type", kind)));
+        attrs.extend(sizes.as_deref().map(|sizes| ("sizes", sizes)));
+        self.link(&attrs);
+    }
+}
+
+/// Resolve a `title` field against an inherited template
+fn resolve_title(title: &Value, template: Option<&str>) -> Option<String> {
+    let apply = |title: &str| match template {
+        Some(template) => template.replacen("%s", title, 1),
+        None => title.to_string(),
+    };
+    match title {
+        Value::String(title) if !title.is_empty() => Some(apply(title)),
+        Value::Object(title) => {
+            if let Some(absolute) = title.get("absolute").and_then(Value::as_str) {
+                return Some(absolute.to_string());
+            }
+            title.get("default").and_then(Value::as_str).map(apply)
+        }
+        _ => None,
+    }
+}
+
+fn robots_directives(robots: &Map<String, Value>) -> Option<String> {
+    let mut directives = Vec::new();
+    let flag = |key: &str| robots.get(key).and_then(Value::as_bool);
+
+    if let Some(index) = flag("index") {
+        directives.push(if index { "index" } else { "noindex" }.to_string());
+    }
+    if let Some(follow) = flag("follow") {
+        directives.push(if follow { "follow" } else { "nofollow" }.to_string());
+    }
+    for key in ["noarchive", "nosnippet", "noimageindex", "nocache"] {
+        if flag(key) == Some(true) {
+            directives.push(key.to_string());
+        }
+    }
+    for key in ["max-snippet", "max-image-preview", "max-video-preview"] {
+        if let Some(value) = robots.get(key).and_then(text) {
+            directives.push(format!("{key}:{value}"));
+        }
+    }
+
+    (!directives.is_empty()).then(|| directives.join(", "))
+}
+
+/// A value, or each value of an array
+fn list(value: Option<&Value>) -> impl Iterator<Item = &Value> {
+    let values: Vec<&Value> = match value {
+        Some(Value::Array(values)) => values.iter().collect(),
+        Some(Value::Null) | None => Vec::new(),
+        Some(value) => vec![value],
+    };
+    values.into_iter()
+}
+
+/// Non-empty string or number as text
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Resolve a URL against `metadataBase` like `new URL(url, base)` does for
+/// paths; absolute URLs are kept as they are
+fn resolve_url(url: &str, base: Option<&str>) -> String {
+    let is_absolute = url.starts_with("//")
+        || url.split_once(':').is_some_and(|(scheme, _)| {
+            !scheme.is_empty() && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        });
+    let Some(base) = base.filter(|_| !is_absolute) else {
+        return url.to_string();
+    };
+
+    let origin_end = base
+        .find("://")
+        .and_then(|scheme| base[scheme + 3..].find('/').map(|path| scheme + 3 + path))
+        .unwrap_or(base.len());
+    let origin = &base[..origin_end];
+    if url.starts_with('/') {
+        return format!("{origin}{url}");
+    }
+    let directory = &base[origin_end..];
+    let directory = &directory[..directory.rfind('/').map_or(0, |i| i + 1)];
+    let directory = if directory.is_empty() { "/" } else { directory };
+    format!("{origin}{directory}{url}")
+}
+
+fn escape_text(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn escape_attr(value: &str) -> String {
+    escape_text(value).replace('"', "&quot;").replace('\'', "&#x27;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_title_templates_through_layouts() {
+        let root = json!({ "title": { "default": "Acme", "template": "%s | Acme" }, "description": "Root" });
+        let blog = json!({ "title": { "default": "Blog", "template": "%s - Blog | Acme" } });
+
+        // Not "Acme | Acme": the root's own template is for its descendants,
+        // matching resolveTitle in packages/vista/src/metadata/generate.tsx
+        assert_eq!(merge_metadata(std::slice::from_ref(&root))["title"], "Acme");
+        assert!(render_metadata_head(std::slice::from_ref(&root)).starts_with("<title>Acme</title>"));
+        assert_eq!(merge_metadata(&[root.clone(), blog.clone()])["title"], "Blog | Acme");
+        let post = json!({ "title": "Hello", "description": "Post" });
+        let merged = merge_metadata(&[root.clone(), blog.clone(), post]);
+        assert_eq!((&merged["title"], &merged["description"]), (&json!("Hello - Blog | Acme"), &json!("Post")));
+        let absolute = json!({ "title": { "absolute": "Standalone" } });
+        assert_eq!(merge_metadata(&[root.clone(), blog, absolute])["title"], "Standalone");
+        // A page without a title keeps the nearest layout's
+        assert_eq!(merge_metadata(&[root, json!({ "robots": "noindex" })])["title"], "Acme");
+    }
+
+    #[test]
+    fn test_render_metadata_head() {
+        let html = render_metadata_head(&[json!({
+            "metadataBase": "https://acme.dev/docs/",
+            "title": "Tips & <Tricks>",
+            "description": "Say \"hi\"",
+            "keywords": ["a", "b"],
+            "robots": { "index": false, "follow": true, "googleBot": { "index": true } },
+            "openGraph": { "images": ["/og.png", { "url": "card.png", "width": 1200, "alt": "Card" }] },
+            "icons": { "icon": ["/favicon.ico", "/favicon.ico"], "apple": { "url": "/apple.png", "sizes": "180x180" } },
+            "alternates": { "canonical": "/docs", "languages": { "de": "https://acme.de/docs" } },
+        })]);
+
+        assert_eq!(html, concat!(
+            "<title>Tips &amp; &lt;Tricks&gt;</title>",
+            "<meta name=\"description\" content=\"Say &quot;hi&quot;\"/>",
+            "<meta name=\"keywords\" content=\"a, b\"/>",
+            "<meta name=\"robots\" content=\"noindex, follow\"/>",
+            "<meta name=\"googlebot\" content=\"index\"/>",
+            "<meta property=\"og:type\" content=\"website\"/>",
+            "<meta property=\"og:image\" content=\"https://acme.dev/og.png\"/>",
+            "<meta property=\"og:image\" content=\"https://acme.dev/docs/card.png\"/>",
+            "<meta property=\"og:image:width\" content=\"1200\"/>",
+            "<meta property=\"og:image:alt\" content=\"Card\"/>",
+            "<link rel=\"icon\" href=\"/favicon.ico\"/>",
+            "<link rel=\"apple-touch-icon\" href=\"/apple.png\" sizes=\"180x180\"/>",
+            "<link rel=\"canonical\" href=\"https://acme.dev/docs\"/>",
+            "<link rel=\"alternate\" hreflang=\"de\" href=\"https://acme.de/docs\"/>",
+        ));
+        assert_eq!(render_metadata(&json!("title")), "");
+    }
+
+    #[test]
+    fn test_resolve_url() {
+        assert_eq!(resolve_url("/a.png", Some("https://acme.dev")), "https://acme.dev/a.png");
+        assert_eq!(resolve_url("a.png", Some("https://acme.dev")), "https://acme.dev/a.png");
+        assert_eq!(resolve_url("a.png", Some("https://acme.dev/x/y")), "https://acme.dev/x/a.png");
+        assert_eq!(resolve_url("mailto:a@b.c", Some("https://acme.dev")), "mailto:a@b.c");
+        assert_eq!(resolve_url("/a.png", None), "/a.png");
+    }
+}
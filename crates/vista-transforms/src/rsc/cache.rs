@@ -0,0 +1,271 @@
+//! RSC Scan Cache
+//!
+//! Persists per-file analysis under `.vista/cache/` keyed by path, mtime and
+//! content hash, so repeated scans only re-analyse files that changed and
+//! can report what was added, changed or removed since the last scan.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use super::scanner::{scan_source, ScannedComponent};
+use crate::{analyzer::ParseError, hash::stable_hash_hex, naming};
+
+/// Cache file name inside the cache directory
+pub const SCAN_CACHE_FILE: &str = "scan-cache.json";
+
+/// Bump when the cached analysis format changes
+const CACHE_FORMAT: u32 = 10;
+
+fn cache_version() -> String {
+    format!("{}-{}", env!("CARGO_PKG_VERSION"), CACHE_FORMAT)
+}
+
+/// Project root: the nearest ancestor of the app directory with a
+/// `package.json` (or its parent)
+pub fn project_root(app_dir: &Path) -> &Path {
+    app_dir
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join("package.json").is_file())
+        .or_else(|| app_dir.parent())
+        .unwrap_or(app_dir)
+}
+
+/// Build directory: `.vista` in the project root
+pub fn default_build_dir(app_dir: &Path) -> PathBuf {
+    project_root(app_dir).join(naming::BUILD_DIR)
+}
+
+/// Default cache directory: `.vista/cache` in the project root
+pub fn default_cache_dir(app_dir: &Path) -> PathBuf {
+    default_build_dir(app_dir).join("cache")
+}
+
+/// Cached analysis of one file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    mtime_ns: u64,
+    size: u64,
+    content_hash: String,
+    component: ScannedComponent,
+    parse_error: Option<ParseError>,
+}
+
+/// Persistent scan cache keyed by absolute path
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanCache {
+    version: String,
+    entries: HashMap<String, CacheEntry>,
+}
+
+impl ScanCache {
+    /// Load a cache file. Missing, unreadable or outdated caches load as empty.
+    pub fn load(path: &Path) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<ScanCache>(&bytes).ok())
+            .filter(|cache| cache.version == cache_version())
+            .unwrap_or_default()
+    }
+
+    /// Write the cache atomically (temp file + rename)
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        fs::write(&tmp, serde_json::to_vec(self)?)?;
+        fs::rename(&tmp, path)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Files added, changed or removed since the previous scan (sorted relative paths)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanDelta {
+    pub added: Vec<String>,
+    pub changed: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl ScanDelta {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Scans files through a previous cache, recording fresh entries. Safe to
+/// share between walker threads.
+#[derive(Default)]
+pub(crate) struct CachedScanner {
+    previous: ScanCache,
+    current: Mutex<HashMap<String, CacheEntry>>,
+    changed: Mutex<Vec<String>>,
+}
+
+impl CachedScanner {
+    pub(crate) fn new(previous: ScanCache) -> Self {
+        Self {
+            previous,
+            ..Default::default()
+        }
+    }
+
+    /// Scan a file, reusing the cached analysis when mtime and size match or
+    /// when the content hash is unchanged
+    pub(crate) fn scan(
+        &self,
+        path: &Path,
+        app_dir: &Path,
+    ) -> Option<(ScannedComponent, Option<ParseError>)> {
+        let key = path.to_string_lossy().to_string();
+        let metadata = fs::metadata(path).ok()?;
+        let size = metadata.len();
+        let mtime_ns = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+
+        let previous = self.previous.entries.get(&key);
+        if let Some(entry) = previous.filter(|e| e.mtime_ns == mtime_ns && e.size == size) {
+            return Some(self.record(key, entry.clone()));
+        }
+
+        let source = fs::read_to_string(path).ok()?;
+        let content_hash = stable_hash_hex(source.as_bytes(), 32);
+
+        let entry = match previous.filter(|e| e.content_hash == content_hash) {
+            Some(entry) => CacheEntry {
+                mtime_ns,
+                size,
+                ..entry.clone()
+            },
+            None => {
+                let (component, parse_error) = scan_source(path, app_dir, &source)?;
+                if previous.is_some() {
+                    self.changed
+                        .lock()
+                        .unwrap()
+                        .push(component.relative_path.clone());
+                }
+                CacheEntry {
+                    mtime_ns,
+                    size,
+                    content_hash,
+                    component,
+                    parse_error,
+                }
+            }
+        };
+        Some(self.record(key, entry))
+    }
+
+    fn record(&self, key: String, entry: CacheEntry) -> (ScannedComponent, Option<ParseError>) {
+        let result = (entry.component.clone(), entry.parse_error.clone());
+        self.current.lock().unwrap().insert(key, entry);
+        result
+    }
+
+    /// The cache for the next scan and the delta against the previous one
+    pub(crate) fn finish(self) -> (ScanCache, ScanDelta) {
+        let current = self.current.into_inner().unwrap();
+        let mut changed = self.changed.into_inner().unwrap();
+
+        let mut added: Vec<String> = current
+            .iter()
+            .filter(|(key, _)| !self.previous.entries.contains_key(*key))
+            .map(|(_, entry)| entry.component.relative_path.clone())
+            .collect();
+        let mut removed: Vec<String> = self
+            .previous
+            .entries
+            .iter()
+            .filter(|(key, _)| !current.contains_key(*key))
+            .map(|(_, entry)| entry.component.relative_path.clone())
+            .collect();
+
+        added.sort();
+        changed.sort();
+        removed.sort();
+
+        let cache = ScanCache {
+            version: cache_version(),
+            entries: current,
+        };
+        (cache, ScanDelta { added, changed, removed })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rsc::{scan_app_directory_incremental, ScanOptions};
+
+    fn write(root: &Path, relative: &str, contents: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_default_cache_dir_uses_project_root() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "package.json", "{}");
+        let app_dir = dir.path().join("src/app");
+        assert_eq!(default_cache_dir(&app_dir), dir.path().join(".vista/cache"));
+        assert_eq!(default_cache_dir(Path::new("/x/app")), Path::new("/x/.vista/cache"));
+    }
+
+    #[test]
+    fn test_incremental_scan_reports_delta() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "app/page.tsx", "export default function Page() {}");
+        write(root, "app/nav.tsx", "export default function Nav() {}");
+        write(root, "app/old.tsx", "export default function Old() {}");
+
+        let app_dir = root.join("app").to_string_lossy().to_string();
+        let options = ScanOptions::default();
+
+        let (first, delta) = scan_app_directory_incremental(&app_dir, &options);
+        assert_eq!(first.total_files, 3);
+        assert_eq!(delta.added, vec!["nav.tsx", "old.tsx", "page.tsx"]);
+        assert!(root.join(".vista/cache").join(SCAN_CACHE_FILE).is_file());
+
+        let (_, delta) = scan_app_directory_incremental(&app_dir, &options);
+        assert!(delta.is_empty());
+
+        write(root, "app/nav.tsx", "'use client';\nexport default function Nav() {}");
+        write(root, "app/footer.tsx", "export default function Footer() {}");
+        fs::remove_file(root.join("app/old.tsx")).unwrap();
+
+        let (result, delta) = scan_app_directory_incremental(&app_dir, &options);
+        assert_eq!(delta.added, vec!["footer.tsx"]);
+        assert_eq!(delta.changed, vec!["nav.tsx"]);
+        assert_eq!(delta.removed, vec!["old.tsx"]);
+        assert_eq!(result.client_components.len(), 1);
+    }
+
+    #[test]
+    fn test_outdated_cache_loads_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SCAN_CACHE_FILE);
+        fs::write(&path, r#"{"version":"0.0.0-0","entries":{}}"#).unwrap();
+        assert!(ScanCache::load(&path).is_empty());
+        assert!(ScanCache::load(&dir.path().join("missing.json")).is_empty());
+    }
+}
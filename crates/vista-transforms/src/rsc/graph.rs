@@ -0,0 +1,586 @@
+//! RSC Module Graph
+//!
+//! Resolves relative and alias imports between modules and builds the
+//! dependency graph used to find which client components a server module
+//! pulls in. Modules outside the app directory are analyzed on demand.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::cache::CachedScanner;
+use super::scanner::{parse_failure, ScannedComponent, ServerComponentError};
+use crate::analyzer::{parse_jsonc, ClientApiUsage, ImportKind};
+
+/// Extensions tried when resolving an import without one
+const RESOLVE_EXTENSIONS: &[&str] = &["tsx", "ts", "jsx", "js"];
+
+/// A `compilerOptions.paths` entry
+#[derive(Debug, Clone)]
+struct PathAlias {
+    /// Pattern with at most one `*`, e.g. `@/*`
+    pattern: String,
+    /// Target patterns relative to the paths base
+    targets: Vec<String>,
+}
+
+/// Resolves import specifiers to module files using `tsconfig.json`/`jsconfig.json`
+/// `baseUrl` and `paths`
+#[derive(Debug, Clone, Default)]
+pub struct ImportResolver {
+    base_url: Option<PathBuf>,
+    paths_base: PathBuf,
+    paths: Vec<PathAlias>,
+}
+
+impl ImportResolver {
+    /// Use the nearest `tsconfig.json` or `jsconfig.json` at or above the app directory
+    pub fn for_app_dir(app_dir: &Path) -> Self {
+        for dir in app_dir.ancestors() {
+            for name in ["tsconfig.json", "jsconfig.json"] {
+                let config_path = dir.join(name);
+                let Ok(source) = fs::read_to_string(&config_path) else {
+                    continue;
+                };
+                return match parse_jsonc(&source) {
+                    Some(config) => Self::from_config(dir, &config),
+                    None => Self::default(),
+                };
+            }
+        }
+        Self::default()
+    }
+
+    /// Build a resolver from a parsed config located in `config_dir`
+    pub fn from_config(config_dir: &Path, config: &serde_json::Value) -> Self {
+        let options = &config["compilerOptions"];
+        let base_url = options["baseUrl"]
+            .as_str()
+            .map(|base| normalize_path(&config_dir.join(base)));
+        let paths_base = base_url.clone().unwrap_or_else(|| config_dir.to_path_buf());
+
+        let mut paths: Vec<PathAlias> = options["paths"]
+            .as_object()
+            .map(|paths| {
+                paths
+                    .iter()
+                    .map(|(pattern, targets)| PathAlias {
+                        pattern: pattern.clone(),
+                        targets: targets
+                            .as_array()
+                            .map(|t| t.iter().filter_map(|t| t.as_str().map(String::from)).collect())
+                            .unwrap_or_default(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        // Longest prefix wins, like TypeScript
+        paths.sort_by_key(|alias| std::cmp::Reverse(alias.pattern.split('*').next().unwrap_or("").len()));
+
+        Self { base_url, paths_base, paths }
+    }
+
+    /// Resolve `specifier` imported from `importer` to a local module file.
+    /// Returns `None` for packages and anything that doesn't exist on disk.
+    pub fn resolve(&self, importer: &Path, specifier: &str) -> Option<PathBuf> {
+        if specifier == "." || specifier == ".." || specifier.starts_with("./") || specifier.starts_with("../") {
+            let base = importer.parent()?;
+            return resolve_file(&normalize_path(&base.join(specifier)));
+        }
+        if Path::new(specifier).is_absolute() {
+            return resolve_file(&normalize_path(Path::new(specifier)));
+        }
+
+        for alias in &self.paths {
+            let Some(capture) = match_alias(&alias.pattern, specifier) else {
+                continue;
+            };
+            for target in &alias.targets {
+                let target = target.replacen('*', capture, 1);
+                if let Some(path) = resolve_file(&normalize_path(&self.paths_base.join(target))) {
+                    return Some(path);
+                }
+            }
+        }
+
+        let base_url = self.base_url.as_ref()?;
+        resolve_file(&normalize_path(&base_url.join(specifier)))
+    }
+}
+
+/// Match a `paths` pattern, returning the text captured by `*`
+fn match_alias<'a>(pattern: &str, specifier: &'a str) -> Option<&'a str> {
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => specifier.strip_prefix(prefix)?.strip_suffix(suffix),
+        None => (pattern == specifier).then_some(""),
+    }
+}
+
+fn with_appended_extension(path: &Path, ext: &str) -> PathBuf {
+    let mut s = path.as_os_str().to_owned();
+    s.push(".");
+    s.push(ext);
+    PathBuf::from(s)
+}
+
+/// Resolve a path to a file: exact match, then with extensions, then `index` files
+fn resolve_file(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path.to_path_buf());
+    }
+
+    // TypeScript ESM style: `./button.js` refers to `./button.ts(x)`
+    if let Some("js" | "jsx") = path.extension().and_then(|e| e.to_str()) {
+        for ext in ["tsx", "ts"] {
+            let candidate = path.with_extension(ext);
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
+    }
+
+    for ext in RESOLVE_EXTENSIONS {
+        let candidate = with_appended_extension(path, ext);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+    }
+
+    if path.is_dir() {
+        for ext in RESOLVE_EXTENSIONS {
+            let candidate = path.join(format!("index.{ext}"));
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
+    }
+
+    None
+}
+
+/// Lexically normalize `.` and `..` components without touching the filesystem
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match out.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    out.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => out.push(".."),
+            },
+            other => out.push(other.as_os_str()),
+        }
+    }
+    out
+}
+
+/// `/`-separated path of `path` relative to `base` (`../` when outside it)
+pub(crate) fn relative_path_from(path: &Path, base: &Path) -> String {
+    let path = normalize_path(path);
+    let base = normalize_path(base);
+    let path_parts: Vec<_> = path.components().collect();
+    let base_parts: Vec<_> = base.components().collect();
+    let common = path_parts
+        .iter()
+        .zip(&base_parts)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); base_parts.len() - common];
+    parts.extend(
+        path_parts[common..]
+            .iter()
+            .map(|c| c.as_os_str().to_string_lossy().to_string()),
+    );
+    parts.join("/")
+}
+
+fn is_script_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| RESOLVE_EXTENSIONS.contains(&ext))
+}
+
+fn is_in_node_modules(path: &Path) -> bool {
+    path.components().any(|c| c.as_os_str() == "node_modules")
+}
+
+/// An import edge in the module graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportEdge {
+    /// Module specifier as written
+    pub specifier: String,
+    pub kind: ImportKind,
+    /// Line of the import in the importing module
+    pub line: usize,
+    /// Resolved module path, when the import points at a local script module
+    pub resolved: Option<String>,
+}
+
+/// A module in the dependency graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphModule {
+    /// Normalized absolute path (graph key)
+    pub absolute_path: String,
+    /// Path relative to the app directory (`../` for modules outside it)
+    pub relative_path: String,
+    /// Has a 'use client' directive
+    pub is_client: bool,
+    /// Has a file-level 'use server' directive
+    pub is_server: bool,
+    /// Runtime imports in source order
+    pub imports: Vec<ImportEdge>,
+    /// Client hooks/APIs used by this module
+    pub client_hooks_used: Vec<String>,
+    /// Location of each client hook/API call and event handler prop
+    pub client_api_usages: Vec<ClientApiUsage>,
+}
+
+/// A shortest chain of imports between two modules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportChain {
+    /// Relative paths from the first module to the last
+    pub modules: Vec<String>,
+    /// Absolute path of the first module
+    pub root_path: String,
+    /// Absolute path of the last module
+    pub target_path: String,
+    /// Line in the first module of the import that starts the chain
+    /// (0 when the chain is a single module)
+    pub import_line: usize,
+}
+
+/// Client-only API usage reached from a server module through its imports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientApiChain {
+    /// From the starting module to the module using the APIs
+    pub chain: ImportChain,
+    /// Client hooks/APIs used by the last module in the chain
+    pub hooks: Vec<String>,
+    /// Locations within the last module in the chain
+    pub usages: Vec<ClientApiUsage>,
+}
+
+/// A module importing a marker package, reached from a root module
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkerImportChain {
+    /// From the root to the module importing the marker
+    pub chain: ImportChain,
+    /// Line of the marker import in the last module
+    pub marker_line: usize,
+}
+
+/// Module dependency graph keyed by normalized absolute path
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModuleGraph {
+    pub modules: BTreeMap<String, GraphModule>,
+}
+
+/// Graph key for a path
+pub(crate) fn graph_key(path: &str) -> String {
+    normalize_path(Path::new(path)).to_string_lossy().to_string()
+}
+
+impl ModuleGraph {
+    /// Look up a module by (possibly non-normalized) path
+    pub fn get(&self, path: &str) -> Option<&GraphModule> {
+        self.modules.get(&graph_key(path))
+    }
+
+    /// Resolved path of an import of `specifier` in the module at `path`
+    pub fn resolved_import(&self, path: &str, specifier: &str) -> Option<&str> {
+        self.get(path)?
+            .imports
+            .iter()
+            .find(|edge| edge.specifier == specifier)?
+            .resolved
+            .as_deref()
+    }
+
+    /// Client modules reachable from `path` through imports, stopping at each
+    /// `'use client'` boundary. Returns sorted relative paths.
+    pub fn client_dependencies(&self, path: &str) -> Vec<String> {
+        let mut found = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![graph_key(path)];
+
+        while let Some(key) = stack.pop() {
+            if !visited.insert(key.clone()) {
+                continue;
+            }
+            let Some(module) = self.modules.get(&key) else {
+                continue;
+            };
+            for target in module.imports.iter().filter_map(|e| e.resolved.as_ref()) {
+                match self.modules.get(target) {
+                    Some(dep) if dep.is_client => {
+                        if !found.contains(&dep.relative_path) {
+                            found.push(dep.relative_path.clone());
+                        }
+                    }
+                    Some(_) => stack.push(target.clone()),
+                    None => {}
+                }
+            }
+        }
+
+        found.sort();
+        found
+    }
+
+    /// Modules importing the bare specifier `marker` (e.g. `server-only`) that
+    /// are reachable from any of `roots`, each reported once with the shortest
+    /// chain of relative paths from a root. Only modules accepted by `follow`
+    /// are traversed into.
+    pub fn marker_import_chains(
+        &self,
+        roots: &[String],
+        marker: &str,
+        follow: impl Fn(&GraphModule) -> bool,
+    ) -> Vec<MarkerImportChain> {
+        let mut parents: HashMap<String, String> = HashMap::new();
+        let mut queue: VecDeque<String> = roots.iter().map(|r| graph_key(r)).collect();
+        let mut visited: HashSet<String> = queue.iter().cloned().collect();
+        let mut chains = Vec::new();
+
+        while let Some(key) = queue.pop_front() {
+            let Some(module) = self.modules.get(&key) else {
+                continue;
+            };
+            if let Some(edge) = module.imports.iter().find(|edge| edge.specifier == marker) {
+                chains.push(MarkerImportChain {
+                    chain: self.chain_to(&key, &parents),
+                    marker_line: edge.line,
+                });
+            }
+            for target in module.imports.iter().filter_map(|e| e.resolved.as_ref()) {
+                let followed = self.modules.get(target).is_some_and(&follow);
+                if followed && visited.insert(target.clone()) {
+                    parents.insert(target.clone(), key.clone());
+                    queue.push_back(target.clone());
+                }
+            }
+        }
+
+        chains
+    }
+
+    /// Import chain from the BFS root to `key`
+    fn chain_to(&self, key: &str, parents: &HashMap<String, String>) -> ImportChain {
+        let mut keys = vec![key];
+        while let Some(parent) = parents.get(*keys.last().unwrap()) {
+            keys.push(parent);
+        }
+        keys.reverse();
+
+        let root = &self.modules[keys[0]];
+        let import_line = keys
+            .get(1)
+            .and_then(|next| {
+                root.imports
+                    .iter()
+                    .find(|edge| edge.resolved.as_deref() == Some(*next))
+            })
+            .map_or(0, |edge| edge.line);
+
+        ImportChain {
+            modules: keys.iter().map(|k| self.modules[*k].relative_path.clone()).collect(),
+            root_path: root.absolute_path.clone(),
+            target_path: key.to_string(),
+            import_line,
+        }
+    }
+
+    /// Modules using client-only APIs that `path` reaches through non-client
+    /// imports, each with the shortest import chain leading to it. Importing
+    /// a `'use client'` module is a boundary and is not followed.
+    pub fn client_api_chains(&self, path: &str) -> Vec<ClientApiChain> {
+        let start = graph_key(path);
+        let mut parents: HashMap<String, String> = HashMap::new();
+        let mut queue = VecDeque::from([start.clone()]);
+        let mut visited = HashSet::from([start.clone()]);
+        let mut chains = Vec::new();
+
+        while let Some(key) = queue.pop_front() {
+            let Some(module) = self.modules.get(&key) else {
+                continue;
+            };
+            if key != start && !module.client_hooks_used.is_empty() {
+                chains.push(ClientApiChain {
+                    chain: self.chain_to(&key, &parents),
+                    hooks: module.client_hooks_used.clone(),
+                    usages: module.client_api_usages.clone(),
+                });
+            }
+            for target in module.imports.iter().filter_map(|e| e.resolved.as_ref()) {
+                let is_server_module = self.modules.get(target).is_some_and(|m| !m.is_client);
+                if is_server_module && visited.insert(target.clone()) {
+                    parents.insert(target.clone(), key.clone());
+                    queue.push_back(target.clone());
+                }
+            }
+        }
+
+        chains
+    }
+}
+
+/// Build the module graph for the scanned app components. Modules outside the
+/// app directory that are reached through imports are analyzed and returned.
+pub(crate) fn build_module_graph(
+    app_dir: &Path,
+    components: &[ScannedComponent],
+    scanner: &CachedScanner,
+    errors: &mut Vec<ServerComponentError>,
+) -> (ModuleGraph, Vec<ScannedComponent>) {
+    let resolver = ImportResolver::for_app_dir(app_dir);
+    let mut graph = ModuleGraph::default();
+    let mut external_modules = Vec::new();
+
+    let mut pending: Vec<ScannedComponent> = components.to_vec();
+    let mut queued: HashSet<String> = components
+        .iter()
+        .map(|c| graph_key(&c.absolute_path))
+        .collect();
+
+    while let Some(component) = pending.pop() {
+        let key = graph_key(&component.absolute_path);
+        let importer = PathBuf::from(&key);
+
+        let mut imports = Vec::with_capacity(component.imports.len());
+        for import in &component.imports {
+            let resolved = resolver
+                .resolve(&importer, &import.specifier)
+                .filter(|path| is_script_file(path) && !is_in_node_modules(path))
+                .map(|path| path.to_string_lossy().to_string());
+
+            if let Some(target) = &resolved {
+                if queued.insert(target.clone()) {
+                    if let Some((module, parse_error)) = scanner.scan(Path::new(target), app_dir) {
+                        if let Some(parse_error) = parse_error {
+                            errors.push(parse_failure(&module, &parse_error));
+                        }
+                        external_modules.push(module.clone());
+                        pending.push(module);
+                    }
+                }
+            }
+
+            imports.push(ImportEdge {
+                specifier: import.specifier.clone(),
+                kind: import.kind,
+                line: import.line,
+                resolved,
+            });
+        }
+
+        graph.modules.insert(key.clone(), GraphModule {
+            absolute_path: key,
+            relative_path: component.relative_path.clone(),
+            is_client: component.is_client,
+            is_server: component.is_server,
+            imports,
+            client_hooks_used: component.client_hooks_used.clone(),
+            client_api_usages: component.client_api_usages.clone(),
+        });
+    }
+
+    // Edges pointing at files that failed to load are not graph members
+    let keys: HashSet<String> = graph.modules.keys().cloned().collect();
+    for module in graph.modules.values_mut() {
+        for edge in &mut module.imports {
+            if edge.resolved.as_ref().is_some_and(|r| !keys.contains(r)) {
+                edge.resolved = None;
+            }
+        }
+    }
+
+    external_modules.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+    (graph, external_modules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, relative: &str, contents: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_normalize_and_relative_paths() {
+        assert_eq!(normalize_path(Path::new("/a/b/../c/./d")), PathBuf::from("/a/c/d"));
+        assert_eq!(normalize_path(Path::new("../a/../../b")), PathBuf::from("../../b"));
+        assert_eq!(relative_path_from(Path::new("/p/app/blog/page.tsx"), Path::new("/p/app")), "blog/page.tsx");
+        assert_eq!(relative_path_from(Path::new("/p/lib/db.ts"), Path::new("/p/app")), "../lib/db.ts");
+    }
+
+    #[test]
+    fn test_match_alias() {
+        assert_eq!(match_alias("@/*", "@/components/Button"), Some("components/Button"));
+        assert_eq!(match_alias("~lib", "~lib"), Some(""));
+        assert_eq!(match_alias("@/*", "react"), None);
+    }
+
+    #[test]
+    fn test_resolve_relative_and_alias_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "tsconfig.json", r#"{
+            // comments are allowed
+            "compilerOptions": { "baseUrl": ".", "paths": { "@/*": ["./src/*"] } },
+        }"#);
+        write(root, "app/page.tsx", "");
+        write(root, "app/ui/Button.tsx", "");
+        write(root, "src/lib/index.ts", "");
+        write(root, "src/utils.ts", "");
+
+        let resolver = ImportResolver::for_app_dir(&root.join("app"));
+        let page = root.join("app/page.tsx");
+        assert_eq!(resolver.resolve(&page, "./ui/Button"), Some(root.join("app/ui/Button.tsx")));
+        assert_eq!(resolver.resolve(&page, "./ui/Button.js"), Some(root.join("app/ui/Button.tsx")));
+        assert_eq!(resolver.resolve(&page, "@/lib"), Some(root.join("src/lib/index.ts")));
+        assert_eq!(resolver.resolve(&page, "src/utils"), Some(root.join("src/utils.ts")));
+        assert_eq!(resolver.resolve(&page, "react"), None);
+        assert_eq!(resolver.resolve(&page, "./missing"), None);
+    }
+
+    #[test]
+    fn test_client_dependencies_stop_at_client_boundary() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "app/page.tsx", "import Nav from './nav';\nimport { db } from '../lib/db';\nexport default function Page() {}");
+        write(root, "app/nav.tsx", "import Menu from './menu';\nexport default function Nav() {}");
+        write(root, "app/menu.tsx", "'use client';\nimport Icon from './icon';\nexport default function Menu() {}");
+        write(root, "app/icon.tsx", "'use client';\nexport default function Icon() {}");
+        write(root, "lib/db.ts", "import Widget from './widget';\nexport const db = 1;");
+        write(root, "lib/widget.tsx", "'use client';\nexport default function Widget() {}");
+
+        let app_dir = root.join("app");
+        let scanner = CachedScanner::default();
+        let components: Vec<_> = ["page.tsx", "nav.tsx", "menu.tsx", "icon.tsx"]
+            .iter()
+            .map(|f| scanner.scan(&app_dir.join(f), &app_dir).unwrap().0)
+            .collect();
+
+        let mut errors = Vec::new();
+        let (graph, external) = build_module_graph(&app_dir, &components, &scanner, &mut errors);
+        assert!(errors.is_empty());
+
+        let external: Vec<_> = external.iter().map(|m| m.relative_path.as_str()).collect();
+        assert_eq!(external, vec!["../lib/db.ts", "../lib/widget.tsx"]);
+
+        let page = app_dir.join("page.tsx");
+        assert_eq!(
+            graph.client_dependencies(&page.to_string_lossy()),
+            vec!["../lib/widget.tsx", "menu.tsx"]
+        );
+    }
+}
@@ -3,14 +3,29 @@
 //! Generates manifests for React Server Components build system.
 //! These manifests are used for:
 //! - Client component hydration
+//! - Server action lookup
 //! - Route matching
 //! - Code splitting
+//!
+//! Manifest maps are ordered by key and routes by specificity, so the same
+//! app always serializes to byte-identical JSON, whether it was scanned cold
+//! or through the scan cache. Module entries and routes hold absolute file
+//! paths because the server imports modules by them; manifest files store
+//! them relative to the project root (see `manifest_file`), so the written
+//! bytes don't depend on where the app is checked out.
 
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use serde::{Serialize, Deserialize};
-use super::scanner::scan_app_directory;
-use crate::naming;
+use super::graph::{graph_key, ModuleGraph};
+use super::routes::{
+    build_url_pattern, compare_route_specificity, is_reserved_internal_route,
+    normalize_route_pattern, parse_route_path, route_conflict_keys, RouteInterception, RoutePath,
+};
+use super::segment_config::SegmentConfig;
+use super::scanner::{scan_app_directory, ComponentType, ScanResult, ScannedComponent};
+use crate::analyzer::ExportKind;
+use crate::diagnostics::{Diagnostic, SourceSpan};
+use crate::{hash::stable_hash_hex, naming};
 
 /// Entry in the client components manifest
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,11 +50,37 @@ pub struct ClientManifest {
     /// Build ID for cache invalidation
     pub build_id: String,
     /// Map of module ID to client component info
-    pub client_modules: HashMap<String, ClientModuleEntry>,
+    pub client_modules: BTreeMap<String, ClientModuleEntry>,
     /// Map of file path to module ID for quick lookups
-    pub path_to_id: HashMap<String, String>,
+    pub path_to_id: BTreeMap<String, String>,
     /// SSR module mapping (server paths to client chunk URLs)
-    pub ssr_module_mapping: HashMap<String, String>,
+    pub ssr_module_mapping: BTreeMap<String, String>,
+}
+
+/// Entry in the server actions manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerActionEntry {
+    /// Stable action ID (derived from module path and export name)
+    pub id: String,
+    /// Relative path from app directory
+    pub path: String,
+    /// Absolute file path
+    pub absolute_path: String,
+    /// Module ID of the defining server module
+    pub module_id: String,
+    /// Export name the action is bound to
+    pub export_name: String,
+    /// Declared with a function-level 'use server' directive
+    pub inline: bool,
+}
+
+/// Server actions manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerActionManifest {
+    /// Build ID
+    pub build_id: String,
+    /// Map of action ID to server action info
+    pub actions: BTreeMap<String, ServerActionEntry>,
 }
 
 /// Entry in the server components manifest
@@ -57,7 +98,10 @@ pub struct ServerModuleEntry {
     pub has_metadata: bool,
     /// Has generateMetadata function
     pub has_generate_metadata: bool,
-    /// Client component dependencies
+    /// Literal parts of the `metadata` export, for rendering `<head>` tags
+    /// without executing the module
+    pub static_metadata: Option<serde_json::Value>,
+    /// Client module IDs reachable through imports, up to each 'use client' boundary
     pub client_dependencies: Vec<String>,
 }
 
@@ -70,12 +114,93 @@ pub struct RouteEntry {
     pub page_path: String,
     /// Layout paths from root to this route
     pub layout_paths: Vec<String>,
-    /// Loading component path
+    /// Nearest loading component path
     pub loading_path: Option<String>,
-    /// Error component path
+    /// Nearest error component path
     pub error_path: Option<String>,
+    /// Nearest not-found component path
+    pub not_found_path: Option<String>,
     /// Route type
     pub route_type: String, // "static", "dynamic", "catch-all"
+    /// `@slot` this page renders into. Slot pages are rendered through their
+    /// parent layout's prop, never matched on their own.
+    pub slot: Option<String>,
+    /// Set for intercepting routes, which only apply to client-side navigations
+    pub interception: Option<RouteInterception>,
+    /// Parallel route slots rendered by the layouts of this route
+    pub slots: Vec<RouteSlot>,
+    /// Boundary files of every segment from the app root to the page
+    pub boundaries: RouteBoundaries,
+    /// The page exports `generateStaticParams`; its params can be expanded
+    /// into concrete paths with `plan_static_paths`
+    pub has_generate_static_params: bool,
+    /// Effective segment config, merged from the outermost layout down to
+    /// the page with `SegmentConfig::merge`: the shortest `revalidate` wins,
+    /// every other option comes from the innermost segment that sets it
+    pub segment_config: SegmentConfig,
+}
+
+/// Boundary files per segment, outermost first. All lists have one entry per
+/// directory from the app root (`""`) to the page's directory, so the
+/// renderer can nest layout > template > error > loading > not-found level
+/// by level.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RouteBoundaries {
+    /// Segment directories relative to the app directory
+    pub segments: Vec<String>,
+    pub layouts: Vec<Option<String>>,
+    pub templates: Vec<Option<String>>,
+    pub loading: Vec<Option<String>>,
+    pub errors: Vec<Option<String>>,
+    pub not_found: Vec<Option<String>>,
+}
+
+impl RouteBoundaries {
+    /// Resolve the boundaries of `page_dir` from the scanned components
+    fn resolve(page_dir: &str, components: &[&ScannedComponent]) -> Self {
+        let mut segments = vec![String::new()];
+        let mut dir = String::new();
+        for part in page_dir.split('/').filter(|p| !p.is_empty()) {
+            dir = join_dir(&dir, part);
+            segments.push(dir.clone());
+        }
+
+        let find = |segment: &str, component_type: ComponentType| {
+            components.iter()
+                .find(|c| c.component_type == component_type && parent_dir(&c.relative_path) == segment)
+                .map(|c| c.absolute_path.clone())
+        };
+        let per_segment = |component_type: ComponentType| {
+            segments.iter().map(|segment| find(segment, component_type)).collect()
+        };
+
+        Self {
+            layouts: per_segment(ComponentType::Layout),
+            templates: per_segment(ComponentType::Template),
+            loading: per_segment(ComponentType::Loading),
+            errors: per_segment(ComponentType::Error),
+            not_found: per_segment(ComponentType::NotFound),
+            segments,
+        }
+    }
+
+    /// Innermost entry of a per-segment list
+    fn nearest(list: &[Option<String>]) -> Option<String> {
+        list.iter().rev().flatten().next().cloned()
+    }
+}
+
+/// A parallel route slot and what it renders for a route
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteSlot {
+    /// Slot name without `@`, also the layout prop name
+    pub name: String,
+    /// Layout receiving the slot prop
+    pub layout_path: Option<String>,
+    /// Slot page matching the route's URL
+    pub page_path: Option<String>,
+    /// `default` file rendered when no slot page matches
+    pub default_path: Option<String>,
 }
 
 /// Server components manifest
@@ -84,11 +209,65 @@ pub struct ServerManifest {
     /// Build ID
     pub build_id: String,
     /// Map of module ID to server component info
-    pub server_modules: HashMap<String, ServerModuleEntry>,
+    pub server_modules: BTreeMap<String, ServerModuleEntry>,
     /// Map of path to module ID
-    pub path_to_id: HashMap<String, String>,
+    pub path_to_id: BTreeMap<String, String>,
     /// Discovered routes
     pub routes: Vec<RouteEntry>,
+    /// Scan errors for the modules behind this manifest and route conflicts.
+    /// The build must fail when any has error severity.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Diagnostic code for pages that map to the same URL pattern
+pub const ROUTE_PATTERN_CONFLICT: &str = "ROUTE_PATTERN_CONFLICT";
+
+/// Routes served by more than one page; no manifest can be built for them
+#[derive(Debug, Clone)]
+pub struct RouteConflictError {
+    /// The `ROUTE_PATTERN_CONFLICT` diagnostics
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl std::fmt::Display for RouteConflictError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} route conflict(s):", self.diagnostics.len())?;
+        for diagnostic in &self.diagnostics {
+            write!(f, "\n  - {}", diagnostic.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for RouteConflictError {}
+
+impl ServerManifest {
+    /// Fail when any route is served by more than one page. Dev keeps the
+    /// manifest to show the conflicts; builds must stop here.
+    pub fn check_route_conflicts(&self) -> Result<(), RouteConflictError> {
+        let diagnostics: Vec<Diagnostic> = self.diagnostics.iter()
+            .filter(|d| d.code == ROUTE_PATTERN_CONFLICT)
+            .cloned()
+            .collect();
+        if diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(RouteConflictError { diagnostics })
+        }
+    }
+}
+
+/// Directory part of a relative path ("" for files in the app dir)
+fn parent_dir(relative_path: &str) -> &str {
+    relative_path.rsplit_once('/').map_or("", |(dir, _)| dir)
+}
+
+fn join_dir(parent: &str, child: &str) -> String {
+    if parent.is_empty() {
+        child.to_string()
+    } else {
+        format!("{parent}/{child}")
+    }
 }
 
 /// Generate a chunk name from a relative path
@@ -121,83 +300,83 @@ fn generate_module_id(relative_path: &str, is_client: bool) -> String {
     }
 }
 
-fn is_reserved_internal_route(relative_path: &str) -> bool {
-    relative_path
-        .split(['/', '\\'])
-        .any(|segment| segment == "[not-found]")
+/// Generate a stable server action ID from relative path and export name
+fn generate_action_id(relative_path: &str, export_name: &str) -> String {
+    let key = format!("{}#{}", relative_path.replace('\\', "/"), export_name);
+    stable_hash_hex(key.as_bytes(), 40)
 }
 
-/// Build URL pattern from relative path
-fn build_url_pattern(relative_path: &str) -> (String, String) {
-    let mut pattern = String::from("/");
-    let mut route_type = "static".to_string();
-    
-    // Get directory part (remove file name)
-    let dir_path = Path::new(relative_path)
-        .parent()
-        .map(|p| p.to_string_lossy().to_string())
-        .unwrap_or_default();
-    
-    if dir_path.is_empty() || dir_path == "." {
-        return (pattern, route_type);
-    }
-    
-    let segments: Vec<&str> = dir_path.split(['/', '\\']).filter(|s| !s.is_empty()).collect();
+/// Scan the app directory and generate the client manifest
+pub fn generate_client_manifest(
+    app_dir: &str,
+    build_id: &str,
+) -> ClientManifest {
+    generate_client_manifest_from_scan(&scan_app_directory(app_dir), build_id)
+}
+
+/// Every scanned module (app and external) keyed by graph key
+fn modules_by_key(scan_result: &ScanResult) -> HashMap<String, &ScannedComponent> {
+    scan_result.client_components.iter()
+        .chain(&scan_result.server_components)
+        .chain(&scan_result.api_routes)
+        .chain(&scan_result.external_modules)
+        .map(|c| (graph_key(&c.absolute_path), c))
+        .collect()
+}
+
+/// Exported names of a module with `export * from` expanded through the
+/// module graph. Star exports never re-export `default`; unresolvable ones
+/// are skipped.
+fn resolve_exports(
+    component: &ScannedComponent,
+    modules: &HashMap<String, &ScannedComponent>,
+    graph: &ModuleGraph,
+) -> Vec<String> {
+    let mut exports = component.exports.clone();
+    let mut visited = HashSet::from([graph_key(&component.absolute_path)]);
+    let mut stack = vec![component];
     
-    for segment in segments {
-        // Route group - doesn't contribute to URL
-        if segment.starts_with('(') && segment.ends_with(')') {
-            continue;
-        }
-        
-        // Optional catch-all segment [[...slug]]
-        if segment.starts_with("[[...") && segment.ends_with("]]") {
-            let param_name = &segment[5..segment.len()-2];
-            pattern.push_str(&format!(":{param_name}*?"));
-            route_type = "optional-catch-all".to_string();
-        }
-        // Catch-all segment
-        else if segment.starts_with("[...") && segment.ends_with(']') {
-            let param_name = &segment[4..segment.len()-1];
-            pattern.push_str(&format!(":{param_name}*"));
-            route_type = "catch-all".to_string();
-        }
-        // Dynamic segment
-        else if segment.starts_with('[') && segment.ends_with(']') {
-            let param_name = &segment[1..segment.len()-1];
-            pattern.push_str(&format!(":{param_name}"));
-            if route_type != "catch-all" && route_type != "optional-catch-all" {
-                route_type = "dynamic".to_string();
+    while let Some(module) = stack.pop() {
+        for entry in module.export_table.iter().filter(|e| e.kind == ExportKind::Star) {
+            let Some(target) = entry.source.as_deref()
+                .and_then(|source| graph.resolved_import(&module.absolute_path, source))
+                .and_then(|path| modules.get(path))
+            else {
+                continue;
+            };
+            if !visited.insert(graph_key(&target.absolute_path)) {
+                continue;
             }
+            for name in &target.exports {
+                if name != "default" && !exports.contains(name) {
+                    exports.push(name.clone());
+                }
+            }
+            stack.push(target);
         }
-        // Static segment
-        else {
-            pattern.push_str(segment);
-        }
-        
-        pattern.push('/');
-    }
-    
-    // Remove trailing slash except for root
-    if pattern.len() > 1 && pattern.ends_with('/') {
-        pattern.pop();
     }
     
-    (pattern, route_type)
+    exports
 }
 
-/// Generate client manifest from scan result
-pub fn generate_client_manifest(
-    app_dir: &str,
+/// Generate client manifest from an existing scan result
+pub fn generate_client_manifest_from_scan(
+    scan_result: &ScanResult,
     build_id: &str,
 ) -> ClientManifest {
-    let scan_result = scan_app_directory(app_dir);
     
-    let mut client_modules = HashMap::new();
-    let mut path_to_id = HashMap::new();
-    let mut ssr_module_mapping = HashMap::new();
+    let mut client_modules = BTreeMap::new();
+    let mut path_to_id = BTreeMap::new();
+    let mut ssr_module_mapping = BTreeMap::new();
+    
+    // Client modules outside the app directory are reached through imports
+    let external_client_modules = scan_result.external_modules
+        .iter()
+        .filter(|c| c.is_client);
     
-    for component in scan_result.client_components {
+    let modules = modules_by_key(scan_result);
+    
+    for component in scan_result.client_components.iter().chain(external_client_modules) {
         let module_id = generate_module_id(&component.relative_path, true);
         let chunk_name = generate_chunk_name(&component.relative_path);
         
@@ -206,14 +385,14 @@ pub fn generate_client_manifest(
             path: component.relative_path.clone(),
             absolute_path: component.absolute_path.clone(),
             chunk_name: chunk_name.clone(),
-            exports: component.exports,
+            exports: resolve_exports(component, &modules, &scan_result.module_graph),
             async_load: false,
         };
         
         path_to_id.insert(component.relative_path.clone(), module_id.clone());
         path_to_id.insert(component.absolute_path.clone(), module_id.clone());
         ssr_module_mapping.insert(
-            component.absolute_path,
+            component.absolute_path.clone(),
             format!("{}{}.js", naming::STATIC_CHUNKS_PATH, chunk_name),
         );
         client_modules.insert(module_id, entry);
@@ -227,15 +406,60 @@ pub fn generate_client_manifest(
     }
 }
 
-/// Generate server manifest from scan result
+/// Scan the app directory and generate the server action manifest
+pub fn generate_server_action_manifest(
+    app_dir: &str,
+    build_id: &str,
+) -> ServerActionManifest {
+    generate_server_action_manifest_from_scan(&scan_app_directory(app_dir), build_id)
+}
+
+/// Generate server action manifest from an existing scan result
+pub fn generate_server_action_manifest_from_scan(
+    scan_result: &ScanResult,
+    build_id: &str,
+) -> ServerActionManifest {
+    
+    let mut actions = BTreeMap::new();
+    
+    for component in &scan_result.server_action_modules {
+        let module_id = generate_module_id(&component.relative_path, false);
+        
+        for action in &component.server_actions {
+            let id = generate_action_id(&component.relative_path, &action.export_name);
+            actions.insert(id.clone(), ServerActionEntry {
+                id,
+                path: component.relative_path.clone(),
+                absolute_path: component.absolute_path.clone(),
+                module_id: module_id.clone(),
+                export_name: action.export_name.clone(),
+                inline: action.inline,
+            });
+        }
+    }
+    
+    ServerActionManifest {
+        build_id: build_id.to_string(),
+        actions,
+    }
+}
+
+/// Scan the app directory and generate the server manifest
 pub fn generate_server_manifest(
     app_dir: &str,
     build_id: &str,
 ) -> ServerManifest {
-    let scan_result = scan_app_directory(app_dir);
+    generate_server_manifest_from_scan(&scan_app_directory(app_dir), build_id)
+}
+
+/// Generate server manifest from an existing scan result
+pub fn generate_server_manifest_from_scan(
+    scan_result: &ScanResult,
+    build_id: &str,
+) -> ServerManifest {
     
-    let mut server_modules = HashMap::new();
-    let mut path_to_id = HashMap::new();
+    let mut server_modules = BTreeMap::new();
+    let mut path_to_id = BTreeMap::new();
     let mut routes = Vec::new();
     
     // Process server components
@@ -249,7 +473,12 @@ pub fn generate_server_manifest(
             component_type: format!("{:?}", component.component_type).to_lowercase(),
             has_metadata: component.has_metadata,
             has_generate_metadata: component.has_generate_metadata,
-            client_dependencies: vec![], // Would need import analysis
+            static_metadata: component.static_metadata.clone(),
+            client_dependencies: scan_result.module_graph
+                .client_dependencies(&component.absolute_path)
+                .iter()
+                .map(|path| generate_module_id(path, true))
+                .collect(),
         };
         
         path_to_id.insert(component.relative_path.clone(), module_id.clone());
@@ -257,79 +486,169 @@ pub fn generate_server_manifest(
         server_modules.insert(module_id, entry);
     }
     
+    // Pages serving each normalized pattern (within the same slot and
+    // interception), to detect conflicts
+    let mut pattern_pages: BTreeMap<String, Vec<&ScannedComponent>> = BTreeMap::new();
+    
+    let parsed_pages: Vec<(&ScannedComponent, RoutePath)> = scan_result.pages.iter()
+        .filter(|page| !is_reserved_internal_route(&page.relative_path))
+        .map(|page| (page, parse_route_path(&page.relative_path)))
+        .collect();
+    let defaults: Vec<&ScannedComponent> = scan_result.server_components.iter()
+        .chain(&scan_result.client_components)
+        .filter(|c| c.component_type == ComponentType::Default)
+        .collect();
+    
+    // Layout, template, loading, error and not-found files in path order,
+    // so the first file for a segment wins regardless of the extension
+    let mut boundary_files: Vec<&ScannedComponent> = scan_result.server_components.iter()
+        .chain(&scan_result.client_components)
+        .filter(|c| matches!(
+            c.component_type,
+            ComponentType::Layout
+                | ComponentType::Template
+                | ComponentType::Loading
+                | ComponentType::Error
+                | ComponentType::NotFound
+        ))
+        .collect();
+    boundary_files.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+    
+    // Every `@slot` folder as (parent dir, slot name)
+    let default_routes: Vec<RoutePath> = defaults.iter()
+        .map(|d| parse_route_path(&d.relative_path))
+        .collect();
+    let mut slot_dirs: Vec<(String, String)> = parsed_pages.iter()
+        .map(|(_, route)| route)
+        .chain(&default_routes)
+        .filter_map(|route| Some((route.slot_parent.clone()?, route.slot.clone()?)))
+        .collect();
+    slot_dirs.sort();
+    slot_dirs.dedup();
+    
     // Build routes from pages
-    for page in &scan_result.pages {
-        if is_reserved_internal_route(&page.relative_path) {
-            continue;
-        }
-
-        let (pattern, route_type) = build_url_pattern(&page.relative_path);
+    for (page, route) in &parsed_pages {
+        let RoutePath { pattern, route_type, slot, interception, .. } = route.clone();
         
-        // Find layouts for this route
-        let mut layout_paths = Vec::new();
-        let page_dir = Path::new(&page.relative_path).parent();
+        let normalized = normalize_route_pattern(&pattern);
+        for key in route_conflict_keys(&pattern) {
+            let conflict_key = format!(
+                "{}@{}|{}{}|{key}",
+                route.slot_parent.as_deref().unwrap_or_default(),
+                slot.as_deref().unwrap_or_default(),
+                interception.as_ref().map(|i| i.source_pattern.as_str()).unwrap_or_default(),
+                interception.as_ref().map(|i| i.marker.as_str()).unwrap_or_default(),
+            );
+            pattern_pages.entry(conflict_key).or_default().push(page);
+        }
         
-        if let Some(mut current_dir) = page_dir {
-            let app_path = Path::new("");
-            
-            loop {
-                // Look for layout in this directory
-                for layout in &scan_result.layouts {
-                    let layout_dir = Path::new(&layout.relative_path).parent();
-                    if layout_dir == Some(current_dir) {
-                        layout_paths.insert(0, layout.absolute_path.clone());
-                        break;
+        // Slots rendered by this route's layouts (slot pages don't get their own)
+        let page_dir = parent_dir(&page.relative_path);
+        let slots = if slot.is_some() {
+            Vec::new()
+        } else {
+            slot_dirs.iter()
+                .filter(|(parent, _)| {
+                    parent.is_empty() || page_dir == parent || page_dir.starts_with(&format!("{parent}/"))
+                })
+                .map(|(parent, name)| {
+                    let slot_dir = join_dir(parent, &format!("@{name}"));
+                    RouteSlot {
+                        name: name.clone(),
+                        layout_path: scan_result.layouts.iter()
+                            .find(|l| parent_dir(&l.relative_path) == parent)
+                            .map(|l| l.absolute_path.clone()),
+                        page_path: parsed_pages.iter()
+                            .find(|(_, r)| {
+                                r.slot.as_ref() == Some(name)
+                                    && r.slot_parent.as_ref() == Some(parent)
+                                    && r.interception.is_none()
+                                    && normalize_route_pattern(&r.pattern) == normalized
+                            })
+                            .map(|(p, _)| p.absolute_path.clone()),
+                        default_path: defaults.iter()
+                            .find(|d| parent_dir(&d.relative_path) == slot_dir)
+                            .map(|d| d.absolute_path.clone()),
                     }
-                }
-                
-                if current_dir == app_path || current_dir.parent().is_none() {
-                    break;
-                }
-                current_dir = current_dir.parent().unwrap_or(app_path);
-            }
-        }
+                })
+                .collect()
+        };
         
+        let boundaries = RouteBoundaries::resolve(page_dir, &boundary_files);
+        let segment_config = boundaries.layouts.iter()
+            .flatten()
+            .filter_map(|layout| boundary_files.iter().copied().find(|c| &c.absolute_path == layout))
+            .chain(std::iter::once(*page))
+            .fold(SegmentConfig::default(), |config, component| {
+                config.merge(&SegmentConfig::parse(&component.segment_config).0)
+            });
         routes.push(RouteEntry {
             pattern,
             page_path: page.absolute_path.clone(),
-            layout_paths,
-            loading_path: None, // Would need to search for loading.tsx
-            error_path: None,   // Would need to search for error.tsx
+            layout_paths: boundaries.layouts.iter().flatten().cloned().collect(),
+            loading_path: RouteBoundaries::nearest(&boundaries.loading),
+            error_path: RouteBoundaries::nearest(&boundaries.errors),
+            not_found_path: RouteBoundaries::nearest(&boundaries.not_found),
             route_type,
+            slot,
+            interception,
+            slots,
+            boundaries,
+            has_generate_static_params: page.has_generate_static_params,
+            segment_config,
         });
     }
     
-    // Sort routes: static first, then dynamic, then catch-all
+    // Most specific first; page path breaks ties between slot and intercepting copies
     routes.sort_by(|a, b| {
-        let order = |t: &str| match t {
-            "static" => 0,
-            "dynamic" => 1,
-            "catch-all" => 2,
-            _ => 3,
-        };
-        order(&a.route_type).cmp(&order(&b.route_type))
+        compare_route_specificity(&a.pattern, &b.pattern).then_with(|| a.page_path.cmp(&b.page_path))
     });
     
+    let mut diagnostics = scan_result.diagnostics.clone();
+    diagnostics.extend(route_conflict_diagnostics(&pattern_pages));
+    
     ServerManifest {
         build_id: build_id.to_string(),
         server_modules,
         path_to_id,
         routes,
+        diagnostics,
     }
 }
 
+/// One error for every page after the first that serves the same
+/// (normalized) URL pattern, pointing back at that first page. Two optional
+/// catch-alls share two keys but are reported once.
+fn route_conflict_diagnostics(pattern_pages: &BTreeMap<String, Vec<&ScannedComponent>>) -> Vec<Diagnostic> {
+    let span = |page: &ScannedComponent| SourceSpan::new(&page.absolute_path, &page.relative_path, 1, 1);
+    let mut reported = HashSet::new();
+    let mut diagnostics = Vec::new();
+    for pages in pattern_pages.values() {
+        let Some((first, rest)) = pages.split_first() else { continue };
+        for page in rest {
+            if !reported.insert((&first.absolute_path, &page.absolute_path)) {
+                continue;
+            }
+            let (pattern, _) = build_url_pattern(&page.relative_path);
+            let message = format!(
+                "Route \"{pattern}\" is served by both {} and {}. Remove or rename one of them so the route has a single page.",
+                first.relative_path, page.relative_path
+            );
+            diagnostics.push(
+                Diagnostic::error(ROUTE_PATTERN_CONFLICT, message)
+                    .with_primary_span(span(page))
+                    .with_secondary_spans([span(first).with_label(format!("also serves \"{pattern}\""))])
+                    .with_code_frame_from_disk(),
+            );
+        }
+    }
+    diagnostics
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    
-    #[test]
-    fn test_build_url_pattern() {
-        assert_eq!(build_url_pattern("page.tsx"), ("/".to_string(), "static".to_string()));
-        assert_eq!(build_url_pattern("blog/page.tsx"), ("/blog".to_string(), "static".to_string()));
-        assert_eq!(build_url_pattern("blog/[slug]/page.tsx"), ("/blog/:slug".to_string(), "dynamic".to_string()));
-        assert_eq!(build_url_pattern("docs/[...path]/page.tsx"), ("/docs/:path*".to_string(), "catch-all".to_string()));
-        assert_eq!(build_url_pattern("(marketing)/about/page.tsx"), ("/about".to_string(), "static".to_string()));
-    }
+    use crate::rsc::Revalidate;
     
     #[test]
     fn test_generate_chunk_name() {
@@ -338,8 +657,292 @@ mod tests {
     }
 
     #[test]
-    fn test_reserved_internal_route_detection() {
-        assert!(is_reserved_internal_route("docs/[not-found]/page.tsx"));
-        assert!(!is_reserved_internal_route("docs/[slug]/page.tsx"));
+    fn test_generate_action_id_is_stable() {
+        let id = generate_action_id("blog/actions.ts", "createPost");
+        assert_eq!(id.len(), 40);
+        assert_eq!(id, generate_action_id("blog/actions.ts", "createPost"));
+        assert_eq!(id, generate_action_id("blog\\actions.ts", "createPost"));
+        assert_ne!(id, generate_action_id("blog/actions.ts", "deletePost"));
+    }
+
+    #[test]
+    fn test_client_manifest_expands_star_exports() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        std::fs::create_dir_all(app.join("ui")).unwrap();
+        std::fs::write(app.join("ui/index.tsx"), "'use client';\nexport * from './buttons';\nexport { Menu as NavMenu } from './menu';\nexport type { Props } from './types';").unwrap();
+        std::fs::write(app.join("ui/buttons.tsx"), "export * from './icons';\nexport const Button = () => null;\nexport default function Base() {}").unwrap();
+        std::fs::write(app.join("ui/icons.tsx"), "export function Icon() {}\nexport * from './buttons';").unwrap();
+        std::fs::write(app.join("ui/menu.tsx"), "export function Menu() {}").unwrap();
+        
+        let manifest = generate_client_manifest(&app.to_string_lossy(), "test");
+        let entry = &manifest.client_modules["client:ui/index"];
+        assert_eq!(entry.exports, vec!["NavMenu", "Button", "Icon"]);
+    }
+
+    #[test]
+    fn test_server_manifest_client_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        std::fs::create_dir_all(app.join("ui")).unwrap();
+        std::fs::create_dir_all(dir.path().join("components")).unwrap();
+        std::fs::write(app.join("page.tsx"), "import Counter from './ui/counter';\nimport Nav from '../components/nav';\nexport default function Page() {}").unwrap();
+        std::fs::write(app.join("ui/counter.tsx"), "'use client';\nexport default function Counter() {}").unwrap();
+        std::fs::write(dir.path().join("components/nav.tsx"), "'use client';\nexport default function Nav() {}").unwrap();
+        
+        let app_dir = app.to_string_lossy();
+        let server = generate_server_manifest(&app_dir, "test");
+        let page = &server.server_modules["server:page"];
+        assert_eq!(page.client_dependencies, vec!["client:../components/nav", "client:ui/counter"]);
+        
+        let client = generate_client_manifest(&app_dir, "test");
+        assert!(client.client_modules.contains_key("client:ui/counter"));
+        assert!(client.client_modules.contains_key("client:../components/nav"));
+    }
+
+    #[test]
+    fn test_manifests_serialize_deterministically() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        for (file, source) in [
+            ("layout.tsx", "export default function Layout() {}"),
+            ("page.tsx", "import A from './ui/a';\nimport B from './ui/b';\nexport default function Page() {}"),
+            ("blog/[slug]/page.tsx", "export const metadata = { title: 'Blog', description: 'Posts' };\nexport default function Page() {}"),
+            ("ui/a.tsx", "'use client';\nexport default function A() {}"),
+            ("ui/b.tsx", "'use client';\nexport default function B() {}"),
+            ("actions.ts", "'use server';\nexport async function save() {}\nexport async function remove() {}"),
+        ] {
+            let path = app.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, source).unwrap();
+        }
+        
+        let scan = scan_app_directory(&app.to_string_lossy());
+        let serialize = || {
+            (
+                serde_json::to_string(&generate_client_manifest_from_scan(&scan, "test")).unwrap(),
+                serde_json::to_string(&generate_server_manifest_from_scan(&scan, "test")).unwrap(),
+                serde_json::to_string(&generate_server_action_manifest_from_scan(&scan, "test")).unwrap(),
+            )
+        };
+        let first = serialize();
+        for _ in 0..5 {
+            assert_eq!(serialize(), first);
+        }
+        
+        let server = generate_server_manifest_from_scan(&scan, "test");
+        let ids: Vec<_> = server.server_modules.keys().collect();
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(ids, sorted);
+    }
+
+    #[test]
+    fn test_cached_scan_serializes_like_cold_scan() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        for (file, source) in [
+            ("layout.tsx", "export const revalidate = 60;\nexport default function Layout() {}"),
+            ("page.tsx", "import A from './ui/a';\nexport default function Page() {}"),
+            ("blog/[slug]/page.tsx", "export const metadata = { title: 'Blog' };\nexport default function Page() {}"),
+            ("ui/a.tsx", "'use client';\nexport * from './b';\nexport default function A() {}"),
+            ("ui/b.tsx", "export function B() {}"),
+            ("actions.ts", "'use server';\nexport async function save() {}"),
+        ] {
+            let path = app.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, source).unwrap();
+        }
+
+        let cache_dir = dir.path().join("cache");
+        let options = crate::rsc::ScanOptions {
+            cache_dir: Some(cache_dir.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let serialize = || {
+            let scan = crate::rsc::scan_app_directory_with_options(&app.to_string_lossy(), &options);
+            (
+                serde_json::to_string(&generate_client_manifest_from_scan(&scan, "test")).unwrap(),
+                serde_json::to_string(&generate_server_manifest_from_scan(&scan, "test")).unwrap(),
+                serde_json::to_string(&generate_server_action_manifest_from_scan(&scan, "test")).unwrap(),
+            )
+        };
+
+        let cold = serialize();
+        assert!(cache_dir.join(crate::rsc::SCAN_CACHE_FILE).is_file());
+        assert_eq!(serialize(), cold);
+    }
+
+    #[test]
+    fn test_server_manifest_reports_route_conflicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        for page in ["about", "(marketing)/about", "blog/[id]", "blog/[slug]", "docs/[...path]", "docs/[id]"] {
+            std::fs::create_dir_all(app.join(page)).unwrap();
+            std::fs::write(app.join(page).join("page.tsx"), "export default function Page() {}").unwrap();
+        }
+        
+        let server = generate_server_manifest(&app.to_string_lossy(), "test");
+        let conflicts: Vec<_> = server.diagnostics.iter()
+            .filter(|d| d.code == ROUTE_PATTERN_CONFLICT)
+            .map(|d| (d.primary_span.as_ref().unwrap().relative_path.as_str(), d.secondary_spans[0].relative_path.as_str()))
+            .collect();
+        assert_eq!(conflicts, vec![
+            ("about/page.tsx", "(marketing)/about/page.tsx"),
+            ("blog/[slug]/page.tsx", "blog/[id]/page.tsx"),
+        ]);
+        assert!(server.diagnostics[0].message.contains("Route \"/about\" is served by both"));
+
+        let error = server.check_route_conflicts().unwrap_err();
+        assert_eq!(error.diagnostics.len(), 2);
+        assert!(error.to_string().starts_with("2 route conflict(s):\n  - Route \"/about\""));
+    }
+
+    #[test]
+    fn test_optional_catch_all_conflicts_with_parent_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        for page in ["docs", "docs/[[...slug]]", "shop/[[...slug]]", "shop/[[...path]]", "blog/[[...slug]]"] {
+            std::fs::create_dir_all(app.join(page)).unwrap();
+            std::fs::write(app.join(page).join("page.tsx"), "export default function Page() {}").unwrap();
+        }
+
+        let server = generate_server_manifest(&app.to_string_lossy(), "test");
+        let conflicts: Vec<_> = server.check_route_conflicts().unwrap_err().diagnostics.iter()
+            .map(|d| (d.primary_span.clone().unwrap().relative_path, d.secondary_spans[0].relative_path.clone()))
+            .collect();
+        assert_eq!(conflicts, vec![
+            ("docs/page.tsx".to_string(), "docs/[[...slug]]/page.tsx".to_string()),
+            ("shop/[[...slug]]/page.tsx".to_string(), "shop/[[...path]]/page.tsx".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_route_conflicts_are_reported_once_per_extra_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        for page in ["about", "(a)/about", "(b)/about", "contact"] {
+            std::fs::create_dir_all(app.join(page)).unwrap();
+            std::fs::write(app.join(page).join("page.tsx"), "export default function Page() {}").unwrap();
+        }
+
+        let server = generate_server_manifest(&app.to_string_lossy(), "test");
+        let conflicts: Vec<_> = server.check_route_conflicts().unwrap_err().diagnostics.iter()
+            .map(|d| (d.primary_span.clone().unwrap().relative_path, d.secondary_spans[0].relative_path.clone()))
+            .collect();
+        assert_eq!(conflicts, vec![
+            ("(b)/about/page.tsx".to_string(), "(a)/about/page.tsx".to_string()),
+            ("about/page.tsx".to_string(), "(a)/about/page.tsx".to_string()),
+        ]);
+
+        std::fs::remove_dir_all(app.join("(a)")).unwrap();
+        std::fs::remove_dir_all(app.join("(b)")).unwrap();
+        let server = generate_server_manifest(&app.to_string_lossy(), "test");
+        assert!(server.check_route_conflicts().is_ok());
+    }
+
+    #[test]
+    fn test_server_manifest_parallel_and_intercepting_routes() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        let write = |relative: &str| {
+            let path = app.join(relative);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "export default function Page() {}").unwrap();
+        };
+        for file in [
+            "layout.tsx", "page.tsx", "photo/[id]/page.tsx",
+            "@modal/default.tsx", "@modal/(.)photo/[id]/page.tsx",
+            "@analytics/page.tsx", "feed/page.tsx", "feed/(..)photo/[id]/page.tsx",
+        ] {
+            write(file);
+        }
+        
+        let server = generate_server_manifest(&app.to_string_lossy(), "test");
+        assert!(server.diagnostics.is_empty(), "{:?}", server.diagnostics);
+        
+        let route = |suffix: &str| server.routes.iter().find(|r| r.page_path.ends_with(suffix)).unwrap();
+        let home = route("app/page.tsx");
+        let slots: Vec<_> = home.slots.iter()
+            .map(|s| (s.name.as_str(), s.page_path.is_some(), s.default_path.is_some()))
+            .collect();
+        assert_eq!(slots, vec![("analytics", true, false), ("modal", false, true)]);
+        assert!(home.slots[0].layout_path.as_deref().unwrap().ends_with("layout.tsx"));
+        
+        let modal = route("@modal/(.)photo/[id]/page.tsx");
+        assert_eq!(modal.slot.as_deref(), Some("modal"));
+        assert_eq!(modal.pattern, "/photo/:id");
+        assert!(modal.slots.is_empty());
+        
+        let intercepted = route("feed/(..)photo/[id]/page.tsx");
+        assert_eq!(intercepted.interception.as_ref().unwrap().source_pattern, "/feed");
+        assert!(route("app/photo/[id]/page.tsx").interception.is_none());
+    }
+
+    #[test]
+    fn test_server_manifest_route_boundaries() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        for (file, source) in [
+            ("layout.tsx", "export default function Layout() {}"),
+            ("not-found.tsx", "export default function NotFound() {}"),
+            ("(shop)/loading.tsx", "export default function Loading() {}"),
+            ("(shop)/products/template.tsx", "export default function Template() {}"),
+            ("(shop)/products/error.tsx", "'use client';\nexport default function Error() {}"),
+            ("(shop)/products/[id]/page.tsx", "export default function Page() {}"),
+        ] {
+            let path = app.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, source).unwrap();
+        }
+        
+        let server = generate_server_manifest(&app.to_string_lossy(), "test");
+        let route = &server.routes[0];
+        let boundaries = &route.boundaries;
+        assert_eq!(boundaries.segments, vec!["", "(shop)", "(shop)/products", "(shop)/products/[id]"]);
+        let present = |list: &[Option<String>]| list.iter().map(Option::is_some).collect::<Vec<_>>();
+        assert_eq!(present(&boundaries.layouts), vec![true, false, false, false]);
+        assert_eq!(present(&boundaries.not_found), vec![true, false, false, false]);
+        assert_eq!(present(&boundaries.loading), vec![false, true, false, false]);
+        assert_eq!(present(&boundaries.templates), vec![false, false, true, false]);
+        assert_eq!(present(&boundaries.errors), vec![false, false, true, false]);
+        
+        assert_eq!(route.layout_paths.len(), 1);
+        assert!(route.loading_path.as_deref().unwrap().ends_with("(shop)/loading.tsx"));
+        assert!(route.error_path.as_deref().unwrap().ends_with("products/error.tsx"));
+        assert!(route.not_found_path.as_deref().unwrap().ends_with("app/not-found.tsx"));
+    }
+
+    #[test]
+    fn test_server_manifest_segment_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        for (file, source) in [
+            ("layout.tsx", "export const runtime = 'edge';\nexport const revalidate = 60;\nexport default function Layout() {}"),
+            ("blog/layout.tsx", "export const dynamic = 'force-static';\nexport default function Layout() {}"),
+            ("blog/[slug]/page.tsx", "export const revalidate = 3600;\nexport const dynamic = 'error';\nexport async function generateStaticParams() { return []; }\nexport default function Page() {}"),
+            ("about/page.tsx", "export const runtime = 'bun';\nexport default function Page() {}"),
+        ] {
+            let path = app.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, source).unwrap();
+        }
+        
+        let server = generate_server_manifest(&app.to_string_lossy(), "test");
+        let route = |pattern: &str| &server.routes.iter().find(|r| r.pattern == pattern).unwrap().segment_config;
+        let blog = route("/blog/:slug");
+        assert_eq!(blog.runtime.as_deref(), Some("edge"));
+        assert_eq!(blog.dynamic.as_deref(), Some("error"));
+        assert_eq!(blog.revalidate, Some(Revalidate::Seconds(60)));
+        assert!(server.routes.iter().any(|r| r.pattern == "/blog/:slug" && r.has_generate_static_params));
+        assert!(server.routes.iter().all(|r| r.pattern == "/blog/:slug" || !r.has_generate_static_params));
+        
+        // The invalid runtime is reported and ignored, so the layout's applies
+        assert_eq!(route("/about").runtime.as_deref(), Some("edge"));
+        let invalid: Vec<_> = server.diagnostics.iter()
+            .filter(|d| d.code == "INVALID_SEGMENT_CONFIG")
+            .map(|d| d.primary_span.as_ref().unwrap().relative_path.as_str())
+            .collect();
+        assert_eq!(invalid, vec!["about/page.tsx"]);
     }
 }
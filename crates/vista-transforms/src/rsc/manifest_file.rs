@@ -0,0 +1,699 @@
+//! RSC Manifest Files
+//!
+//! Writes the client, server and server action manifests to
+//! `.vista/manifests/` with a `schema_version` field, alongside a JSON Schema
+//! per manifest type, and reads them back. Reading checks the version first,
+//! so a build from another Vista version fails with a clear error, then
+//! validates the file against the schema before deserializing it.
+//!
+//! File paths are written relative to the project root and resolved against
+//! it again when read, so the same app produces byte-identical manifest
+//! files wherever it is checked out.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Map, Value};
+use super::cache::default_build_dir;
+use super::graph::{normalize_path, relative_path_from};
+use super::manifest::{ClientManifest, ServerActionManifest, ServerManifest};
+
+/// Version of the on-disk manifest format. Bump on any change to the
+/// serialized shape of a manifest.
+pub const MANIFEST_SCHEMA_VERSION: u64 = 2;
+
+/// Manifest directory inside the build directory
+pub const MANIFESTS_DIR: &str = "manifests";
+
+/// Schema directory inside the manifest directory
+pub const MANIFEST_SCHEMA_DIR: &str = "schema";
+
+/// A manifest type with an on-disk file and a JSON Schema
+pub trait ManifestFile: Serialize + DeserializeOwned {
+    /// File name inside the manifest directory
+    const FILE_NAME: &'static str;
+
+    /// JSON Schema (draft 2020-12) of the file, including `schema_version`
+    fn json_schema() -> Value;
+
+    /// Consistency checks the schema can't express, e.g. dangling module IDs
+    fn check(&self) -> Vec<String>;
+
+    /// Rewrite every file path with `map`. `path_to_id` keys are kept only
+    /// while they are absolute and re-added from the module entries once the
+    /// paths are absolute again.
+    fn map_paths(&mut self, map: &dyn Fn(&str) -> String);
+
+    /// File name of the schema inside the schema directory
+    fn schema_file_name() -> String {
+        format!("{}.schema.json", Self::FILE_NAME.trim_end_matches(".json"))
+    }
+}
+
+/// Why a manifest file could not be read
+#[derive(Debug)]
+pub enum ManifestError {
+    Io { path: PathBuf, error: io::Error },
+    /// Written by a build with another manifest format (`None` when the
+    /// file has no `schema_version`)
+    VersionMismatch { path: PathBuf, found: Option<u64> },
+    /// Not valid JSON, doesn't match the schema or fails consistency checks
+    Invalid { path: PathBuf, problems: Vec<String> },
+}
+
+impl std::fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io { path, error } => write!(f, "Failed to read {}: {error}", path.display()),
+            Self::VersionMismatch { path, found } => write!(
+                f,
+                "{} has schema_version {}, but this version of Vista reads {MANIFEST_SCHEMA_VERSION}. Rebuild the app.",
+                path.display(),
+                found.map_or_else(|| "(missing)".to_string(), |v| v.to_string()),
+            ),
+            Self::Invalid { path, problems } => {
+                write!(f, "{} is not a valid manifest:", path.display())?;
+                for problem in problems {
+                    write!(f, "\n  - {problem}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+/// Default manifest directory: `.vista/manifests` in the project root
+pub fn default_manifest_dir(app_dir: &Path) -> PathBuf {
+    default_build_dir(app_dir).join(MANIFESTS_DIR)
+}
+
+/// Write a manifest with its `schema_version` and paths relative to
+/// `project_root`, returning the file path. The file is left untouched when
+/// its content is unchanged.
+pub fn write_manifest<M: ManifestFile + Clone>(dir: &Path, project_root: &Path, manifest: &M) -> io::Result<PathBuf> {
+    let mut manifest = manifest.clone();
+    manifest.map_paths(&|path| portable_path(project_root, path));
+    let mut value = serde_json::to_value(&manifest)?;
+    if let Value::Object(fields) = &mut value {
+        fields.insert("schema_version".to_string(), json!(MANIFEST_SCHEMA_VERSION));
+    }
+    write_json(dir, M::FILE_NAME, &value)
+}
+
+/// Write the JSON Schema of a manifest type into `dir/schema/`
+pub fn write_manifest_schema<M: ManifestFile>(dir: &Path) -> io::Result<PathBuf> {
+    write_json(&dir.join(MANIFEST_SCHEMA_DIR), &M::schema_file_name(), &M::json_schema())
+}
+
+/// Write all three manifests and their schemas, returning the written paths
+pub fn write_manifests(
+    dir: &Path,
+    project_root: &Path,
+    client: &ClientManifest,
+    server: &ServerManifest,
+    server_actions: &ServerActionManifest,
+) -> io::Result<Vec<PathBuf>> {
+    Ok(vec![
+        write_manifest(dir, project_root, client)?,
+        write_manifest(dir, project_root, server)?,
+        write_manifest(dir, project_root, server_actions)?,
+        write_manifest_schema::<ClientManifest>(dir)?,
+        write_manifest_schema::<ServerManifest>(dir)?,
+        write_manifest_schema::<ServerActionManifest>(dir)?,
+    ])
+}
+
+/// Read a manifest back, checking its version, schema and consistency, and
+/// resolve its paths against `project_root`
+pub fn read_manifest<M: ManifestFile>(dir: &Path, project_root: &Path) -> Result<M, ManifestError> {
+    let path = dir.join(M::FILE_NAME);
+    let source = std::fs::read_to_string(&path)
+        .map_err(|error| ManifestError::Io { path: path.clone(), error })?;
+    let invalid = |problems: Vec<String>| ManifestError::Invalid { path: path.clone(), problems };
+
+    let mut value: Value = serde_json::from_str(&source).map_err(|e| invalid(vec![e.to_string()]))?;
+    let found = value.get("schema_version").and_then(Value::as_u64);
+    if found != Some(MANIFEST_SCHEMA_VERSION) {
+        return Err(ManifestError::VersionMismatch { path, found });
+    }
+
+    let schema = M::json_schema();
+    let mut problems = Vec::new();
+    validate(&value, &schema, &schema, "", &mut problems);
+    if !problems.is_empty() {
+        return Err(invalid(problems));
+    }
+
+    if let Value::Object(fields) = &mut value {
+        fields.remove("schema_version");
+    }
+    let mut manifest: M = serde_json::from_value(value).map_err(|e| invalid(vec![e.to_string()]))?;
+    manifest.map_paths(&|path| resolve_path(project_root, path));
+    let problems = manifest.check();
+    if !problems.is_empty() {
+        return Err(invalid(problems));
+    }
+    Ok(manifest)
+}
+
+/// `/`-separated path relative to `root`; relative paths are kept
+fn portable_path(root: &Path, path: &str) -> String {
+    if Path::new(path).is_absolute() {
+        relative_path_from(Path::new(path), root)
+    } else {
+        path.to_string()
+    }
+}
+
+fn resolve_path(root: &Path, path: &str) -> String {
+    normalize_path(&root.join(path)).to_string_lossy().to_string()
+}
+
+fn write_json(dir: &Path, name: &str, value: &Value) -> io::Result<PathBuf> {
+    let path = dir.join(name);
+    let mut content = serde_json::to_string_pretty(value)?;
+    content.push('\n');
+
+    if std::fs::read_to_string(&path).ok().as_deref() != Some(content.as_str()) {
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(&path, content)?;
+    }
+    Ok(path)
+}
+
+// ============================================================================
+// Schemas
+// ============================================================================
+
+impl ManifestFile for ClientManifest {
+    const FILE_NAME: &'static str = "client-manifest.json";
+
+    fn json_schema() -> Value {
+        let entry = object([
+            ("id", string()),
+            ("path", string()),
+            ("absolute_path", string()),
+            ("chunk_name", string()),
+            ("exports", array(string())),
+            ("async_load", boolean()),
+        ]);
+        root_schema("Vista client manifest", [
+            ("build_id", string()),
+            ("client_modules", map(reference("client_module"))),
+            ("path_to_id", map(string())),
+            ("ssr_module_mapping", map(string())),
+        ], [("client_module", entry)])
+    }
+
+    fn check(&self) -> Vec<String> {
+        let mut problems = mismatched_ids(
+            "client_modules",
+            self.client_modules.iter().map(|(key, entry)| (key, &entry.id)),
+        );
+        problems.extend(dangling_ids(&self.path_to_id, |id| self.client_modules.contains_key(id)));
+        problems
+    }
+
+    fn map_paths(&mut self, map: &dyn Fn(&str) -> String) {
+        self.path_to_id.retain(|path, _| !Path::new(path).is_absolute());
+        for entry in self.client_modules.values_mut() {
+            entry.absolute_path = map(&entry.absolute_path);
+            index_absolute_path(&mut self.path_to_id, &entry.absolute_path, &entry.id);
+        }
+        self.ssr_module_mapping = std::mem::take(&mut self.ssr_module_mapping)
+            .into_iter()
+            .map(|(path, chunk)| (map(&path), chunk))
+            .collect();
+    }
+}
+
+impl ManifestFile for ServerManifest {
+    const FILE_NAME: &'static str = "server-manifest.json";
+
+    fn json_schema() -> Value {
+        let module = object([
+            ("id", string()),
+            ("path", string()),
+            ("absolute_path", string()),
+            ("component_type", string()),
+            ("has_metadata", boolean()),
+            ("has_generate_metadata", boolean()),
+            ("static_metadata", nullable(json!({ "type": "object" }))),
+            ("client_dependencies", array(string())),
+        ]);
+        let route = object([
+            ("pattern", string()),
+            ("page_path", string()),
+            ("layout_paths", array(string())),
+            ("loading_path", nullable(string())),
+            ("error_path", nullable(string())),
+            ("not_found_path", nullable(string())),
+            ("route_type", string()),
+            ("slot", nullable(string())),
+            ("interception", nullable(object([("marker", string()), ("source_pattern", string())]))),
+            ("slots", array(object([
+                ("name", string()),
+                ("layout_path", nullable(string())),
+                ("page_path", nullable(string())),
+                ("default_path", nullable(string())),
+            ]))),
+            ("boundaries", object([
+                ("segments", array(string())),
+                ("layouts", array(nullable(string()))),
+                ("templates", array(nullable(string()))),
+                ("loading", array(nullable(string()))),
+                ("errors", array(nullable(string()))),
+                ("not_found", array(nullable(string()))),
+            ])),
+            ("has_generate_static_params", boolean()),
+            ("segment_config", reference("segment_config")),
+        ]);
+        let segment_config = object([
+            ("dynamic", nullable(string())),
+            ("dynamic_params", nullable(boolean())),
+            ("revalidate", nullable(json!({ "anyOf": [{ "const": false }, integer()] }))),
+            ("fetch_cache", nullable(string())),
+            ("runtime", nullable(string())),
+            ("preferred_region", nullable(array(string()))),
+            ("max_duration", nullable(integer())),
+        ]);
+        let span = object([
+            ("path", string()),
+            ("relative_path", string()),
+            ("line", integer()),
+            ("column", integer()),
+            ("label", nullable(string())),
+        ]);
+        let diagnostic = object([
+            ("severity", json!({ "enum": ["Error", "Warning"] })),
+            ("code", string()),
+            ("message", string()),
+            ("primary_span", nullable(reference("source_span"))),
+            ("secondary_spans", array(reference("source_span"))),
+            ("code_frame", nullable(string())),
+            ("fix", nullable(string())),
+        ]);
+        root_schema("Vista server manifest", [
+            ("build_id", string()),
+            ("server_modules", map(reference("server_module"))),
+            ("path_to_id", map(string())),
+            ("routes", array(reference("route"))),
+            ("diagnostics", array(reference("diagnostic"))),
+        ], [
+            ("server_module", module),
+            ("route", route),
+            ("segment_config", segment_config),
+            ("source_span", span),
+            ("diagnostic", diagnostic),
+        ])
+    }
+
+    fn check(&self) -> Vec<String> {
+        let mut problems = mismatched_ids(
+            "server_modules",
+            self.server_modules.iter().map(|(key, entry)| (key, &entry.id)),
+        );
+        problems.extend(dangling_ids(&self.path_to_id, |id| self.server_modules.contains_key(id)));
+        problems
+    }
+
+    fn map_paths(&mut self, map: &dyn Fn(&str) -> String) {
+        let map_option = |path: &mut Option<String>| {
+            if let Some(path) = path {
+                *path = map(path);
+            }
+        };
+
+        self.path_to_id.retain(|path, _| !Path::new(path).is_absolute());
+        for entry in self.server_modules.values_mut() {
+            entry.absolute_path = map(&entry.absolute_path);
+            index_absolute_path(&mut self.path_to_id, &entry.absolute_path, &entry.id);
+        }
+        for route in &mut self.routes {
+            route.page_path = map(&route.page_path);
+            route.layout_paths.iter_mut().for_each(|path| *path = map(path));
+            map_option(&mut route.loading_path);
+            map_option(&mut route.error_path);
+            map_option(&mut route.not_found_path);
+            for slot in &mut route.slots {
+                map_option(&mut slot.layout_path);
+                map_option(&mut slot.page_path);
+                map_option(&mut slot.default_path);
+            }
+            let boundaries = &mut route.boundaries;
+            for list in [
+                &mut boundaries.layouts,
+                &mut boundaries.templates,
+                &mut boundaries.loading,
+                &mut boundaries.errors,
+                &mut boundaries.not_found,
+            ] {
+                list.iter_mut().for_each(map_option);
+            }
+        }
+        for diagnostic in &mut self.diagnostics {
+            for span in diagnostic.primary_span.iter_mut().chain(&mut diagnostic.secondary_spans) {
+                span.path = map(&span.path);
+            }
+        }
+    }
+}
+
+impl ManifestFile for ServerActionManifest {
+    const FILE_NAME: &'static str = "server-action-manifest.json";
+
+    fn json_schema() -> Value {
+        let action = object([
+            ("id", string()),
+            ("path", string()),
+            ("absolute_path", string()),
+            ("module_id", string()),
+            ("export_name", string()),
+            ("inline", boolean()),
+        ]);
+        root_schema("Vista server action manifest", [
+            ("build_id", string()),
+            ("actions", map(reference("server_action"))),
+        ], [("server_action", action)])
+    }
+
+    fn check(&self) -> Vec<String> {
+        mismatched_ids("actions", self.actions.iter().map(|(key, entry)| (key, &entry.id)))
+    }
+
+    fn map_paths(&mut self, map: &dyn Fn(&str) -> String) {
+        for entry in self.actions.values_mut() {
+            entry.absolute_path = map(&entry.absolute_path);
+        }
+    }
+}
+
+/// Look a module up by its absolute path too, as the generated manifests do
+fn index_absolute_path(path_to_id: &mut std::collections::BTreeMap<String, String>, path: &str, id: &str) {
+    if Path::new(path).is_absolute() {
+        path_to_id.insert(path.to_string(), id.to_string());
+    }
+}
+
+fn mismatched_ids<'a>(field: &str, entries: impl Iterator<Item = (&'a String, &'a String)>) -> Vec<String> {
+    entries
+        .filter(|(key, id)| key != id)
+        .map(|(key, id)| format!("/{field}/{key}: entry has id \"{id}\""))
+        .collect()
+}
+
+fn dangling_ids(
+    path_to_id: &std::collections::BTreeMap<String, String>,
+    exists: impl Fn(&str) -> bool,
+) -> Vec<String> {
+    path_to_id.iter()
+        .filter(|(_, id)| !exists(id))
+        .map(|(path, id)| format!("/path_to_id/{path}: unknown module \"{id}\""))
+        .collect()
+}
+
+fn root_schema<const P: usize, const D: usize>(
+    title: &str,
+    properties: [(&str, Value); P],
+    defs: [(&str, Value); D],
+) -> Value {
+    let mut schema = object(
+        std::iter::once(("schema_version", json!({ "const": MANIFEST_SCHEMA_VERSION }))).chain(properties),
+    );
+    let fields = schema.as_object_mut().expect("object schema");
+    fields.insert("$schema".to_string(), json!("https://json-schema.org/draft/2020-12/schema"));
+    fields.insert("title".to_string(), json!(title));
+    fields.insert(
+        "$defs".to_string(),
+        Value::Object(defs.into_iter().map(|(name, def)| (name.to_string(), def)).collect()),
+    );
+    schema
+}
+
+fn object<'a>(properties: impl IntoIterator<Item = (&'a str, Value)>) -> Value {
+    let properties: Map<String, Value> = properties.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+    let required: Vec<&String> = properties.keys().collect();
+    json!({
+        "type": "object",
+        "required": required,
+        "properties": properties,
+        "additionalProperties": false,
+    })
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn boolean() -> Value {
+    json!({ "type": "boolean" })
+}
+
+fn integer() -> Value {
+    json!({ "type": "integer", "minimum": 0 })
+}
+
+fn array(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+fn map(values: Value) -> Value {
+    json!({ "type": "object", "additionalProperties": values })
+}
+
+fn nullable(schema: Value) -> Value {
+    json!({ "anyOf": [schema, { "type": "null" }] })
+}
+
+fn reference(name: &str) -> Value {
+    json!({ "$ref": format!("#/$defs/{name}") })
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+/// Validate `value` against the subset of JSON Schema the manifest schemas
+/// use, pushing one problem per mismatch with its JSON pointer
+fn validate(value: &Value, schema: &Value, root: &Value, pointer: &str, problems: &mut Vec<String>) {
+    let at = || if pointer.is_empty() { "(root)".to_string() } else { pointer.to_string() };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let target = reference.strip_prefix('#').and_then(|p| root.pointer(p));
+        match target {
+            Some(target) => validate(value, target, root, pointer, problems),
+            None => problems.push(format!("{}: unresolvable schema reference {reference}", at())),
+        }
+        return;
+    }
+
+    if let Some(options) = schema.get("anyOf").and_then(Value::as_array) {
+        let matches = options.iter().any(|option| {
+            let mut nested = Vec::new();
+            validate(value, option, root, pointer, &mut nested);
+            nested.is_empty()
+        });
+        if !matches {
+            problems.push(format!("{}: {value} doesn't match any of the allowed types", at()));
+        }
+        return;
+    }
+
+    if let Some(expected) = schema.get("const") {
+        if value != expected {
+            problems.push(format!("{}: expected {expected}, got {value}", at()));
+        }
+        return;
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            problems.push(format!("{}: {value} is not one of {}", at(), Value::Array(allowed.clone())));
+        }
+        return;
+    }
+
+    let type_matches = match schema.get("type").and_then(Value::as_str) {
+        Some("object") => value.is_object(),
+        Some("array") => value.is_array(),
+        Some("string") => value.is_string(),
+        Some("boolean") => value.is_boolean(),
+        Some("integer") => value.is_u64() || value.is_i64(),
+        Some("null") => value.is_null(),
+        _ => true,
+    };
+    if !type_matches {
+        problems.push(format!("{}: expected {}, got {value}", at(), schema["type"]));
+        return;
+    }
+
+    if let (Some(minimum), Some(number)) = (schema.get("minimum").and_then(Value::as_i64), value.as_i64()) {
+        if number < minimum {
+            problems.push(format!("{}: {number} is less than {minimum}", at()));
+        }
+    }
+
+    if let (Some(items), Value::Array(values)) = (schema.get("items"), value) {
+        for (index, item) in values.iter().enumerate() {
+            validate(item, items, root, &format!("{pointer}/{index}"), problems);
+        }
+    }
+
+    let Value::Object(fields) = value else {
+        return;
+    };
+    let properties = schema.get("properties").and_then(Value::as_object);
+    for name in schema.get("required").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str) {
+        if !fields.contains_key(name) {
+            problems.push(format!("{}: missing \"{name}\"", at()));
+        }
+    }
+    for (name, field) in fields {
+        let field_pointer = format!("{pointer}/{name}");
+        match (properties.and_then(|p| p.get(name)), schema.get("additionalProperties")) {
+            (Some(field_schema), _) => validate(field, field_schema, root, &field_pointer, problems),
+            (None, Some(Value::Bool(false))) => problems.push(format!("{field_pointer}: unknown field")),
+            (None, Some(extra)) => validate(field, extra, root, &field_pointer, problems),
+            (None, None) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rsc::{
+        generate_client_manifest_from_scan, generate_server_action_manifest_from_scan,
+        generate_server_manifest_from_scan, scan_app_directory,
+    };
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        for (file, source) in [
+            ("layout.tsx", "export const revalidate = 60;\nexport default function Layout() {}"),
+            ("page.tsx", "import Counter from './counter';\nexport const metadata = { title: 'Home' };\nexport default function Page() {}"),
+            ("counter.tsx", "'use client';\nexport default function Counter() {}"),
+            ("blog/[slug]/page.tsx", "export const dynamic = 'force-static';\nexport default function Page() {}"),
+            ("blog/loading.tsx", "export default function Loading() {}"),
+            ("@modal/default.tsx", "export default function Default() {}"),
+            ("(.)photo/[id]/page.tsx", "export default function Photo() {}"),
+            ("actions.ts", "'use server';\nexport async function save() {}"),
+            ("broken/page.tsx", "export const runtime = 'deno';\nexport default function Page() {}"),
+        ] {
+            let path = app.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, source).unwrap();
+        }
+        let scan = scan_app_directory(&app.to_string_lossy());
+        let client = generate_client_manifest_from_scan(&scan, "build-1");
+        let server = generate_server_manifest_from_scan(&scan, "build-1");
+        let actions = generate_server_action_manifest_from_scan(&scan, "build-1");
+        assert!(!server.diagnostics.is_empty());
+
+        let out = default_manifest_dir(&app);
+        let written = write_manifests(&out, dir.path(), &client, &server, &actions).unwrap();
+        assert_eq!(written.len(), 6);
+        assert!(out.join("schema/server-manifest.schema.json").is_file());
+        let source = std::fs::read_to_string(out.join("server-manifest.json")).unwrap();
+        assert!(source.contains("\"schema_version\": 2"));
+        assert!(source.contains("\"page_path\": \"app/page.tsx\""));
+
+        let read: ServerManifest = read_manifest(&out, dir.path()).unwrap();
+        assert_eq!(serde_json::to_string(&read).unwrap(), serde_json::to_string(&server).unwrap());
+        let read: ClientManifest = read_manifest(&out, dir.path()).unwrap();
+        assert_eq!(serde_json::to_string(&read).unwrap(), serde_json::to_string(&client).unwrap());
+        let read: ServerActionManifest = read_manifest(&out, dir.path()).unwrap();
+        assert_eq!(serde_json::to_string(&read).unwrap(), serde_json::to_string(&actions).unwrap());
+    }
+
+    #[test]
+    fn test_manifests_are_identical_across_checkouts() {
+        let files = [
+            ("package.json", "{}"),
+            ("app/layout.tsx", "export default function Layout() {}"),
+            ("app/page.tsx", "import Counter from '../components/counter';\nimport { save } from '../lib/actions';\nexport default function Page() {}"),
+            ("app/blog/[slug]/page.tsx", "export const runtime = 'deno';\nexport default function Post() {}"),
+            ("app/blog/[id]/page.tsx", "export default function Post() {}"),
+            ("app/@modal/default.tsx", "export default function Default() {}"),
+            ("components/counter.tsx", "'use client';\nexport default function Counter() {}"),
+            ("lib/actions.ts", "'use server';\nexport async function save() {}"),
+        ];
+        let write = |root: &Path| -> Vec<String> {
+            for (file, source) in files {
+                let path = root.join(file);
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(path, source).unwrap();
+            }
+            let scan = scan_app_directory(&root.join("app").to_string_lossy());
+            let out = root.join("out");
+            write_manifests(
+                &out,
+                root,
+                &generate_client_manifest_from_scan(&scan, "build-1"),
+                &generate_server_manifest_from_scan(&scan, "build-1"),
+                &generate_server_action_manifest_from_scan(&scan, "build-1"),
+            )
+            .unwrap();
+            ["client-manifest.json", "server-manifest.json", "server-action-manifest.json"]
+                .iter()
+                .map(|file| std::fs::read_to_string(out.join(file)).unwrap())
+                .collect()
+        };
+
+        let (first, second) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        let first_files = write(first.path());
+        assert_eq!(first_files, write(second.path()));
+        assert!(first_files[0].contains("\"absolute_path\": \"components/counter.tsx\""));
+        assert!(first_files[1].contains("ROUTE_PATTERN_CONFLICT"));
+        assert!(first_files[2].contains("\"absolute_path\": \"lib/actions.ts\""));
+        let root = first.path().to_string_lossy().to_string();
+        assert!(first_files.iter().all(|file| !file.contains(&root)));
+
+        let read: ServerManifest = read_manifest(&first.path().join("out"), second.path()).unwrap();
+        let page = &read.routes.iter().find(|r| r.pattern == "/").unwrap().page_path;
+        assert_eq!(Path::new(page), second.path().join("app/page.tsx"));
+        assert_eq!(read.path_to_id.get(page.as_str()).map(String::as_str), Some("server:page"));
+    }
+
+    #[test]
+    fn test_read_manifest_rejects_mismatches() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(ServerActionManifest::FILE_NAME);
+        let write = |value: Value| std::fs::write(&path, value.to_string()).unwrap();
+
+        write(json!({ "build_id": "b", "actions": {} }));
+        let err = read_manifest::<ServerActionManifest>(dir.path(), dir.path()).unwrap_err();
+        assert!(matches!(err, ManifestError::VersionMismatch { found: None, .. }));
+
+        write(json!({ "schema_version": 99, "build_id": "b", "actions": {} }));
+        let err = read_manifest::<ServerActionManifest>(dir.path(), dir.path()).unwrap_err();
+        assert!(err.to_string().contains("has schema_version 99, but this version of Vista reads 2"));
+
+        write(json!({
+            "schema_version": 2,
+            "build_id": 7,
+            "actions": { "a": { "id": "a", "path": "x.ts", "absolute_path": "/x.ts", "module_id": "server:x", "export_name": "save" } },
+            "extra": true,
+        }));
+        let ManifestError::Invalid { problems, .. } = read_manifest::<ServerActionManifest>(dir.path(), dir.path()).unwrap_err() else {
+            panic!("expected an invalid manifest");
+        };
+        assert_eq!(problems, vec![
+            "/actions/a: missing \"inline\"",
+            "/build_id: expected \"string\", got 7",
+            "/extra: unknown field",
+        ]);
+
+        write(json!({
+            "schema_version": 2,
+            "build_id": "b",
+            "actions": { "a": { "id": "b", "path": "x.ts", "absolute_path": "/x.ts", "module_id": "server:x", "export_name": "save", "inline": false } },
+        }));
+        let err = read_manifest::<ServerActionManifest>(dir.path(), dir.path()).unwrap_err();
+        assert!(err.to_string().contains("/actions/a: entry has id \"b\""));
+    }
+}
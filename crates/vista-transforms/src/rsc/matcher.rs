@@ -0,0 +1,271 @@
+//! RSC Route Matcher
+//!
+//! Compiles `RouteEntry` patterns into a segment trie and matches request
+//! pathnames against it. At every segment a static child beats a dynamic
+//! one, which beats a catch-all, which beats an optional catch-all; the
+//! matcher backtracks so a more specific branch that dead-ends falls back
+//! to the next one.
+
+use std::collections::{BTreeMap, HashMap};
+use serde::{Serialize, Deserialize};
+use super::manifest::{RouteEntry, ServerManifest};
+
+/// Value of a matched route parameter
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RouteParam {
+    /// `[slug]`
+    Single(String),
+    /// `[...path]` and `[[...path]]`
+    Multiple(Vec<String>),
+}
+
+/// A matched route with its decoded parameters
+#[derive(Debug, Clone)]
+pub struct RouteMatch<'a> {
+    pub route: &'a RouteEntry,
+    /// Index of the route in the list the matcher was built from
+    pub index: usize,
+    /// Optional catch-alls that matched nothing are left out
+    pub params: BTreeMap<String, RouteParam>,
+}
+
+#[derive(Debug, Default)]
+struct TrieNode {
+    static_children: HashMap<String, TrieNode>,
+    dynamic: Option<Box<TrieNode>>,
+    /// Route ending in `:param*` at this node
+    catch_all: Option<usize>,
+    /// Route ending in `:param*?` at this node
+    optional_catch_all: Option<usize>,
+    /// Route ending exactly at this node
+    route: Option<usize>,
+}
+
+/// Compiled route matcher
+#[derive(Debug)]
+pub struct RouteMatcher {
+    root: TrieNode,
+    routes: Vec<RouteEntry>,
+    /// Parameter names of each route, in pattern order
+    param_names: Vec<Vec<String>>,
+}
+
+impl RouteMatcher {
+    /// Compile routes. Slot and intercepting routes are skipped: they are
+    /// never the target of a request on their own. When two routes share a
+    /// pattern the first one wins.
+    pub fn new(routes: Vec<RouteEntry>) -> Self {
+        let mut root = TrieNode::default();
+        let mut param_names = Vec::with_capacity(routes.len());
+
+        for (index, route) in routes.iter().enumerate() {
+            let parts: Vec<&str> = route.pattern.split('/').filter(|p| !p.is_empty()).collect();
+            param_names.push(
+                parts
+                    .iter()
+                    .filter_map(|p| p.strip_prefix(':'))
+                    .map(|p| p.trim_end_matches('?').trim_end_matches('*').to_string())
+                    .collect(),
+            );
+            if route.slot.is_some() || route.interception.is_some() {
+                continue;
+            }
+
+            let mut node = &mut root;
+            let mut ending = &mut node.route;
+            for part in parts {
+                if part.starts_with(':') && part.ends_with("*?") {
+                    ending = &mut node.optional_catch_all;
+                    break;
+                } else if part.starts_with(':') && part.ends_with('*') {
+                    ending = &mut node.catch_all;
+                    break;
+                } else if part.starts_with(':') {
+                    node = node.dynamic.get_or_insert_with(Default::default);
+                } else {
+                    node = node.static_children.entry(part.to_string()).or_default();
+                }
+                ending = &mut node.route;
+            }
+            ending.get_or_insert(index);
+        }
+
+        Self {
+            root,
+            routes,
+            param_names,
+        }
+    }
+
+    /// Compile the routes of a server manifest
+    pub fn from_manifest(manifest: &ServerManifest) -> Self {
+        Self::new(manifest.routes.clone())
+    }
+
+    pub fn routes(&self) -> &[RouteEntry] {
+        &self.routes
+    }
+
+    /// Match a pathname (query and hash are ignored, trailing slashes too).
+    /// Segments are percent-decoded before being returned as params.
+    pub fn match_path(&self, pathname: &str) -> Option<RouteMatch<'_>> {
+        let path = pathname.split(['?', '#']).next().unwrap_or_default();
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        let mut values = Vec::new();
+        let (index, rest) = match_node(&self.root, &segments, &mut values)?;
+
+        let names = &self.param_names[index];
+        let mut params = BTreeMap::new();
+        for (name, value) in names.iter().zip(values.iter()) {
+            params.insert(name.clone(), RouteParam::Single(decode_segment(value)));
+        }
+        if let (Some(name), Some(rest)) = (names.get(values.len()), rest) {
+            if !rest.is_empty() {
+                let rest = rest.iter().map(|s| decode_segment(s)).collect();
+                params.insert(name.clone(), RouteParam::Multiple(rest));
+            }
+        }
+
+        Some(RouteMatch {
+            route: &self.routes[index],
+            index,
+            params,
+        })
+    }
+}
+
+/// Find the most specific route for `segments`. Returns the route index and,
+/// for catch-alls, the segments they consumed. Dynamic values are pushed to
+/// `values` in order (and popped again when a branch fails).
+fn match_node<'s>(
+    node: &TrieNode,
+    segments: &[&'s str],
+    values: &mut Vec<&'s str>,
+) -> Option<(usize, Option<Vec<&'s str>>)> {
+    let Some((first, rest)) = segments.split_first() else {
+        return node
+            .route
+            .map(|index| (index, None))
+            .or_else(|| node.optional_catch_all.map(|index| (index, Some(Vec::new()))));
+    };
+
+    if let Some(child) = node.static_children.get(*first) {
+        if let Some(found) = match_node(child, rest, values) {
+            return Some(found);
+        }
+    }
+    if let Some(child) = &node.dynamic {
+        values.push(first);
+        if let Some(found) = match_node(child, rest, values) {
+            return Some(found);
+        }
+        values.pop();
+    }
+    node.catch_all
+        .or(node.optional_catch_all)
+        .map(|index| (index, Some(segments.to_vec())))
+}
+
+/// Percent-decode a path segment; invalid escapes or UTF-8 leave it as is
+fn decode_segment(segment: &str) -> String {
+    if !segment.contains('%') {
+        return segment.to_string();
+    }
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = segment.get(i + 1..i + 3).and_then(|h| u8::from_str_radix(h, 16).ok());
+            match hex {
+                Some(byte) => {
+                    decoded.push(byte);
+                    i += 3;
+                    continue;
+                }
+                None => return segment.to_string(),
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8(decoded).unwrap_or_else(|_| segment.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(pattern: &str) -> RouteEntry {
+        RouteEntry {
+            pattern: pattern.to_string(),
+            page_path: format!("{pattern}/page.tsx"),
+            layout_paths: vec![],
+            loading_path: None,
+            error_path: None,
+            not_found_path: None,
+            route_type: String::new(),
+            slot: None,
+            interception: None,
+            slots: vec![],
+            boundaries: Default::default(),
+            has_generate_static_params: false,
+            segment_config: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_match_specificity_and_params() {
+        let matcher = RouteMatcher::new(vec![
+            route("/docs/:path*"),
+            route("/blog/:slug"),
+            route("/blog/new"),
+            route("/"),
+            route("/shop/:category/:item"),
+            route("/shop/:category/sale"),
+            route("/wiki/:page*?"),
+        ]);
+        let matched = |path: &str| matcher.match_path(path).map(|m| (m.route.pattern.clone(), m.params));
+
+        assert_eq!(matched("/").unwrap().0, "/");
+        assert_eq!(matched("/blog/new").unwrap().0, "/blog/new");
+        let (pattern, params) = matched("/blog/hello%20world/").unwrap();
+        assert_eq!(pattern, "/blog/:slug");
+        assert_eq!(params["slug"], RouteParam::Single("hello world".to_string()));
+
+        let (_, params) = matched("/docs/a/b%2Fc?x=1").unwrap();
+        assert_eq!(params["path"], RouteParam::Multiple(vec!["a".to_string(), "b/c".to_string()]));
+        assert!(matched("/docs").is_none());
+
+        // A static segment beats a dynamic one at the same depth
+        assert_eq!(matched("/shop/shoes/sale").unwrap().0, "/shop/:category/sale");
+        let (pattern, params) = matched("/shop/shoes/boot").unwrap();
+        assert_eq!(pattern, "/shop/:category/:item");
+        assert_eq!(params.len(), 2);
+
+        let (pattern, params) = matched("/wiki").unwrap();
+        assert_eq!((pattern.as_str(), params.is_empty()), ("/wiki/:page*?", true));
+        assert_eq!(matched("/wiki/a/b").unwrap().1["page"], RouteParam::Multiple(vec!["a".to_string(), "b".to_string()]));
+        assert!(matched("/missing").is_none());
+    }
+
+    #[test]
+    fn test_backtracking_and_skipped_routes() {
+        let mut modal = route("/photo/:id");
+        modal.slot = Some("modal".to_string());
+        let matcher = RouteMatcher::new(vec![
+            modal,
+            route("/a/b/c"),
+            route("/a/:x/d"),
+            route("/photo/:id"),
+        ]);
+        // "b" matches statically but dead-ends, the dynamic branch matches
+        let found = matcher.match_path("/a/b/d").unwrap();
+        assert_eq!(found.route.pattern, "/a/:x/d");
+        assert_eq!(found.params["x"], RouteParam::Single("b".to_string()));
+        assert_eq!(matcher.match_path("/photo/1").unwrap().index, 3);
+        assert_eq!(decode_segment("100%"), "100%");
+    }
+}
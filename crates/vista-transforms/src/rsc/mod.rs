@@ -2,17 +2,49 @@
 //! 
 //! High-performance Rust implementation for RSC compilation:
 //! - Fast directory scanning and component classification
+//! - Persistent incremental scan cache
+//! - Debounced app directory watching
+//! - Import resolution and module graph analysis
+//! - Route model and app structure validation
+//! - Route segment config parsing and inheritance
 //! - Manifest generation (client/server)
+//! - Versioned manifest files and JSON Schemas
+//! - URL route matching
+//! - Static path planning from generateStaticParams
+//! - Typed route declarations (.d.ts)
+//! - sitemap.xml and robots.txt generation
 //! - RSC payload serialization
 //! - Client component pre-rendering for zero CLS
 //! - Code transformation
 
 mod scanner;
+mod cache;
+mod watcher;
+mod graph;
+mod routes;
+mod segment_config;
+mod structure;
 mod manifest;
+mod manifest_file;
+mod matcher;
+mod static_paths;
+mod route_types;
+mod sitemap;
 mod serializer;
 mod prerender;
 
 pub use scanner::*;
+pub use cache::*;
+pub use watcher::*;
+pub use graph::*;
+pub use routes::*;
+pub use segment_config::*;
+pub use structure::*;
 pub use manifest::*;
+pub use manifest_file::*;
+pub use matcher::*;
+pub use static_paths::*;
+pub use route_types::*;
+pub use sitemap::*;
 pub use serializer::*;
 pub use prerender::*;
@@ -10,7 +10,7 @@ use std::path::Path;
 use serde::{Deserialize, Serialize};
 
 /// Extracted style information from a component
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ExtractedStyles {
     pub padding: Option<String>,
     pub margin: Option<String>,
@@ -31,30 +31,6 @@ pub struct ExtractedStyles {
     pub font_weight: Option<String>,
 }
 
-impl Default for ExtractedStyles {
-    fn default() -> Self {
-        Self {
-            padding: None,
-            margin: None,
-            background_color: None,
-            border_radius: None,
-            text_align: None,
-            display: None,
-            flex_direction: None,
-            gap: None,
-            justify_content: None,
-            align_items: None,
-            width: None,
-            height: None,
-            min_height: None,
-            min_width: None,
-            color: None,
-            font_size: None,
-            font_weight: None,
-        }
-    }
-}
-
 /// Pre-rendered component structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PrerenderedComponent {
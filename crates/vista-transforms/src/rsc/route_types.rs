@@ -0,0 +1,252 @@
+//! RSC Route Type Generation
+//!
+//! Turns the server manifest routes into `.vista/types/routes.d.ts`, which
+//! augments the `VistaRouteRegistry` interface of `vista/link` with the app's
+//! route patterns, their params and every href it serves. `Link`,
+//! `useIsActive` and `useParams` read their types from that registry, so a
+//! broken `<Link href>` or a misspelt param fails type checking.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use super::cache::default_build_dir;
+use super::manifest::{RouteEntry, ServerManifest};
+
+/// Directory under `.vista` holding generated declarations
+pub const TYPES_DIR: &str = "types";
+
+/// File name of the generated route declarations
+pub const ROUTE_TYPES_FILE: &str = "routes.d.ts";
+
+const HEADER: &str = "// Generated by Vista from the app directory. Do not edit.\n\n";
+
+/// Hrefs `Link` passes through without matching a route
+const PASSTHROUGH_HREFS: &[&str] = &[
+    "`${Route}?${string}`",
+    "`${Route}#${string}`",
+    "`${'http' | 'https'}://${string}`",
+    "`//${string}`",
+    "`mailto:${string}`",
+    "`tel:${string}`",
+    "`#${string}`",
+    "`?${string}`",
+];
+
+/// Render the route declarations for `routes`. Slot and intercepting routes
+/// are left out: their URLs are served by the main route with the same pattern.
+pub fn render_route_types(routes: &[RouteEntry]) -> String {
+    let mut patterns: Vec<&str> = routes
+        .iter()
+        .filter(|r| r.slot.is_none() && r.interception.is_none())
+        .map(|r| r.pattern.as_str())
+        .collect();
+    patterns.sort_unstable();
+    patterns.dedup();
+
+    let mut hrefs: Vec<String> = patterns.iter().flat_map(|p| route_hrefs(p)).collect();
+    hrefs.dedup();
+
+    let mut out = String::from(HEADER);
+    out.push_str("export {};\n\n/** URL paths served by the app */\ntype Route =");
+    push_union(&mut out, "  ", hrefs.into_iter());
+
+    out.push_str("\ndeclare module 'vista/link' {\n  interface VistaRouteRegistry {\n");
+    out.push_str("    /** Every route pattern of the app */\n    RoutePattern:");
+    push_union(&mut out, "      ", patterns.iter().map(|p| quote(p)));
+
+    out.push_str("    /** Params of each route pattern */\n    RouteParams: {\n");
+    for pattern in &patterns {
+        out.push_str(&format!("      {}: {};\n", quote(pattern), params_type(pattern)));
+    }
+    out.push_str("    };\n");
+
+    out.push_str("    /** URL paths served by the app */\n    Route: Route;\n");
+    out.push_str("    /** Hrefs accepted by `Link` */\n    Href:");
+    push_union(
+        &mut out,
+        "      ",
+        std::iter::once("Route".to_string()).chain(PASSTHROUGH_HREFS.iter().map(|h| h.to_string())),
+    );
+    out.push_str("  }\n}\n");
+    out
+}
+
+/// Write `.vista/types/routes.d.ts` for the manifest's routes, returning its
+/// path. The file is left untouched when its content would not change, so
+/// editors and watchers don't reload it on every build.
+pub fn generate_route_types(app_dir: &str, manifest: &ServerManifest) -> io::Result<PathBuf> {
+    let dir = default_build_dir(Path::new(app_dir)).join(TYPES_DIR);
+    let path = dir.join(ROUTE_TYPES_FILE);
+    let content = render_route_types(&manifest.routes);
+
+    if std::fs::read_to_string(&path).ok().as_deref() != Some(content.as_str()) {
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(&path, content)?;
+    }
+    Ok(path)
+}
+
+fn push_union(out: &mut String, indent: &str, members: impl Iterator<Item = String>) {
+    let mut empty = true;
+    for member in members {
+        out.push('\n');
+        out.push_str(indent);
+        out.push_str("| ");
+        out.push_str(&member);
+        empty = false;
+    }
+    if empty {
+        out.push_str(" never");
+    }
+    out.push_str(";\n");
+}
+
+/// TypeScript string literal
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+/// Params object type of a URL pattern
+fn params_type(pattern: &str) -> String {
+    let params: Vec<String> = pattern
+        .split('/')
+        .filter_map(|segment| segment.strip_prefix(':'))
+        .map(|param| {
+            if let Some(name) = param.strip_suffix("*?") {
+                format!("{}?: string[]", quote(name))
+            } else if let Some(name) = param.strip_suffix('*') {
+                format!("{}: string[]", quote(name))
+            } else {
+                format!("{}: string", quote(param))
+            }
+        })
+        .collect();
+
+    if params.is_empty() {
+        "Record<string, never>".to_string()
+    } else {
+        format!("{{ {} }}", params.join("; "))
+    }
+}
+
+/// Href types matching a URL pattern. An optional catch-all yields two: one
+/// without the segment and one with it.
+fn route_hrefs(pattern: &str) -> Vec<String> {
+    // Template literal body, and the plain path while no param has been seen
+    let mut prefix = String::new();
+    let mut static_path = Some(String::new());
+    for segment in pattern.split('/').filter(|s| !s.is_empty()) {
+        match segment.strip_prefix(':') {
+            Some(param) if param.ends_with("*?") => {
+                let bare = match static_path {
+                    Some(path) if path.is_empty() => quote("/"),
+                    Some(path) => quote(&path),
+                    None => format!("`{prefix}`"),
+                };
+                return vec![bare, format!("`{prefix}/${{string}}`")];
+            }
+            Some(_) => {
+                prefix.push_str("/${string}");
+                static_path = None;
+            }
+            None => {
+                prefix.push('/');
+                prefix.push_str(&escape_template(segment));
+                if let Some(path) = &mut static_path {
+                    path.push('/');
+                    path.push_str(segment);
+                }
+            }
+        }
+    }
+
+    match static_path {
+        Some(path) if path.is_empty() => vec![quote("/")],
+        Some(path) => vec![quote(&path)],
+        None => vec![format!("`{prefix}`")],
+    }
+}
+
+fn escape_template(segment: &str) -> String {
+    segment.replace('\\', "\\\\").replace('`', "\\`").replace("${", "\\${")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(pattern: &str) -> RouteEntry {
+        RouteEntry {
+            pattern: pattern.to_string(),
+            page_path: String::new(),
+            layout_paths: vec![],
+            loading_path: None,
+            error_path: None,
+            not_found_path: None,
+            route_type: String::new(),
+            slot: None,
+            interception: None,
+            slots: vec![],
+            boundaries: Default::default(),
+            has_generate_static_params: false,
+            segment_config: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_render_route_types() {
+        let mut modal = route("/photo/:id");
+        modal.slot = Some("modal".to_string());
+        let types = render_route_types(&[
+            route("/"),
+            route("/blog/:slug"),
+            route("/docs/:path*"),
+            route("/shop/:category/:item"),
+            route("/wiki/:page*?"),
+            route("/about"),
+            modal,
+        ]);
+
+        assert!(types.contains("      \"/blog/:slug\": { \"slug\": string };\n"));
+        assert!(types.contains("      \"/docs/:path*\": { \"path\": string[] };\n"));
+        assert!(types.contains("      \"/wiki/:page*?\": { \"page\"?: string[] };\n"));
+        assert!(types.contains("      \"/about\": Record<string, never>;\n"));
+        assert!(types.contains("\"/shop/:category/:item\": { \"category\": string; \"item\": string }"));
+
+        let route_union = types.split("type Route =").nth(1).unwrap().split(';').next().unwrap();
+        let hrefs: Vec<&str> = route_union.split("| ").skip(1).map(str::trim).collect();
+        assert_eq!(hrefs, vec![
+            "\"/\"",
+            "\"/about\"",
+            "`/blog/${string}`",
+            "`/docs/${string}`",
+            "`/shop/${string}/${string}`",
+            "\"/wiki\"",
+            "`/wiki/${string}`",
+        ]);
+        assert!(!types.contains("/photo"));
+        assert!(types.contains("declare module 'vista/link' {\n  interface VistaRouteRegistry {\n"));
+        assert!(types.contains("    Href:\n      | Route\n      | `${Route}?${string}`\n"));
+        // Only the registry is augmented; the modules keep their own declarations
+        assert_eq!(types.matches("declare module").count(), 1);
+        assert!(!types.contains("LinkProps") && !types.contains("vista/navigation"));
+    }
+
+    #[test]
+    fn test_generate_route_types_writes_to_build_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("package.json"), "{}").unwrap();
+        let app = dir.path().join("app");
+        std::fs::create_dir_all(&app).unwrap();
+        let manifest = ServerManifest {
+            build_id: "test".to_string(),
+            server_modules: Default::default(),
+            path_to_id: Default::default(),
+            routes: vec![],
+            diagnostics: vec![],
+        };
+
+        let path = generate_route_types(&app.to_string_lossy(), &manifest).unwrap();
+        assert_eq!(path, dir.path().join(".vista/types/routes.d.ts"));
+        assert!(std::fs::read_to_string(&path).unwrap().contains("RoutePattern: never;"));
+    }
+}
@@ -0,0 +1,384 @@
+//! RSC Route Model
+//!
+//! Classification of app directory segments and the URL patterns they map
+//! to, shared by manifest generation and structure validation.
+
+use std::cmp::Ordering;
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+
+/// Segments reserved for Vista's internal routes (never publicly routable)
+pub const RESERVED_INTERNAL_SEGMENTS: &[&str] = &["[not-found]"];
+
+/// Kind of an app directory segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SegmentKind {
+    /// `blog`
+    Static,
+    /// `[slug]`
+    Dynamic,
+    /// `[...path]`
+    CatchAll,
+    /// `[[...path]]`
+    OptionalCatchAll,
+    /// `(marketing)` - doesn't contribute to the URL
+    Group,
+    /// `@modal` - parallel route slot, rendered through a layout prop
+    Slot,
+    /// `(.)photo`, `(..)photo`, `(..)(..)photo` or `(...)photo` - intercepts
+    /// navigations to a route relative to this level
+    Intercepting,
+    /// `[not-found]`
+    ReservedInternal,
+}
+
+impl SegmentKind {
+    pub fn from_segment(segment: &str) -> Self {
+        if RESERVED_INTERNAL_SEGMENTS.contains(&segment) {
+            Self::ReservedInternal
+        } else if segment.len() > 1 && segment.starts_with('@') {
+            Self::Slot
+        } else if split_interception(segment).is_some() {
+            Self::Intercepting
+        } else if segment.starts_with('(') && segment.ends_with(')') {
+            Self::Group
+        } else if segment.starts_with("[[...") && segment.ends_with("]]") {
+            Self::OptionalCatchAll
+        } else if segment.starts_with("[...") && segment.ends_with(']') {
+            Self::CatchAll
+        } else if segment.starts_with('[') && segment.ends_with(']') {
+            Self::Dynamic
+        } else {
+            Self::Static
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Static => "static",
+            Self::Dynamic => "dynamic",
+            Self::CatchAll => "catch-all",
+            Self::OptionalCatchAll => "optional-catch-all",
+            Self::Group => "group",
+            Self::Slot => "slot",
+            Self::Intercepting => "intercepting",
+            Self::ReservedInternal => "reserved-internal",
+        }
+    }
+
+    /// URL pattern part for a segment of this kind (`:slug`, `:path*`, `:path*?`),
+    /// empty for groups and slots. Intercepting segments give the part of the
+    /// route they intercept; see [`apply_segment`] for the levels they climb.
+    pub fn pattern_part(&self, segment: &str) -> String {
+        match self {
+            Self::Dynamic => format!(":{}", &segment[1..segment.len() - 1]),
+            Self::CatchAll => format!(":{}*", &segment[4..segment.len() - 1]),
+            Self::OptionalCatchAll => format!(":{}*?", &segment[5..segment.len() - 2]),
+            Self::Group | Self::Slot => String::new(),
+            Self::Intercepting => {
+                let (_, rest) = split_interception(segment).unwrap_or(("", segment));
+                Self::from_segment(rest).pattern_part(rest)
+            }
+            Self::Static | Self::ReservedInternal => segment.to_string(),
+        }
+    }
+}
+
+/// Split an intercepting segment into its marker and the intercepted segment:
+/// `(..)(..)photo` -> `("(..)(..)", "photo")`
+pub fn split_interception(segment: &str) -> Option<(&str, &str)> {
+    let marker_len = if segment.starts_with("(...)") {
+        "(...)".len()
+    } else if segment.starts_with("(..)") {
+        let mut len = 0;
+        while segment[len..].starts_with("(..)") {
+            len += "(..)".len();
+        }
+        len
+    } else if segment.starts_with("(.)") {
+        "(.)".len()
+    } else {
+        return None;
+    };
+    let (marker, rest) = segment.split_at(marker_len);
+    (!rest.is_empty()).then_some((marker, rest))
+}
+
+/// Where an intercepting route applies
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteInterception {
+    /// `(.)`, `(..)`, `(..)(..)` or `(...)`
+    pub marker: String,
+    /// Pattern of the level the intercepting folder lives at: client-side
+    /// navigations from there to the intercepted route render this page instead
+    pub source_pattern: String,
+}
+
+/// Append a directory segment to the URL parts of its parent. Intercepting
+/// segments first climb the number of levels given by their marker.
+pub fn apply_segment(parts: &mut Vec<String>, segment: &str) {
+    let kind = SegmentKind::from_segment(segment);
+    if kind == SegmentKind::Intercepting {
+        if let Some((marker, _)) = split_interception(segment) {
+            if marker == "(...)" {
+                parts.clear();
+            } else {
+                let levels = marker.matches("(..)").count();
+                parts.truncate(parts.len().saturating_sub(levels));
+            }
+        }
+    }
+    let part = kind.pattern_part(segment);
+    if !part.is_empty() {
+        parts.push(part);
+    }
+}
+
+/// Join URL parts into a pattern ("/" when empty)
+pub fn join_pattern(parts: &[String]) -> String {
+    format!("/{}", parts.join("/"))
+}
+
+/// Everything the route model derives from a page's relative path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutePath {
+    pub pattern: String,
+    /// "static", "dynamic", "catch-all" or "optional-catch-all"
+    pub route_type: String,
+    /// Innermost `@slot` the page renders into
+    pub slot: Option<String>,
+    /// Directory containing that `@slot` folder, relative to the app dir
+    pub slot_parent: Option<String>,
+    /// Set when the page is inside an intercepting folder
+    pub interception: Option<RouteInterception>,
+}
+
+/// Derive the URL pattern, slot and interception of a page from its relative path
+pub fn parse_route_path(relative_path: &str) -> RoutePath {
+    let mut parts: Vec<String> = Vec::new();
+    let mut route = RoutePath {
+        pattern: String::new(),
+        route_type: "static".to_string(),
+        slot: None,
+        slot_parent: None,
+        interception: None,
+    };
+
+    let dir_path = Path::new(relative_path)
+        .parent()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let segments: Vec<&str> = dir_path.split(['/', '\\']).filter(|s| !s.is_empty()).collect();
+
+    for (index, segment) in segments.iter().enumerate() {
+        match SegmentKind::from_segment(segment) {
+            SegmentKind::Slot => {
+                route.slot = Some(segment[1..].to_string());
+                route.slot_parent = Some(segments[..index].join("/"));
+            }
+            SegmentKind::Intercepting => {
+                if let Some((marker, _)) = split_interception(segment) {
+                    route.interception = Some(RouteInterception {
+                        marker: marker.to_string(),
+                        source_pattern: join_pattern(&parts),
+                    });
+                }
+            }
+            _ => {}
+        }
+        apply_segment(&mut parts, segment);
+    }
+
+    // The route type follows the segments that remain in the URL
+    for part in &parts {
+        if part.ends_with("*?") {
+            route.route_type = "optional-catch-all".to_string();
+        } else if part.ends_with('*') {
+            if route.route_type != "optional-catch-all" {
+                route.route_type = "catch-all".to_string();
+            }
+        } else if part.starts_with(':') && route.route_type == "static" {
+            route.route_type = "dynamic".to_string();
+        }
+    }
+    route.pattern = join_pattern(&parts);
+    route
+}
+
+/// Whether a directory name is a valid route segment: alphanumerics, `-` and `_`,
+/// `[param]`, `[...param]`, `[[...param]]`, `(group)`, `@slot`, or an
+/// intercepting marker followed by a non-group segment
+pub fn is_valid_segment_name(segment: &str) -> bool {
+    fn is_name(name: &str) -> bool {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    }
+
+    if let Some(slot) = segment.strip_prefix('@') {
+        return is_name(slot);
+    }
+    if let Some((_, rest)) = split_interception(segment) {
+        return !rest.starts_with('(') && is_valid_segment_name(rest);
+    }
+
+    let inner = segment
+        .strip_prefix("[[...")
+        .and_then(|s| s.strip_suffix("]]"))
+        .or_else(|| segment.strip_prefix("[...").and_then(|s| s.strip_suffix(']')))
+        .or_else(|| segment.strip_prefix('[').and_then(|s| s.strip_suffix(']')))
+        .or_else(|| segment.strip_prefix('(').and_then(|s| s.strip_suffix(')')));
+    is_name(inner.unwrap_or(segment))
+}
+
+/// Whether a file path goes through one of the reserved internal segments
+pub(crate) fn is_reserved_internal_route(relative_path: &str) -> bool {
+    relative_path
+        .split(['/', '\\'])
+        .any(|segment| RESERVED_INTERNAL_SEGMENTS.contains(&segment))
+}
+
+/// Replace parameter names in a URL pattern with `param`, keeping catch-all
+/// modifiers, so `/blog/:id` and `/blog/:slug` compare equal
+pub fn normalize_route_pattern(pattern: &str) -> String {
+    pattern
+        .split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(param) if param.ends_with("*?") => ":param*?".to_string(),
+            Some(param) if param.ends_with('*') => ":param*".to_string(),
+            Some(_) => ":param".to_string(),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Normalized patterns a URL pattern serves, for conflict detection. An
+/// optional catch-all also matches its parent path, so `/docs/:slug*?`
+/// serves `/docs` as well.
+pub fn route_conflict_keys(pattern: &str) -> Vec<String> {
+    let normalized = normalize_route_pattern(pattern);
+    let parent = normalized
+        .strip_suffix("/:param*?")
+        .map(|parent| if parent.is_empty() { "/" } else { parent }.to_string());
+    std::iter::once(normalized).chain(parent).collect()
+}
+
+/// Rank of one URL pattern segment; `None` is the end of the pattern, which
+/// sorts after a static segment (deeper static prefixes win) but before any
+/// parameter.
+fn specificity_rank(segment: Option<&str>) -> u8 {
+    match segment {
+        Some(s) if !s.starts_with(':') => 0,
+        None => 1,
+        Some(s) if s.ends_with("*?") => 4,
+        Some(s) if s.ends_with('*') => 3,
+        Some(_) => 2,
+    }
+}
+
+/// Order URL patterns from most to least specific, segment by segment: a
+/// static segment beats a dynamic one, which beats a catch-all, which beats
+/// an optional catch-all. Patterns of equal specificity compare
+/// lexicographically so the order never depends on the filesystem.
+pub fn compare_route_specificity(a: &str, b: &str) -> Ordering {
+    let mut left = a.split('/').filter(|s| !s.is_empty());
+    let mut right = b.split('/').filter(|s| !s.is_empty());
+    loop {
+        let (l, r) = (left.next(), right.next());
+        if l.is_none() && r.is_none() {
+            return a.cmp(b);
+        }
+        match specificity_rank(l).cmp(&specificity_rank(r)) {
+            Ordering::Equal => continue,
+            unequal => return unequal,
+        }
+    }
+}
+
+/// Build URL pattern from relative path
+pub(crate) fn build_url_pattern(relative_path: &str) -> (String, String) {
+    let route = parse_route_path(relative_path);
+    (route.pattern, route.route_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserved_internal_route_detection() {
+        assert!(is_reserved_internal_route("docs/[not-found]/page.tsx"));
+        assert!(!is_reserved_internal_route("docs/[slug]/page.tsx"));
+    }
+
+    #[test]
+    fn test_build_url_pattern() {
+        assert_eq!(build_url_pattern("page.tsx"), ("/".to_string(), "static".to_string()));
+        assert_eq!(build_url_pattern("blog/page.tsx"), ("/blog".to_string(), "static".to_string()));
+        assert_eq!(build_url_pattern("blog/[slug]/page.tsx"), ("/blog/:slug".to_string(), "dynamic".to_string()));
+        assert_eq!(build_url_pattern("docs/[...path]/page.tsx"), ("/docs/:path*".to_string(), "catch-all".to_string()));
+        assert_eq!(build_url_pattern("(marketing)/about/page.tsx"), ("/about".to_string(), "static".to_string()));
+    }
+
+    #[test]
+    fn test_parse_route_path_slots_and_interception() {
+        let route = parse_route_path("dashboard/@team/settings/page.tsx");
+        assert_eq!(route.pattern, "/dashboard/settings");
+        assert_eq!(route.slot.as_deref(), Some("team"));
+        assert_eq!(route.slot_parent.as_deref(), Some("dashboard"));
+
+        let route = parse_route_path("feed/(..)photo/[id]/page.tsx");
+        assert_eq!((route.pattern.as_str(), route.route_type.as_str()), ("/photo/:id", "dynamic"));
+        let interception = route.interception.unwrap();
+        assert_eq!((interception.marker.as_str(), interception.source_pattern.as_str()), ("(..)", "/feed"));
+
+        assert_eq!(parse_route_path("@modal/(.)photo/[id]/page.tsx").pattern, "/photo/:id");
+        assert_eq!(parse_route_path("a/b/(..)(..)c/page.tsx").pattern, "/c");
+        assert_eq!(parse_route_path("a/(group)/b/(...)c/page.tsx").pattern, "/c");
+        assert_eq!(split_interception("(..)(..)photo"), Some(("(..)(..)", "photo")));
+        assert_eq!(split_interception("(marketing)"), None);
+    }
+
+    #[test]
+    fn test_normalize_route_pattern() {
+        assert_eq!(normalize_route_pattern("/blog/:id"), normalize_route_pattern("/blog/:slug"));
+        assert_eq!(normalize_route_pattern("/docs/:path*/edit"), "/docs/:param*/edit");
+        assert_ne!(normalize_route_pattern("/docs/:id"), normalize_route_pattern("/docs/:path*"));
+        assert_ne!(normalize_route_pattern("/docs/:path*"), normalize_route_pattern("/docs/:path*?"));
+    }
+
+    #[test]
+    fn test_route_conflict_keys() {
+        assert_eq!(route_conflict_keys("/docs/:id"), vec!["/docs/:param"]);
+        assert_eq!(route_conflict_keys("/docs/:slug*"), vec!["/docs/:param*"]);
+        assert_eq!(route_conflict_keys("/docs/:slug*?"), vec!["/docs/:param*?", "/docs"]);
+        assert_eq!(route_conflict_keys("/:slug*?"), vec!["/:param*?", "/"]);
+    }
+
+    #[test]
+    fn test_compare_route_specificity() {
+        let mut patterns = vec![
+            "/wiki/:page*?", "/:slug", "/docs/:path*", "/blog/:slug", "/blog",
+            "/blog/new", "/", "/a/:x/c", "/a/b/:y", "/about", "/docs",
+        ];
+        patterns.sort_by(|a, b| compare_route_specificity(a, b));
+        assert_eq!(patterns, vec![
+            "/blog/new", "/a/b/:y", "/about", "/blog", "/docs", "/a/:x/c",
+            "/blog/:slug", "/docs/:path*", "/wiki/:page*?", "/", "/:slug",
+        ]);
+    }
+
+    #[test]
+    fn test_segment_names() {
+        assert_eq!(SegmentKind::from_segment("[[...slug]]"), SegmentKind::OptionalCatchAll);
+        assert_eq!(SegmentKind::from_segment("[not-found]"), SegmentKind::ReservedInternal);
+        assert_eq!(SegmentKind::OptionalCatchAll.pattern_part("[[...slug]]"), ":slug*?");
+
+        for valid in ["blog", "my_post-2", "[id]", "[...path]", "[[...path]]", "(marketing)", "@modal", "(.)photo", "(..)(..)[id]"] {
+            assert!(is_valid_segment_name(valid), "{valid}");
+        }
+        for invalid in ["my blog", "[]", "[a.b]", "(x", "[[slug]]", "caf\u{e9}", "@", "(.)(group)"] {
+            assert!(!is_valid_segment_name(invalid), "{invalid}");
+        }
+    }
+}
@@ -1,12 +1,28 @@
 //! RSC Scanner
 //! 
 //! High-performance Rust scanner for React Server Components.
-//! Scans directories and classifies components as client or server.
+//! Scans directories in parallel (respecting `.gitignore`) and classifies
+//! components as client or server.
 
-use std::path::Path;
-use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc};
+use ignore::{
+    gitignore::{Gitignore, GitignoreBuilder},
+    WalkBuilder, WalkState,
+};
 use serde::{Serialize, Deserialize};
-use crate::has_client_directive;
+use super::cache::{default_cache_dir, CachedScanner, ScanCache, ScanDelta, SCAN_CACHE_FILE};
+use super::graph::{
+    build_module_graph, relative_path_from, ClientApiChain, GraphModule, ImportChain,
+    MarkerImportChain, ModuleGraph,
+};
+use super::routes::is_reserved_internal_route;
+use super::segment_config::SegmentConfig;
+use crate::analyzer::{
+    analyze_module, ClientApiUsage, ExportEntry, ImportRecord, MetadataIssue, ParseError,
+    SegmentConfigExport, ServerActionExport,
+};
+use crate::diagnostics::{Diagnostic, SourceSpan};
 
 /// Component type classification
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -15,7 +31,9 @@ pub enum ComponentType {
     Layout,
     Loading,
     Error,
+    Template,
     NotFound,
+    Default,  // Fallback for an unmatched parallel route slot
     Component,
     Route,  // API route
 }
@@ -27,7 +45,9 @@ impl ComponentType {
             "layout" | "root" => ComponentType::Layout,
             "loading" => ComponentType::Loading,
             "error" => ComponentType::Error,
+            "template" => ComponentType::Template,
             "not-found" => ComponentType::NotFound,
+            "default" => ComponentType::Default,
             "route" => ComponentType::Route,
             _ => ComponentType::Component,
         }
@@ -45,24 +65,104 @@ pub struct ScannedComponent {
     pub is_client: bool,
     /// Line number of the directive (0 if not client)
     pub directive_line: usize,
+    /// Has a file-level 'use server' directive
+    pub is_server: bool,
+    /// Exported server actions
+    pub server_actions: Vec<ServerActionExport>,
     /// Component type (page, layout, etc.)
     pub component_type: ComponentType,
     /// Exported names from this module
     pub exports: Vec<String>,
+    /// Full export table, including re-exports and their sources
+    pub export_table: Vec<ExportEntry>,
+    /// Runtime imports of this module
+    pub imports: Vec<ImportRecord>,
     /// Client hooks/APIs used (for error detection)
     pub client_hooks_used: Vec<String>,
+    /// Location of each client hook/API call and event handler prop
+    pub client_api_usages: Vec<ClientApiUsage>,
     /// Has metadata export
     pub has_metadata: bool,
+    /// `metadata` is exported as a primitive instead of an object
+    pub metadata_is_primitive: bool,
+    /// Statically evaluated `metadata` object
+    pub static_metadata: Option<serde_json::Value>,
+    /// Parts of `metadata` that need `generateMetadata` instead
+    pub metadata_issues: Vec<MetadataIssue>,
     /// Has generateMetadata function
     pub has_generate_metadata: bool,
+    /// Has generateStaticParams function
+    pub has_generate_static_params: bool,
+    /// Route segment config exports (`dynamic`, `revalidate`, ...)
+    pub segment_config: Vec<SegmentConfigExport>,
 }
 
+/// What a scan error is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScanErrorKind {
+    /// The module could not be parsed
+    ParseError,
+    /// Both 'use client' and 'use server' directives
+    DirectiveConflict,
+    /// Inline 'use server' function in a client component
+    ServerActionInClientComponent,
+    /// Client-only hooks/APIs used (directly or through imports) by a server module
+    ClientApiInServerComponent,
+    /// A `server-only` module is reachable from a 'use client' module
+    ServerOnlyInClient,
+    /// A `client-only` module is reachable from a server entry
+    ClientOnlyInServer,
+    /// A route segment config export has an invalid or non-literal value
+    InvalidSegmentConfig,
+}
+
+impl ScanErrorKind {
+    /// Stable diagnostic code
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ParseError => "PARSE_ERROR",
+            Self::DirectiveConflict => "DIRECTIVE_CONFLICT",
+            Self::ServerActionInClientComponent => "SERVER_ACTION_IN_CLIENT_COMPONENT",
+            Self::ClientApiInServerComponent => "CLIENT_API_IN_SERVER_COMPONENT",
+            Self::ServerOnlyInClient => "SERVER_ONLY_IN_CLIENT",
+            Self::ClientOnlyInServer => "CLIENT_ONLY_IN_SERVER",
+            Self::InvalidSegmentConfig => "INVALID_SEGMENT_CONFIG",
+        }
+    }
+}
+
+/// Diagnostic code for `metadata` properties that need evaluation
+pub const NON_STATIC_METADATA: &str = "NON_STATIC_METADATA";
+
 /// Error when using client features in server component
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerComponentError {
+    pub kind: ScanErrorKind,
     pub file: String,
     pub message: String,
     pub hooks: Vec<String>,
+    /// Location of each offending hook/API call or event handler prop
+    /// (in the last module of `import_chain` when reached through imports)
+    pub usages: Vec<ClientApiUsage>,
+    /// Import chain from this file to the module using client-only APIs
+    /// (empty when the file uses them directly)
+    pub import_chain: Vec<String>,
+    /// Where the error is reported
+    pub primary_span: Option<SourceSpan>,
+    /// Related locations (other usages, the marker import...)
+    pub secondary_spans: Vec<SourceSpan>,
+}
+
+impl ServerComponentError {
+    /// Convert to a diagnostic, with a code frame read from the primary span's file
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let mut diagnostic = Diagnostic::error(self.kind.code(), self.message.clone())
+            .with_secondary_spans(self.secondary_spans.iter().cloned());
+        if let Some(span) = &self.primary_span {
+            diagnostic = diagnostic.with_primary_span(span.clone()).with_code_frame_from_disk();
+        }
+        diagnostic
+    }
 }
 
 /// Result of scanning the app directory
@@ -73,209 +173,509 @@ pub struct ScanResult {
     pub pages: Vec<ScannedComponent>,
     pub layouts: Vec<ScannedComponent>,
     pub api_routes: Vec<ScannedComponent>,
+    /// Modules exporting at least one server action, including imported
+    /// modules outside the app directory
+    pub server_action_modules: Vec<ScannedComponent>,
     pub errors: Vec<ServerComponentError>,
+    /// `errors` as diagnostics with stable codes and code frames, followed
+    /// by warnings such as `metadata` that can't be extracted statically
+    pub diagnostics: Vec<Diagnostic>,
+    /// Modules outside the app directory reached through imports
+    pub external_modules: Vec<ScannedComponent>,
+    /// Import graph over app and external modules
+    pub module_graph: ModuleGraph,
     /// Total files scanned
     pub total_files: usize,
     /// Time taken in milliseconds
     pub scan_time_ms: u64,
 }
 
-/// Client-only hooks that require 'use client' directive
-const CLIENT_HOOKS: &[&str] = &[
-    "useState", "useEffect", "useLayoutEffect", "useReducer", "useRef",
-    "useImperativeHandle", "useCallback", "useMemo", "useContext",
-    "useDebugValue", "useDeferredValue", "useTransition", "useId",
-    "useSyncExternalStore", "useInsertionEffect",
-];
-
-/// Client-only APIs
-const CLIENT_APIS: &[&str] = &[
-    "createContext", "forwardRef", "memo", "lazy", "startTransition",
-    "useFormStatus", "useFormState", "useOptimistic",
-];
-
-/// Detect client hooks used in source
-fn detect_client_hooks(source: &str) -> Vec<String> {
-    let mut used = Vec::new();
-    
-    for hook in CLIENT_HOOKS {
-        // Match hook usage: useState( or useState<
-        if source.contains(&format!("{}(", hook)) || source.contains(&format!("{}<", hook)) {
-            used.push(hook.to_string());
+/// Extensions of files the scanner analyzes
+pub const SCANNED_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx"];
+
+/// Options for scanning the app directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanOptions {
+    /// Gitignore-style patterns (relative to the app directory) to skip
+    pub ignore: Vec<String>,
+    /// Respect `.gitignore` files in and above the app directory
+    pub respect_gitignore: bool,
+    /// Reuse analysis from the persistent scan cache
+    pub cache: bool,
+    /// Cache directory (defaults to `.vista/cache` in the project root)
+    pub cache_dir: Option<String>,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            ignore: vec!["node_modules".to_string()],
+            respect_gitignore: true,
+            cache: true,
+            cache_dir: None,
         }
     }
-    
-    for api in CLIENT_APIS {
-        if source.contains(&format!("{}(", api)) || source.contains(&format!("{}<", api)) {
-            used.push(api.to_string());
+}
+
+/// Compiled file filter shared by the scanner and file watchers.
+/// Hidden directories are always skipped.
+pub struct ScanFilter {
+    app_dir: PathBuf,
+    ignore: Gitignore,
+    /// `.gitignore` files in and above the app directory
+    gitignores: Vec<Gitignore>,
+    /// Extensions of accepted files (without the dot)
+    extensions: Vec<String>,
+}
+
+impl ScanFilter {
+    pub fn new(app_dir: &Path, options: &ScanOptions) -> Self {
+        let mut builder = GitignoreBuilder::new(app_dir);
+        for pattern in &options.ignore {
+            // Invalid patterns are skipped rather than failing the whole scan
+            let _ = builder.add_line(None, pattern);
+        }
+        let gitignores = if options.respect_gitignore {
+            app_dir
+                .ancestors()
+                .map(|dir| dir.join(".gitignore"))
+                .filter(|file| file.is_file())
+                .map(|file| Gitignore::new(file).0)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Self {
+            app_dir: app_dir.to_path_buf(),
+            ignore: builder.build().unwrap_or_else(|_| Gitignore::empty()),
+            gitignores,
+            extensions: SCANNED_EXTENSIONS.iter().map(|ext| ext.to_string()).collect(),
         }
     }
     
-    // Check for event handlers
-    if source.contains("onClick=") || source.contains("onChange=") || 
-       source.contains("onSubmit=") || source.contains("onFocus=") {
-        used.push("event handlers".to_string());
+    /// Accept files with `extensions` instead of the scanned script extensions
+    pub fn with_extensions(mut self, extensions: &[String]) -> Self {
+        self.extensions = extensions.to_vec();
+        self
     }
     
-    used
+    /// Whether a path (or one of its parent directories) is excluded
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.app_dir) else {
+            return false;
+        };
+        let mut dirs: Vec<_> = relative.components().collect();
+        if !is_dir {
+            dirs.pop();
+        }
+        let hidden_dir = dirs.iter().any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+        hidden_dir
+            || self.ignore.matched_path_or_any_parents(relative, is_dir).is_ignore()
+            || self
+                .gitignores
+                .iter()
+                .any(|gitignore| gitignore.matched_path_or_any_parents(path, is_dir).is_ignore())
+    }
+    
+    /// Whether a file has an accepted extension and isn't ignored
+    pub fn accepts_file(&self, path: &Path) -> bool {
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        self.extensions.iter().any(|e| e == ext) && !self.is_ignored(path, false)
+    }
 }
 
-/// Extract export names from source
-fn extract_exports(source: &str) -> Vec<String> {
-    let mut exports = Vec::new();
+/// Analyze a single file's source. Returns the component and its parse error, if any.
+/// Files outside `app_dir` get a `../` relative path and are always plain components.
+pub(crate) fn scan_source(
+    path: &Path,
+    app_dir: &Path,
+    source: &str,
+) -> Option<(ScannedComponent, Option<ParseError>)> {
+    let file_stem = path.file_stem()?.to_str()?;
+    let (relative_path, component_type) = match path.strip_prefix(app_dir) {
+        Ok(relative) => (
+            relative.to_string_lossy().to_string().replace('\\', "/"),
+            ComponentType::from_filename(file_stem),
+        ),
+        Err(_) => (relative_path_from(path, app_dir), ComponentType::Component),
+    };
+    
+    let analysis = analyze_module(source, &path.to_string_lossy());
     
-    // export default
-    if source.contains("export default") {
-        exports.push("default".to_string());
+    let component = ScannedComponent {
+        absolute_path: path.to_string_lossy().to_string(),
+        relative_path,
+        is_client: analysis.is_client,
+        directive_line: analysis.directive_line,
+        is_server: analysis.is_server,
+        server_actions: analysis.server_actions,
+        component_type,
+        exports: analysis.exports,
+        export_table: analysis.export_table,
+        imports: analysis.imports,
+        client_hooks_used: analysis.client_hooks_used,
+        client_api_usages: analysis.client_api_usages,
+        has_metadata: analysis.has_metadata,
+        metadata_is_primitive: analysis.metadata_is_primitive,
+        static_metadata: analysis.static_metadata,
+        metadata_issues: analysis.metadata_issues,
+        has_generate_metadata: analysis.has_generate_metadata,
+        has_generate_static_params: analysis.has_generate_static_params,
+        segment_config: analysis.segment_config,
+    };
+    
+    Some((component, analysis.parse_error))
+}
+
+/// Report parse failures and directive misuse for a scanned module
+fn check_component(
+    component: &ScannedComponent,
+    parse_error: Option<ParseError>,
+    errors: &mut Vec<ServerComponentError>,
+) {
+    if let Some(parse_error) = parse_error {
+        errors.push(parse_failure(component, &parse_error));
     }
     
-    // export function Name or export const Name
-    for line in source.lines() {
-        let trimmed = line.trim();
+    let directive_span = || component_span(component, component.directive_line, 1);
+    
+    if component.is_client && component.is_server {
+        errors.push(ServerComponentError {
+            kind: ScanErrorKind::DirectiveConflict,
+            file: component.relative_path.clone(),
+            message: "A module cannot have both 'use client' and 'use server' directives.".to_string(),
+            hooks: vec![],
+            usages: vec![],
+            import_chain: vec![],
+            primary_span: Some(directive_span()),
+            secondary_spans: vec![],
+        });
+    } else if component.is_client && !component.server_actions.is_empty() {
+        let mut actions = component.server_actions.iter().filter(|a| a.inline);
+        let primary = actions
+            .next()
+            .map(|action| component_span(component, action.line, 1).with_label(&action.export_name));
+        let mut secondary_spans: Vec<SourceSpan> = actions
+            .map(|action| component_span(component, action.line, 1).with_label(&action.export_name))
+            .collect();
+        secondary_spans.push(directive_span().with_label("'use client'"));
         
-        if trimmed.starts_with("export function ") || trimmed.starts_with("export async function ") {
-            if let Some(name) = extract_identifier(trimmed, "function ") {
-                exports.push(name);
-            }
-        } else if trimmed.starts_with("export const ") {
-            if let Some(name) = extract_identifier(trimmed, "const ") {
-                exports.push(name);
-            }
-        } else if trimmed.starts_with("export class ") {
-            if let Some(name) = extract_identifier(trimmed, "class ") {
-                exports.push(name);
-            }
+        errors.push(ServerComponentError {
+            kind: ScanErrorKind::ServerActionInClientComponent,
+            file: component.relative_path.clone(),
+            message: "Inline 'use server' actions are not allowed in Client Components. Move them to a separate 'use server' module.".to_string(),
+            hooks: vec![],
+            usages: vec![],
+            import_chain: vec![],
+            primary_span: primary.or_else(|| Some(directive_span())),
+            secondary_spans,
+        });
+    }
+    
+    // Segment config is only read from route entry files
+    if matches!(
+        component.component_type,
+        ComponentType::Page | ComponentType::Layout | ComponentType::Route
+    ) {
+        let (_, issues) = SegmentConfig::parse(&component.segment_config);
+        for issue in issues {
+            errors.push(ServerComponentError {
+                kind: ScanErrorKind::InvalidSegmentConfig,
+                file: component.relative_path.clone(),
+                message: issue.message,
+                hooks: vec![],
+                usages: vec![],
+                import_chain: vec![],
+                primary_span: Some(component_span(component, issue.line, issue.column).with_label(&issue.name)),
+                secondary_spans: vec![],
+            });
         }
     }
     
-    exports
+    // Check for server component errors
+    if !component.is_client && !component.client_hooks_used.is_empty() {
+        let mut spans = usage_spans(
+            &component.absolute_path,
+            &component.relative_path,
+            &component.client_api_usages,
+        );
+        let primary_span = (!spans.is_empty()).then(|| spans.remove(0));
+        
+        errors.push(ServerComponentError {
+            kind: ScanErrorKind::ClientApiInServerComponent,
+            file: component.relative_path.clone(),
+            message: format!(
+                "Using {} in a Server Component. Add 'use client' to make it a Client Component.",
+                component.client_hooks_used.join(", ")
+            ),
+            hooks: component.client_hooks_used.clone(),
+            usages: component.client_api_usages.clone(),
+            import_chain: vec![],
+            primary_span,
+            secondary_spans: spans,
+        });
+    }
 }
 
-/// Helper to extract identifier after a keyword
-fn extract_identifier(line: &str, after: &str) -> Option<String> {
-    if let Some(pos) = line.find(after) {
-        let rest = &line[pos + after.len()..];
-        let name: String = rest.chars()
-            .take_while(|c| c.is_alphanumeric() || *c == '_')
-            .collect();
-        if !name.is_empty() {
-            return Some(name);
-        }
-    }
-    None
+/// Warnings for `metadata` properties that can't be extracted statically
+fn metadata_diagnostics(component: &ScannedComponent) -> Vec<Diagnostic> {
+    component.metadata_issues.iter().map(|issue| {
+        Diagnostic::warning(
+            NON_STATIC_METADATA,
+            format!(
+                "`{}` is not a literal and is left out of the static metadata, \
+                 along with any array that contains it. Use `generateMetadata` for values computed at runtime.",
+                issue.path
+            ),
+        )
+        .with_primary_span(component_span(component, issue.line, issue.column))
+        .with_code_frame_from_disk()
+    }).collect()
 }
 
-/// Check for metadata exports
-fn has_metadata_export(source: &str) -> bool {
-    source.contains("export const metadata") || source.contains("export let metadata")
+fn component_span(component: &ScannedComponent, line: usize, column: usize) -> SourceSpan {
+    SourceSpan::new(&component.absolute_path, &component.relative_path, line, column)
 }
 
-fn has_generate_metadata(source: &str) -> bool {
-    source.contains("export function generateMetadata") ||
-    source.contains("export async function generateMetadata") ||
-    source.contains("export const generateMetadata")
+/// One span per client API usage, labelled with the API name
+fn usage_spans(path: &str, relative_path: &str, usages: &[ClientApiUsage]) -> Vec<SourceSpan> {
+    usages
+        .iter()
+        .map(|usage| SourceSpan::new(path, relative_path, usage.line, usage.column).with_label(&usage.name))
+        .collect()
 }
 
-fn is_reserved_internal_route(relative_path: &str) -> bool {
-    relative_path
-        .split(['/', '\\'])
-        .any(|segment| segment == "[not-found]")
+/// Span of the import that starts `chain`, in its first module
+fn chain_import_span(chain: &ImportChain, label: &str) -> SourceSpan {
+    let relative_path = chain.modules.first().map(String::as_str).unwrap_or_default();
+    SourceSpan::new(&chain.root_path, relative_path, chain.import_line, 1).with_label(label)
 }
 
-/// Scan a single file
-fn scan_file(path: &Path, app_dir: &Path) -> Option<ScannedComponent> {
-    let source = fs::read_to_string(path).ok()?;
-    let relative_path = path.strip_prefix(app_dir).ok()?;
+/// Error for a server module that reaches client-only APIs through its imports
+fn transitive_client_api_error(component: &ScannedComponent, chain: ClientApiChain) -> ServerComponentError {
+    let import_chain = chain.chain.modules.clone();
+    let offender = import_chain.last().cloned().unwrap_or_default();
     
-    let file_stem = path.file_stem()?.to_str()?;
-    let component_type = ComponentType::from_filename(file_stem);
-    
-    let is_client = has_client_directive(&source);
-    let directive_line = if is_client {
-        source.lines()
-            .enumerate()
-            .find(|(_, line)| {
-                let trimmed = line.trim();
-                trimmed.starts_with("'use client'") || trimmed.starts_with("\"use client\"")
-            })
-            .map(|(i, _)| i + 1)
-            .unwrap_or(1)
+    ServerComponentError {
+        kind: ScanErrorKind::ClientApiInServerComponent,
+        file: component.relative_path.clone(),
+        message: format!(
+            "Using {} in a Server Component through {} -> {}. Add 'use client' to {} or to a module that imports it.",
+            chain.hooks.join(", "),
+            import_chain.join(" -> "),
+            chain.hooks.join(", "),
+            offender,
+        ),
+        primary_span: Some(chain_import_span(&chain.chain, &format!("imports {offender}"))),
+        secondary_spans: usage_spans(&chain.chain.target_path, &offender, &chain.usages),
+        hooks: chain.hooks,
+        usages: chain.usages,
+        import_chain,
+    }
+}
+
+/// Modules rendered on the server as route entries (pages, layouts, routes...).
+/// Plain modules are only checked through the entries that import them, since
+/// a helper imported only by client components never runs on the server.
+fn server_entries(components: &[ScannedComponent]) -> impl Iterator<Item = &ScannedComponent> {
+    components
+        .iter()
+        .filter(|c| !c.is_client && c.component_type != ComponentType::Component)
+}
+
+/// Enforce the `server-only` / `client-only` marker packages: a `server-only`
+/// module must not be reachable from a 'use client' module, and a `client-only`
+/// module must not be reachable from a server entry (page, layout, route...).
+fn check_marker_imports(
+    graph: &ModuleGraph,
+    components: &[ScannedComponent],
+    errors: &mut Vec<ServerComponentError>,
+) {
+    let mut client_roots: Vec<&GraphModule> =
+        graph.modules.values().filter(|m| m.is_client).collect();
+    client_roots.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+    let client_roots: Vec<String> = client_roots.iter().map(|m| m.absolute_path.clone()).collect();
+    
+    // Everything a client module imports ends up in the client bundle, except
+    // 'use server' modules, which are replaced by action references
+    for chain in graph.marker_import_chains(&client_roots, "server-only", |m| !m.is_server) {
+        errors.push(marker_import_error(
+            ScanErrorKind::ServerOnlyInClient,
+            chain,
+            "server-only",
+            "imports 'server-only' but is included in the client bundle through",
+            "Move the client-side usage behind a Server Component or a server action.",
+        ));
+    }
+    
+    let server_roots: Vec<String> = server_entries(components)
+        .map(|c| c.absolute_path.clone())
+        .collect();
+    
+    for chain in graph.marker_import_chains(&server_roots, "client-only", |m| !m.is_client) {
+        errors.push(marker_import_error(
+            ScanErrorKind::ClientOnlyInServer,
+            chain,
+            "client-only",
+            "imports 'client-only' but is rendered on the server through",
+            "Add 'use client' to the module that imports it.",
+        ));
+    }
+}
+
+fn marker_import_error(
+    kind: ScanErrorKind,
+    chain: MarkerImportChain,
+    marker: &str,
+    problem: &str,
+    hint: &str,
+) -> ServerComponentError {
+    let import_chain = chain.chain.modules.clone();
+    let offender = import_chain.last().cloned().unwrap_or_default();
+    let marker_span = SourceSpan::new(&chain.chain.target_path, &offender, chain.marker_line, 1)
+        .with_label(format!("'{marker}' imported here"));
+    let (primary_span, secondary_spans) = if import_chain.len() > 1 {
+        (chain_import_span(&chain.chain, &format!("imports {offender}")), vec![marker_span])
     } else {
-        0
+        (marker_span, vec![])
     };
     
-    Some(ScannedComponent {
-        absolute_path: path.to_string_lossy().to_string(),
-        relative_path: relative_path.to_string_lossy().to_string().replace('\\', "/"),
-        is_client,
-        directive_line,
-        component_type,
-        exports: extract_exports(&source),
-        client_hooks_used: detect_client_hooks(&source),
-        has_metadata: has_metadata_export(&source),
-        has_generate_metadata: has_generate_metadata(&source),
-    })
+    ServerComponentError {
+        kind,
+        file: import_chain.first().cloned().unwrap_or_default(),
+        message: format!("{offender} {problem} {}. {hint}", import_chain.join(" -> ")),
+        hooks: vec![],
+        usages: vec![],
+        import_chain,
+        primary_span: Some(primary_span),
+        secondary_spans,
+    }
 }
 
-/// Scan directory recursively
-fn scan_directory_recursive(
-    dir: &Path,
+/// Error for a module that failed to parse
+pub(crate) fn parse_failure(component: &ScannedComponent, error: &ParseError) -> ServerComponentError {
+    let relative_path = &component.relative_path;
+    ServerComponentError {
+        kind: ScanErrorKind::ParseError,
+        file: relative_path.clone(),
+        message: format!("Failed to parse {relative_path}:{error}"),
+        hooks: vec![],
+        usages: vec![],
+        import_chain: vec![],
+        primary_span: Some(component_span(component, error.line, error.column)),
+        secondary_spans: vec![],
+    }
+}
+
+/// Walk the app directory in parallel and scan every matching file.
+/// Results are sorted by relative path so output doesn't depend on thread timing.
+fn walk_app_directory(
     app_dir: &Path,
-    components: &mut Vec<ScannedComponent>,
-    errors: &mut Vec<ServerComponentError>,
-) {
-    let entries = match fs::read_dir(dir) {
-        Ok(e) => e,
-        Err(_) => return,
-    };
+    options: &ScanOptions,
+    scanner: &CachedScanner,
+) -> Vec<(ScannedComponent, Option<ParseError>)> {
+    let filter = Arc::new(ScanFilter::new(app_dir, options));
     
-    for entry in entries.flatten() {
-        let path = entry.path();
-        let file_name = entry.file_name().to_string_lossy().to_string();
-        
-        if path.is_dir() {
-            // Skip hidden directories and node_modules
-            if !file_name.starts_with('.') && file_name != "node_modules" {
-                scan_directory_recursive(&path, app_dir, components, errors);
-            }
-        } else if path.is_file() {
-            // Only process TypeScript/JavaScript files
-            let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-            if !["ts", "tsx", "js", "jsx"].contains(&ext) {
-                continue;
-            }
-            
-            if let Some(component) = scan_file(&path, app_dir) {
-                // Check for server component errors
-                if !component.is_client && !component.client_hooks_used.is_empty() {
-                    errors.push(ServerComponentError {
-                        file: component.relative_path.clone(),
-                        message: format!(
-                            "Using {} in a Server Component. Add 'use client' to make it a Client Component.",
-                            component.client_hooks_used.join(", ")
-                        ),
-                        hooks: component.client_hooks_used.clone(),
-                    });
+    let mut builder = WalkBuilder::new(app_dir);
+    builder
+        .hidden(false)
+        .ignore(false)
+        .parents(options.respect_gitignore)
+        .git_ignore(options.respect_gitignore)
+        .git_exclude(options.respect_gitignore)
+        .git_global(false)
+        .require_git(false)
+        .follow_links(false);
+    
+    let entry_filter = Arc::clone(&filter);
+    builder.filter_entry(move |entry| {
+        let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+        entry.depth() == 0 || !entry_filter.is_ignored(entry.path(), is_dir)
+    });
+    
+    let (tx, rx) = mpsc::channel();
+    builder.build_parallel().run(|| {
+        let tx = tx.clone();
+        let filter = Arc::clone(&filter);
+        Box::new(move |entry| {
+            let Ok(entry) = entry else {
+                return WalkState::Continue;
+            };
+            let is_file = entry.file_type().is_some_and(|t| t.is_file());
+            if is_file && filter.accepts_file(entry.path()) {
+                if let Some(scanned) = scanner.scan(entry.path(), &filter.app_dir) {
+                    let _ = tx.send(scanned);
                 }
-                
-                components.push(component);
             }
-        }
-    }
+            WalkState::Continue
+        })
+    });
+    drop(tx);
+    
+    let mut scanned: Vec<_> = rx.into_iter().collect();
+    scanned.sort_by(|(a, _), (b, _)| a.relative_path.cmp(&b.relative_path));
+    scanned
 }
 
 /// Scan the app directory and classify all components
 pub fn scan_app_directory(app_dir: &str) -> ScanResult {
+    scan_app_directory_with_options(app_dir, &ScanOptions::default())
+}
+
+/// Scan the app directory with custom ignore rules
+pub fn scan_app_directory_with_options(app_dir: &str, options: &ScanOptions) -> ScanResult {
+    scan_app_directory_incremental(app_dir, options).0
+}
+
+/// Scan the app directory through the persistent scan cache, returning the
+/// result and which files were added, changed or removed since the last scan
+pub fn scan_app_directory_incremental(app_dir: &str, options: &ScanOptions) -> (ScanResult, ScanDelta) {
     let start = std::time::Instant::now();
     let app_path = Path::new(app_dir);
     
+    let cache_file = options.cache.then(|| {
+        options
+            .cache_dir
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| default_cache_dir(app_path))
+            .join(SCAN_CACHE_FILE)
+    });
+    let previous = cache_file
+        .as_deref()
+        .map(ScanCache::load)
+        .unwrap_or_default();
+    let scanner = CachedScanner::new(previous);
+    
     let mut components = Vec::new();
     let mut errors = Vec::new();
     
-    scan_directory_recursive(app_path, app_path, &mut components, &mut errors);
+    for (component, parse_error) in walk_app_directory(app_path, options, &scanner) {
+        check_component(&component, parse_error, &mut errors);
+        components.push(component);
+    }
     
     let total_files = components.len();
     
+    let (module_graph, external_modules) = build_module_graph(app_path, &components, &scanner, &mut errors);
+    
+    // Client-only APIs reached from a server entry through its imports fail at
+    // render time. Each entry reports every offending module once, through
+    // the shortest chain from the entry.
+    for component in server_entries(&components) {
+        for chain in module_graph.client_api_chains(&component.absolute_path) {
+            errors.push(transitive_client_api_error(component, chain));
+        }
+    }
+    
+    check_marker_imports(&module_graph, &components, &mut errors);
+    
+    let (cache, delta) = scanner.finish();
+    if let Some(cache_file) = &cache_file {
+        // The cache is only an optimization; failing to persist it isn't an error
+        let _ = cache.save(cache_file);
+    }
+    
     // Classify components
     let client_components: Vec<_> = components.iter()
         .filter(|c| c.is_client)
@@ -302,23 +702,41 @@ pub fn scan_app_directory(app_dir: &str) -> ScanResult {
         .cloned()
         .collect();
     
+    // 'use server' modules are usually kept outside app/ (e.g. `lib/actions.ts`)
+    // and only reached through imports
+    let server_action_modules: Vec<_> = components.iter()
+        .chain(&external_modules)
+        .filter(|c| !c.is_client && !c.server_actions.is_empty())
+        .cloned()
+        .collect();
+    
+    let mut diagnostics: Vec<Diagnostic> = errors.iter().map(ServerComponentError::to_diagnostic).collect();
+    diagnostics.extend(components.iter().flat_map(metadata_diagnostics));
+    
     let scan_time_ms = start.elapsed().as_millis() as u64;
     
-    ScanResult {
+    let result = ScanResult {
         client_components,
         server_components,
         pages,
         layouts,
         api_routes,
+        server_action_modules,
         errors,
+        diagnostics,
+        external_modules,
+        module_graph,
         total_files,
         scan_time_ms,
-    }
+    };
+    (result, delta)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::diagnostics::Severity;
+    use std::fs;
     
     #[test]
     fn test_detect_client_hooks() {
@@ -332,7 +750,7 @@ mod tests {
             }
         "#;
         
-        let hooks = detect_client_hooks(source);
+        let hooks = analyze_module(source, "counter.tsx").client_hooks_used;
         assert!(hooks.contains(&"useState".to_string()));
         assert!(hooks.contains(&"useEffect".to_string()));
         assert!(hooks.contains(&"event handlers".to_string()));
@@ -346,7 +764,7 @@ mod tests {
             export default function Page() {}
         "#;
         
-        let exports = extract_exports(source);
+        let exports = analyze_module(source, "page.tsx").exports;
         assert!(exports.contains(&"default".to_string()));
         assert!(exports.contains(&"metadata".to_string()));
         assert!(exports.contains(&"generateMetadata".to_string()));
@@ -357,12 +775,206 @@ mod tests {
         assert_eq!(ComponentType::from_filename("page"), ComponentType::Page);
         assert_eq!(ComponentType::from_filename("layout"), ComponentType::Layout);
         assert_eq!(ComponentType::from_filename("loading"), ComponentType::Loading);
+        assert_eq!(ComponentType::from_filename("default"), ComponentType::Default);
+        assert_eq!(ComponentType::from_filename("template"), ComponentType::Template);
         assert_eq!(ComponentType::from_filename("Button"), ComponentType::Component);
     }
 
     #[test]
-    fn test_reserved_internal_route_detection() {
-        assert!(is_reserved_internal_route("docs/[not-found]/page.tsx"));
-        assert!(!is_reserved_internal_route("docs/[slug]/page.tsx"));
+    fn test_scan_respects_ignore_rules_and_is_sorted() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        for file in [
+            "page.tsx",
+            "b/page.tsx",
+            "a/page.tsx",
+            "a/z/page.tsx",
+            "generated/page.tsx",
+            "drafts/page.tsx",
+            "node_modules/pkg/index.js",
+            ".cache/page.tsx",
+            "styles.css",
+        ] {
+            let path = app.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "export default function Page() {}").unwrap();
+        }
+        fs::write(dir.path().join(".gitignore"), "generated/\n").unwrap();
+        
+        let options = ScanOptions {
+            ignore: vec!["node_modules".to_string(), "drafts/".to_string()],
+            ..ScanOptions::default()
+        };
+        let app_dir = app.to_string_lossy().to_string();
+        let result = scan_app_directory_with_options(&app_dir, &options);
+        let pages: Vec<_> = result.pages.iter().map(|p| p.relative_path.as_str()).collect();
+        assert_eq!(pages, vec!["a/page.tsx", "a/z/page.tsx", "b/page.tsx", "page.tsx"]);
+        assert_eq!(result.total_files, 4);
+        
+        let again = scan_app_directory_with_options(&app_dir, &options);
+        assert_eq!(
+            serde_json::to_string(&result.pages).unwrap(),
+            serde_json::to_string(&again.pages).unwrap()
+        );
+        
+        let unfiltered = ScanOptions {
+            ignore: vec![],
+            respect_gitignore: false,
+            ..ScanOptions::default()
+        };
+        assert_eq!(scan_app_directory_with_options(&app_dir, &unfiltered).total_files, 7);
+    }
+
+    #[test]
+    fn test_transitive_client_api_errors_report_import_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |relative: &str, contents: &str| {
+            let path = dir.path().join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        };
+        write("app/page.tsx", "import { useCounter } from '../lib/hooks';\nimport Widget from './widget';\nexport default function Page() {}");
+        write("app/widget.tsx", "'use client';\nimport { useCounter } from '../lib/hooks';\nexport default function Widget() {}");
+        write("lib/hooks.ts", "import { useState } from 'react';\nexport function useCounter() {\n  return useState(0);\n}");
+        
+        let app_dir = dir.path().join("app").to_string_lossy().to_string();
+        let result = scan_app_directory(&app_dir);
+        
+        assert_eq!(result.errors.len(), 1);
+        let error = &result.errors[0];
+        assert_eq!(error.file, "page.tsx");
+        assert_eq!(error.import_chain, vec!["page.tsx", "../lib/hooks.ts"]);
+        assert_eq!(error.hooks, vec!["useState"]);
+        assert_eq!(error.usages[0].line, 3);
+        assert!(error.message.contains("page.tsx -> ../lib/hooks.ts -> useState"));
+        
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.code, "CLIENT_API_IN_SERVER_COMPONENT");
+        let primary = diagnostic.primary_span.as_ref().unwrap();
+        assert_eq!((primary.relative_path.as_str(), primary.line), ("page.tsx", 1));
+        let secondary = &diagnostic.secondary_spans[0];
+        assert_eq!((secondary.relative_path.as_str(), secondary.line, secondary.column), ("../lib/hooks.ts", 3, 10));
+        assert!(diagnostic.code_frame.as_deref().unwrap().starts_with("> 1 | import { useCounter }"));
+    }
+
+    #[test]
+    fn test_transitive_client_api_errors_start_from_server_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |relative: &str, contents: &str| {
+            let path = dir.path().join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        };
+        write("app/page.tsx", "import { format } from './helpers';\nexport default function Page() {}");
+        write("app/layout.tsx", "import { useCounter } from '../lib/hooks';\nimport Widget from './widget';\nexport default function Layout() {}");
+        write("app/helpers.ts", "import { useCounter } from '../lib/hooks';\nexport const format = () => useCounter;");
+        write("app/widget.tsx", "'use client';\nimport { count } from './widget-state';\nexport default function Widget() {}");
+        write("app/widget-state.ts", "import { useCounter } from '../lib/hooks';\nexport const count = () => useCounter();");
+        write("lib/hooks.ts", "import { useState } from 'react';\nexport function useCounter() {\n  return useState(0);\n}");
+        
+        let app_dir = dir.path().join("app").to_string_lossy().to_string();
+        let result = scan_app_directory(&app_dir);
+        
+        let chains: Vec<_> = result.errors.iter().map(|e| (e.file.as_str(), e.import_chain.clone())).collect();
+        assert_eq!(chains, vec![
+            ("layout.tsx", vec!["layout.tsx".to_string(), "../lib/hooks.ts".to_string()]),
+            ("page.tsx", vec!["page.tsx".to_string(), "helpers.ts".to_string(), "../lib/hooks.ts".to_string()]),
+        ]);
+        assert!(result.errors[1].message.contains("page.tsx -> helpers.ts -> ../lib/hooks.ts -> useState"));
+    }
+
+    #[test]
+    fn test_server_action_modules_include_imported_modules() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |relative: &str, contents: &str| {
+            let path = dir.path().join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        };
+        write("app/page.tsx", "import Form from './form';\nexport default function Page() {}");
+        write("app/form.tsx", "'use client';\nimport { save } from '../lib/actions';\nexport default function Form() {}");
+        write("lib/actions.ts", "'use server';\nexport async function save() {}");
+        
+        let app_dir = dir.path().join("app").to_string_lossy().to_string();
+        let result = scan_app_directory(&app_dir);
+        
+        let modules: Vec<_> = result.server_action_modules.iter().map(|m| m.relative_path.as_str()).collect();
+        assert_eq!(modules, vec!["../lib/actions.ts"]);
+        let manifest = crate::rsc::generate_server_action_manifest_from_scan(&result, "test");
+        let action = manifest.actions.values().next().unwrap();
+        assert_eq!((action.path.as_str(), action.export_name.as_str()), ("../lib/actions.ts", "save"));
+    }
+
+    #[test]
+    fn test_marker_import_poisoning() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |relative: &str, contents: &str| {
+            let path = dir.path().join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        };
+        write("app/page.tsx", "import Chart from './chart';\nimport { db } from '../lib/db';\nimport { track } from '../lib/analytics';\nexport default function Page() {}");
+        write("app/chart.tsx", "'use client';\nimport { format } from '../lib/format';\nimport { save } from './actions';\nexport default function Chart() {}");
+        write("app/actions.ts", "'use server';\nimport { db } from '../lib/db';\nexport async function save() {}");
+        write("lib/format.ts", "import { db } from './db';\nexport const format = () => db;");
+        write("lib/db.ts", "import 'server-only';\nexport const db = {};");
+        write("lib/analytics.ts", "import 'client-only';\nexport const track = () => {};");
+        
+        let app_dir = dir.path().join("app").to_string_lossy().to_string();
+        let result = scan_app_directory(&app_dir);
+        
+        let summary: Vec<_> = result.errors.iter().map(|e| (e.kind, e.import_chain.clone())).collect();
+        assert_eq!(summary, vec![
+            (ScanErrorKind::ServerOnlyInClient, vec!["chart.tsx".to_string(), "../lib/format.ts".to_string(), "../lib/db.ts".to_string()]),
+            (ScanErrorKind::ClientOnlyInServer, vec!["page.tsx".to_string(), "../lib/analytics.ts".to_string()]),
+        ]);
+        assert_eq!(result.errors[0].file, "chart.tsx");
+    }
+
+    #[test]
+    fn test_non_static_metadata_warnings() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        fs::create_dir_all(&app).unwrap();
+        fs::write(
+            app.join("layout.tsx"),
+            "export const metadata = {\n  title: 'Home',\n  description: getDescription(),\n};\nexport default function Layout() {}",
+        ).unwrap();
+        
+        let result = scan_app_directory(&app.to_string_lossy());
+        assert!(result.errors.is_empty());
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!((diagnostic.code.as_str(), diagnostic.severity), (NON_STATIC_METADATA, Severity::Warning));
+        assert!(diagnostic.message.contains("`metadata.description`"));
+        assert_eq!(diagnostic.primary_span.as_ref().unwrap().line, 3);
+        
+        let manifest = crate::rsc::generate_server_manifest_from_scan(&result, "test");
+        let layout = &manifest.server_modules["server:layout"];
+        assert_eq!(layout.static_metadata, Some(serde_json::json!({ "title": "Home" })));
+    }
+
+    #[test]
+    fn test_scan_filter() {
+        let filter = ScanFilter::new(Path::new("/app"), &ScanOptions::default());
+        assert!(filter.accepts_file(Path::new("/app/blog/page.tsx")));
+        assert!(filter.accepts_file(Path::new("/app/.eslintrc.js")));
+        assert!(!filter.accepts_file(Path::new("/app/blog/styles.css")));
+        assert!(!filter.accepts_file(Path::new("/app/.cache/page.tsx")));
+        assert!(!filter.accepts_file(Path::new("/app/node_modules/x/index.js")));
+    }
+
+    #[test]
+    fn test_scan_filter_respects_parent_gitignore() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(".gitignore"), "generated/\n").unwrap();
+        let app = dir.path().join("app");
+        
+        let filter = ScanFilter::new(&app, &ScanOptions::default());
+        assert!(!filter.accepts_file(&app.join("generated/page.tsx")));
+        assert!(filter.accepts_file(&app.join("blog/page.tsx")));
+        
+        let options = ScanOptions { respect_gitignore: false, ..ScanOptions::default() };
+        let filter = ScanFilter::new(&app, &options);
+        assert!(filter.accepts_file(&app.join("generated/page.tsx")));
     }
 }
@@ -0,0 +1,271 @@
+//! RSC Route Segment Config
+//!
+//! Validates the `dynamic`, `dynamicParams`, `revalidate`, `fetchCache`,
+//! `runtime`, `preferredRegion` and `maxDuration` exports of pages, layouts
+//! and API routes, and merges them down a route's layout chain.
+
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use crate::analyzer::SegmentConfigExport;
+
+const DYNAMIC_VALUES: &[&str] = &["auto", "force-dynamic", "error", "force-static"];
+
+const FETCH_CACHE_VALUES: &[&str] = &[
+    "auto", "default-cache", "only-cache", "force-cache",
+    "force-no-store", "default-no-store", "only-no-store",
+];
+
+const RUNTIME_VALUES: &[&str] = &["nodejs", "edge"];
+
+/// `revalidate` option: `false` caches indefinitely, `0` renders on every request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Revalidate {
+    /// Always `false`
+    Never(bool),
+    Seconds(u64),
+}
+
+impl Revalidate {
+    /// Seconds between revalidations, `None` for never
+    pub fn seconds(&self) -> Option<u64> {
+        match self {
+            Revalidate::Never(_) => None,
+            Revalidate::Seconds(seconds) => Some(*seconds),
+        }
+    }
+}
+
+/// Parsed route segment config. Unset options are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SegmentConfig {
+    /// "auto", "force-dynamic", "error" or "force-static"
+    pub dynamic: Option<String>,
+    pub dynamic_params: Option<bool>,
+    pub revalidate: Option<Revalidate>,
+    /// "auto", "default-cache", "only-cache", "force-cache", "force-no-store",
+    /// "default-no-store" or "only-no-store"
+    pub fetch_cache: Option<String>,
+    /// "nodejs" or "edge"
+    pub runtime: Option<String>,
+    pub preferred_region: Option<Vec<String>>,
+    /// Seconds
+    pub max_duration: Option<u64>,
+}
+
+/// A segment config export with an invalid value
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentConfigIssue {
+    pub name: String,
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl SegmentConfig {
+    /// Parse a module's segment config exports. Invalid options are left
+    /// unset and reported.
+    pub fn parse(exports: &[SegmentConfigExport]) -> (Self, Vec<SegmentConfigIssue>) {
+        let mut config = Self::default();
+        let mut issues = Vec::new();
+
+        for export in exports {
+            let issue = |message: String| SegmentConfigIssue {
+                name: export.name.clone(),
+                message,
+                line: export.line,
+                column: export.column,
+            };
+            let Some(value) = &export.value else {
+                issues.push(issue(format!(
+                    "Segment config \"{}\" must be a literal so it can be read at build time.",
+                    export.name
+                )));
+                continue;
+            };
+
+            let parsed = match export.name.as_str() {
+                "dynamic" => one_of(value, DYNAMIC_VALUES).map(|v| config.dynamic = Some(v)),
+                "dynamicParams" => value.as_bool().map(|v| config.dynamic_params = Some(v)),
+                "revalidate" => match value {
+                    Value::Bool(false) => Some(Revalidate::Never(false)),
+                    _ => value.as_u64().map(Revalidate::Seconds),
+                }
+                .map(|v| config.revalidate = Some(v)),
+                "fetchCache" => one_of(value, FETCH_CACHE_VALUES).map(|v| config.fetch_cache = Some(v)),
+                "runtime" => one_of(value, RUNTIME_VALUES).map(|v| config.runtime = Some(v)),
+                "preferredRegion" => regions(value).map(|v| config.preferred_region = Some(v)),
+                "maxDuration" => value.as_u64().map(|v| config.max_duration = Some(v)),
+                _ => Some(()),
+            };
+
+            if parsed.is_none() {
+                issues.push(issue(format!(
+                    "Invalid segment config \"{}\": {value}. Expected {}.",
+                    export.name,
+                    expected(&export.name)
+                )));
+            }
+        }
+
+        (config, issues)
+    }
+
+    /// Apply `child` (a nested layout or the page) on top of this config:
+    ///
+    /// - `revalidate`: the shortest interval wins, so no segment can cache
+    ///   the route longer than another allows. `false` is the longest.
+    /// - Every other option: the child's value wins when it sets one,
+    ///   otherwise the parent's is inherited.
+    pub fn merge(&self, child: &SegmentConfig) -> SegmentConfig {
+        let revalidate = match (self.revalidate, child.revalidate) {
+            (Some(parent), Some(child)) => match (parent.seconds(), child.seconds()) {
+                (Some(p), Some(c)) if p < c => Some(parent),
+                (Some(_), None) => Some(parent),
+                _ => Some(child),
+            },
+            (parent, child) => child.or(parent),
+        };
+
+        SegmentConfig {
+            dynamic: child.dynamic.clone().or_else(|| self.dynamic.clone()),
+            dynamic_params: child.dynamic_params.or(self.dynamic_params),
+            revalidate,
+            fetch_cache: child.fetch_cache.clone().or_else(|| self.fetch_cache.clone()),
+            runtime: child.runtime.clone().or_else(|| self.runtime.clone()),
+            preferred_region: child.preferred_region.clone().or_else(|| self.preferred_region.clone()),
+            max_duration: child.max_duration.or(self.max_duration),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == SegmentConfig::default()
+    }
+}
+
+fn one_of(value: &Value, allowed: &[&str]) -> Option<String> {
+    value.as_str().filter(|v| allowed.contains(v)).map(str::to_string)
+}
+
+fn regions(value: &Value) -> Option<Vec<String>> {
+    match value {
+        Value::String(region) => Some(vec![region.clone()]),
+        Value::Array(regions) => regions.iter().map(|r| r.as_str().map(str::to_string)).collect(),
+        _ => None,
+    }
+}
+
+fn expected(name: &str) -> String {
+    let quoted = |values: &[&str]| {
+        values.iter().map(|v| format!("'{v}'")).collect::<Vec<_>>().join(", ")
+    };
+    match name {
+        "dynamic" => format!("one of {}", quoted(DYNAMIC_VALUES)),
+        "fetchCache" => format!("one of {}", quoted(FETCH_CACHE_VALUES)),
+        "runtime" => format!("one of {}", quoted(RUNTIME_VALUES)),
+        "dynamicParams" => "true or false".to_string(),
+        "revalidate" => "false or a non-negative whole number of seconds".to_string(),
+        "preferredRegion" => "a region string or an array of region strings".to_string(),
+        "maxDuration" => "a non-negative whole number of seconds".to_string(),
+        _ => "a literal".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn export(name: &str, value: Option<Value>) -> SegmentConfigExport {
+        SegmentConfigExport { name: name.to_string(), value, line: 1, column: 1 }
+    }
+
+    #[test]
+    fn test_parse_segment_config() {
+        let (config, issues) = SegmentConfig::parse(&[
+            export("dynamic", Some(json!("force-static"))),
+            export("revalidate", Some(json!(false))),
+            export("preferredRegion", Some(json!("iad1"))),
+            export("runtime", Some(json!("deno"))),
+            export("maxDuration", Some(json!(-5))),
+            export("fetchCache", None),
+        ]);
+        assert_eq!(config.dynamic.as_deref(), Some("force-static"));
+        assert_eq!(config.revalidate, Some(Revalidate::Never(false)));
+        assert_eq!(config.preferred_region, Some(vec!["iad1".to_string()]));
+        assert_eq!((config.runtime, config.max_duration, config.fetch_cache), (None, None, None));
+
+        let names: Vec<_> = issues.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, vec!["runtime", "maxDuration", "fetchCache"]);
+        assert!(issues[0].message.contains("Expected one of 'nodejs', 'edge'"));
+        assert!(issues[2].message.contains("must be a literal"));
+    }
+
+    #[test]
+    fn test_merge_segment_config() {
+        let parse = |exports: &[SegmentConfigExport]| SegmentConfig::parse(exports).0;
+        let layout = parse(&[
+            export("runtime", Some(json!("edge"))),
+            export("revalidate", Some(json!(60))),
+            export("dynamic", Some(json!("force-static"))),
+        ]);
+        let page = parse(&[export("revalidate", Some(json!(3600))), export("dynamic", Some(json!("auto")))]);
+
+        let merged = layout.merge(&page);
+        assert_eq!(merged.runtime.as_deref(), Some("edge"));
+        assert_eq!(merged.dynamic.as_deref(), Some("auto"));
+        assert_eq!(merged.revalidate, Some(Revalidate::Seconds(60)));
+
+        let never = parse(&[export("revalidate", Some(json!(false)))]);
+        assert_eq!(never.merge(&page).revalidate, Some(Revalidate::Seconds(3600)));
+        assert_eq!(page.merge(&never).revalidate, Some(Revalidate::Seconds(3600)));
+        assert!(SegmentConfig::default().merge(&SegmentConfig::default()).is_empty());
+        assert_eq!(serde_json::to_value(&never).unwrap()["revalidate"], json!(false));
+    }
+
+    #[test]
+    fn test_merge_rules_per_option() {
+        let parse = |exports: &[SegmentConfigExport]| SegmentConfig::parse(exports).0;
+        let root = parse(&[
+            export("dynamic", Some(json!("force-dynamic"))),
+            export("dynamicParams", Some(json!(false))),
+            export("revalidate", Some(json!(30))),
+            export("fetchCache", Some(json!("only-no-store"))),
+            export("runtime", Some(json!("edge"))),
+            export("preferredRegion", Some(json!(["iad1", "sfo1"]))),
+            export("maxDuration", Some(json!(10))),
+        ]);
+        let nested = parse(&[
+            export("dynamic", Some(json!("force-static"))),
+            export("dynamicParams", Some(json!(true))),
+            export("revalidate", Some(json!(300))),
+            export("fetchCache", Some(json!("force-cache"))),
+            export("runtime", Some(json!("nodejs"))),
+            export("preferredRegion", Some(json!("fra1"))),
+            export("maxDuration", Some(json!(60))),
+        ]);
+        let page = parse(&[export("revalidate", Some(json!(false)))]);
+
+        // Innermost value wins for every option but `revalidate`
+        let merged = root.merge(&nested).merge(&page);
+        assert_eq!(merged, SegmentConfig {
+            dynamic: Some("force-static".to_string()),
+            dynamic_params: Some(true),
+            revalidate: Some(Revalidate::Seconds(30)),
+            fetch_cache: Some("force-cache".to_string()),
+            runtime: Some("nodejs".to_string()),
+            preferred_region: Some(vec!["fra1".to_string()]),
+            max_duration: Some(60),
+        });
+
+        // Unset options are inherited, whichever side sets them
+        assert_eq!(root.merge(&SegmentConfig::default()), root);
+        assert_eq!(SegmentConfig::default().merge(&nested), nested);
+
+        // The shortest `revalidate` wins from either side; `false` is the longest
+        assert_eq!(nested.merge(&root).revalidate, Some(Revalidate::Seconds(30)));
+        assert_eq!(page.merge(&page).revalidate, Some(Revalidate::Never(false)));
+        let zero = parse(&[export("revalidate", Some(json!(0)))]);
+        assert_eq!(zero.merge(&page).revalidate, Some(Revalidate::Seconds(0)));
+    }
+}
@@ -95,10 +95,10 @@ pub fn serialize_value(value: &serde_json::Value) -> SerializedValue {
         }
         serde_json::Value::String(s) => {
             // Check for special serialized types
-            if s.starts_with("__DATE__:") {
-                SerializedValue::Date(s[9..].to_string())
-            } else if s.starts_with("__SYMBOL__:") {
-                SerializedValue::Symbol(s[11..].to_string())
+            if let Some(date) = s.strip_prefix("__DATE__:") {
+                SerializedValue::Date(date.to_string())
+            } else if let Some(symbol) = s.strip_prefix("__SYMBOL__:") {
+                SerializedValue::Symbol(symbol.to_string())
             } else {
                 SerializedValue::String(s.clone())
             }
@@ -108,27 +108,25 @@ pub fn serialize_value(value: &serde_json::Value) -> SerializedValue {
         }
         serde_json::Value::Object(obj) => {
             // Check for special object types
-            if let Some(type_marker) = obj.get("__type") {
-                if let serde_json::Value::String(t) = type_marker {
-                    match t.as_str() {
-                        "undefined" => return SerializedValue::Undefined,
-                        "Date" => {
-                            if let Some(serde_json::Value::String(v)) = obj.get("value") {
-                                return SerializedValue::Date(v.clone());
-                            }
+            if let Some(serde_json::Value::String(t)) = obj.get("__type") {
+                match t.as_str() {
+                    "undefined" => return SerializedValue::Undefined,
+                    "Date" => {
+                        if let Some(serde_json::Value::String(v)) = obj.get("value") {
+                            return SerializedValue::Date(v.clone());
                         }
-                        "ReactElement" => {
-                            if let Some(serde_json::Value::String(id)) = obj.get("id") {
-                                return SerializedValue::ReactElement { id: id.clone() };
-                            }
+                    }
+                    "ReactElement" => {
+                        if let Some(serde_json::Value::String(id)) = obj.get("id") {
+                            return SerializedValue::ReactElement { id: id.clone() };
                         }
-                        "Function" => {
-                            if let Some(serde_json::Value::String(name)) = obj.get("name") {
-                                return SerializedValue::Function { name: name.clone() };
-                            }
+                    }
+                    "Function" => {
+                        if let Some(serde_json::Value::String(name)) = obj.get("name") {
+                            return SerializedValue::Function { name: name.clone() };
                         }
-                        _ => {}
                     }
+                    _ => {}
                 }
             }
             
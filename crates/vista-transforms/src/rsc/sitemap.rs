@@ -0,0 +1,416 @@
+//! RSC Sitemap and robots.txt Generation
+//!
+//! Builds `sitemap.xml` (split into numbered files behind a sitemap index
+//! past `max_urls_per_file` URLs) and `robots.txt` from the server manifest
+//! routes and the expanded static paths. Per-route `changeFrequency`,
+//! `priority` and `lastModified` come from a `sitemap` field in the static
+//! metadata of the route's layouts and page, merged field by field. Without
+//! a `changeFrequency`, one is derived from the route's `revalidate`
+//! segment config.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use super::manifest::{RouteEntry, ServerManifest};
+use super::segment_config::SegmentConfig;
+use super::static_paths::StaticPath;
+use crate::diagnostics::{Diagnostic, SourceSpan};
+use crate::metadata::merge_metadata;
+
+/// Most URLs a single sitemap file may list
+pub const SITEMAP_MAX_URLS: usize = 50_000;
+
+/// Diagnostic code for unusable `sitemap` metadata values
+pub const INVALID_SITEMAP_ENTRY: &str = "INVALID_SITEMAP_ENTRY";
+
+const CHANGE_FREQUENCIES: &[&str] = &["always", "hourly", "daily", "weekly", "monthly", "yearly", "never"];
+
+/// Options for sitemap generation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SitemapOptions {
+    /// Site origin URLs are prefixed with, e.g. `https://acme.dev`
+    pub base_url: String,
+    /// URLs per sitemap file, capped at `SITEMAP_MAX_URLS`
+    pub max_urls_per_file: usize,
+    /// Paths listed as `Disallow` in robots.txt
+    pub disallow: Vec<String>,
+}
+
+impl SitemapOptions {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.to_string(),
+            max_urls_per_file: SITEMAP_MAX_URLS,
+            disallow: Vec::new(),
+        }
+    }
+}
+
+/// One `<url>` of the sitemap
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SitemapEntry {
+    pub loc: String,
+    pub last_modified: Option<String>,
+    pub change_frequency: Option<String>,
+    pub priority: Option<f64>,
+}
+
+/// A route left out of the sitemap
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OmittedRoute {
+    pub pattern: String,
+    pub reason: String,
+}
+
+/// A generated file, relative to the output directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SitemapFile {
+    pub name: String,
+    pub content: String,
+}
+
+/// Generated sitemap files, robots.txt and what was left out
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SitemapOutput {
+    pub entries: Vec<SitemapEntry>,
+    /// `sitemap.xml`, or a sitemap index of that name followed by `sitemap-N.xml` files
+    pub files: Vec<SitemapFile>,
+    pub robots_txt: String,
+    pub omitted: Vec<OmittedRoute>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Generate the sitemap and robots.txt. Static routes are listed as they
+/// are, dynamic routes through their `static_paths`; dynamic routes without
+/// any and `robots: { index: false }` routes are reported as omitted.
+pub fn generate_sitemap(
+    manifest: &ServerManifest,
+    static_paths: &[StaticPath],
+    options: &SitemapOptions,
+) -> SitemapOutput {
+    let mut output = SitemapOutput::default();
+    let base_url = options.base_url.trim_end_matches('/');
+
+    for route in &manifest.routes {
+        if route.slot.is_some() || route.interception.is_some() {
+            continue;
+        }
+
+        let chain = metadata_chain(manifest, route);
+        let metadata = merge_metadata(&chain);
+        if metadata.pointer("/robots/index") == Some(&Value::Bool(false)) {
+            output.omitted.push(OmittedRoute {
+                pattern: route.pattern.clone(),
+                reason: "robots.index is false".to_string(),
+            });
+            continue;
+        }
+
+        let paths: Vec<&str> = if route.pattern.split('/').any(|s| s.starts_with(':')) {
+            static_paths
+                .iter()
+                .filter(|p| p.pattern == route.pattern)
+                .map(|p| p.path.as_str())
+                .collect()
+        } else {
+            vec![route.pattern.as_str()]
+        };
+        if paths.is_empty() {
+            output.omitted.push(OmittedRoute {
+                pattern: route.pattern.clone(),
+                reason: "dynamic route without static params".to_string(),
+            });
+            continue;
+        }
+
+        let sitemap = chain.iter().filter_map(|m| m.get("sitemap")?.as_object()).fold(
+            serde_json::Map::new(),
+            |mut merged, fields| {
+                merged.extend(fields.clone());
+                merged
+            },
+        );
+        let (mut fields, problems) = sitemap_fields(&sitemap);
+        if fields.change_frequency.is_none() {
+            fields.change_frequency = revalidate_change_frequency(&route.segment_config).map(str::to_string);
+        }
+        if !problems.is_empty() {
+            let relative_path = manifest.path_to_id.get(&route.page_path)
+                .and_then(|id| manifest.server_modules.get(id))
+                .map_or(route.page_path.as_str(), |module| module.path.as_str());
+            for problem in problems {
+                output.diagnostics.push(
+                    Diagnostic::warning(INVALID_SITEMAP_ENTRY, format!("{problem} (route \"{}\")", route.pattern))
+                        .with_primary_span(SourceSpan::new(&route.page_path, relative_path, 1, 1)),
+                );
+            }
+        }
+
+        for path in paths {
+            let loc = if path == "/" { format!("{base_url}/") } else { format!("{base_url}{path}") };
+            output.entries.push(SitemapEntry { loc, ..fields.clone() });
+        }
+    }
+
+    output.entries.sort_by(|a, b| a.loc.cmp(&b.loc));
+    output.entries.dedup_by(|a, b| a.loc == b.loc);
+
+    let chunks: Vec<&[SitemapEntry]> = output.entries.chunks(options.max_urls_per_file.clamp(1, SITEMAP_MAX_URLS)).collect();
+    if chunks.len() <= 1 {
+        output.files.push(SitemapFile {
+            name: "sitemap.xml".to_string(),
+            content: render_urlset(chunks.first().copied().unwrap_or_default()),
+        });
+    } else {
+        let names: Vec<String> = (0..chunks.len()).map(|i| format!("sitemap-{i}.xml")).collect();
+        output.files.push(SitemapFile {
+            name: "sitemap.xml".to_string(),
+            content: render_index(base_url, &names),
+        });
+        for (name, chunk) in names.into_iter().zip(chunks) {
+            output.files.push(SitemapFile { name, content: render_urlset(chunk) });
+        }
+    }
+
+    output.robots_txt = render_robots(base_url, &options.disallow);
+    output
+}
+
+/// Write the sitemap files and robots.txt into `out_dir`, returning their paths
+pub fn write_sitemap(out_dir: &Path, output: &SitemapOutput) -> io::Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(out_dir)?;
+    let mut written = Vec::new();
+    let robots = SitemapFile { name: "robots.txt".to_string(), content: output.robots_txt.clone() };
+    for file in output.files.iter().chain(std::iter::once(&robots)) {
+        let path = out_dir.join(&file.name);
+        std::fs::write(&path, &file.content)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Static metadata of the route's layouts and page, root first
+fn metadata_chain(manifest: &ServerManifest, route: &RouteEntry) -> Vec<Value> {
+    route.layout_paths.iter()
+        .chain(std::iter::once(&route.page_path))
+        .filter_map(|path| manifest.path_to_id.get(path))
+        .filter_map(|id| manifest.server_modules.get(id)?.static_metadata.clone())
+        .collect()
+}
+
+/// Change frequency matching how often the route is regenerated: `always`
+/// for dynamic rendering, otherwise the `revalidate` interval rounded up
+fn revalidate_change_frequency(config: &SegmentConfig) -> Option<&'static str> {
+    if config.dynamic.as_deref() == Some("force-dynamic") {
+        return Some("always");
+    }
+    let seconds = config.revalidate.as_ref()?.seconds()?;
+    Some(match seconds {
+        0 => "always",
+        1..=3_600 => "hourly",
+        3_601..=86_400 => "daily",
+        86_401..=604_800 => "weekly",
+        604_801..=2_678_400 => "monthly",
+        _ => "yearly",
+    })
+}
+
+/// Read `{ changeFrequency, priority, lastModified }`, reporting unusable values
+fn sitemap_fields(sitemap: &serde_json::Map<String, Value>) -> (SitemapEntry, Vec<String>) {
+    let mut entry = SitemapEntry { loc: String::new(), last_modified: None, change_frequency: None, priority: None };
+    let mut problems = Vec::new();
+
+    match sitemap.get("changeFrequency") {
+        Some(Value::String(freq)) if CHANGE_FREQUENCIES.contains(&freq.as_str()) => {
+            entry.change_frequency = Some(freq.clone());
+        }
+        Some(other) => problems.push(format!(
+            "sitemap.changeFrequency must be one of {}, got {other}",
+            CHANGE_FREQUENCIES.join(", ")
+        )),
+        None => {}
+    }
+    match sitemap.get("priority") {
+        Some(value) if value.as_f64().is_some_and(|p| (0.0..=1.0).contains(&p)) => entry.priority = value.as_f64(),
+        Some(other) => problems.push(format!("sitemap.priority must be a number from 0 to 1, got {other}")),
+        None => {}
+    }
+    match sitemap.get("lastModified") {
+        Some(Value::String(date)) if !date.is_empty() => entry.last_modified = Some(date.clone()),
+        Some(other) => problems.push(format!("sitemap.lastModified must be a date string, got {other}")),
+        None => {}
+    }
+    (entry, problems)
+}
+
+fn render_urlset(entries: &[SitemapEntry]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for entry in entries {
+        xml.push_str(&format!("  <url>\n    <loc>{}</loc>\n", escape_xml(&entry.loc)));
+        if let Some(last_modified) = &entry.last_modified {
+            xml.push_str(&format!("    <lastmod>{}</lastmod>\n", escape_xml(last_modified)));
+        }
+        if let Some(change_frequency) = &entry.change_frequency {
+            xml.push_str(&format!("    <changefreq>{change_frequency}</changefreq>\n"));
+        }
+        if let Some(priority) = entry.priority {
+            xml.push_str(&format!("    <priority>{priority}</priority>\n"));
+        }
+        xml.push_str("  </url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+fn render_index(base_url: &str, names: &[String]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for name in names {
+        xml.push_str(&format!("  <sitemap>\n    <loc>{}</loc>\n  </sitemap>\n", escape_xml(&format!("{base_url}/{name}"))));
+    }
+    xml.push_str("</sitemapindex>\n");
+    xml
+}
+
+fn render_robots(base_url: &str, disallow: &[String]) -> String {
+    let mut robots = String::from("User-agent: *\nAllow: /\n");
+    for path in disallow {
+        robots.push_str(&format!("Disallow: {path}\n"));
+    }
+    robots.push_str(&format!("\nSitemap: {base_url}/sitemap.xml\n"));
+    robots
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::rsc::{generate_server_manifest, Revalidate};
+
+    #[test]
+    fn test_generate_sitemap() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        for (file, source) in [
+            ("layout.tsx", "export const metadata = { sitemap: { changeFrequency: 'weekly' } };\nexport default function Layout() {}"),
+            ("page.tsx", "export const metadata = { sitemap: { priority: 1 } };\nexport default function Page() {}"),
+            ("news/page.tsx", "export const revalidate = 600;\nexport const metadata = { sitemap: { priority: 0.25 } };\nexport default function Page() {}"),
+            ("blog/[slug]/page.tsx", "export const metadata = { sitemap: { lastModified: '2024-05-01', priority: 7 } };\nexport default function Page() {}"),
+            ("docs/[...path]/page.tsx", "export default function Page() {}"),
+            ("admin/page.tsx", "export const metadata = { robots: { index: false } };\nexport default function Page() {}"),
+        ] {
+            let path = app.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, source).unwrap();
+        }
+        let manifest = generate_server_manifest(&app.to_string_lossy(), "test");
+        let plan = crate::rsc::plan_static_paths(
+            &app.to_string_lossy(),
+            &manifest.routes,
+            &HashMap::from([("/blog/:slug".to_string(), vec![serde_json::json!({ "slug": "a&b" })])]),
+        );
+
+        let mut options = SitemapOptions::new("https://acme.dev/");
+        options.disallow = vec!["/admin".to_string()];
+        let output = generate_sitemap(&manifest, &plan.paths, &options);
+
+        let locs: Vec<_> = output.entries.iter().map(|e| e.loc.as_str()).collect();
+        assert_eq!(locs, vec!["https://acme.dev/", "https://acme.dev/blog/a%26b", "https://acme.dev/news"]);
+        assert_eq!(output.entries[0].priority, Some(1.0));
+        assert_eq!(output.entries[1].change_frequency.as_deref(), Some("weekly"));
+        assert_eq!(output.entries[1].last_modified.as_deref(), Some("2024-05-01"));
+        // Metadata wins over the layout's weekly; the page's revalidate fills in otherwise
+        assert_eq!(output.entries[2].change_frequency.as_deref(), Some("weekly"));
+
+        let omitted: Vec<_> = output.omitted.iter().map(|o| o.pattern.as_str()).collect();
+        assert_eq!(omitted, vec!["/admin", "/docs/:path*"]);
+        assert_eq!(output.diagnostics.len(), 1);
+        assert_eq!(output.diagnostics[0].primary_span.as_ref().unwrap().relative_path, "blog/[slug]/page.tsx");
+
+        assert_eq!(output.files.len(), 1);
+        assert!(output.files[0].content.contains("<loc>https://acme.dev/blog/a%26b</loc>\n    <lastmod>2024-05-01</lastmod>"));
+        assert!(output.files[0].content.contains("<priority>1</priority>"));
+        // Printed as given, not rounded to one decimal
+        assert!(output.files[0].content.contains("<priority>0.25</priority>"));
+        assert_eq!(output.robots_txt, "User-agent: *\nAllow: /\nDisallow: /admin\n\nSitemap: https://acme.dev/sitemap.xml\n");
+    }
+
+    #[test]
+    fn test_sitemap_index_past_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        for page in ["page.tsx", "a/page.tsx", "b/page.tsx", "c/page.tsx", "d/page.tsx"] {
+            let path = app.join(page);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "export default function Page() {}").unwrap();
+        }
+        let manifest = generate_server_manifest(&app.to_string_lossy(), "test");
+
+        let mut options = SitemapOptions::new("https://acme.dev");
+        options.max_urls_per_file = 2;
+        let output = generate_sitemap(&manifest, &[], &options);
+
+        let names: Vec<_> = output.files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["sitemap.xml", "sitemap-0.xml", "sitemap-1.xml", "sitemap-2.xml"]);
+        assert!(output.files[0].content.contains("<sitemapindex"));
+        assert!(output.files[0].content.contains("<loc>https://acme.dev/sitemap-2.xml</loc>"));
+        assert_eq!(output.files[3].content.matches("<url>").count(), 1);
+
+        let out = dir.path().join("public");
+        let written = write_sitemap(&out, &output).unwrap();
+        assert_eq!(written.len(), 5);
+        assert!(out.join("robots.txt").is_file());
+    }
+
+    #[test]
+    fn test_sitemap_files_are_capped_at_protocol_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        std::fs::create_dir_all(app.join("p/[id]")).unwrap();
+        std::fs::write(app.join("p/[id]/page.tsx"), "export default function Page() {}").unwrap();
+        let manifest = generate_server_manifest(&app.to_string_lossy(), "test");
+        let paths: Vec<StaticPath> = (0..=SITEMAP_MAX_URLS)
+            .map(|i| StaticPath {
+                pattern: "/p/:id".to_string(),
+                path: format!("/p/{i}"),
+                page_path: String::new(),
+                params: Default::default(),
+            })
+            .collect();
+
+        let mut options = SitemapOptions::new("https://acme.dev");
+        options.max_urls_per_file = usize::MAX;
+        let output = generate_sitemap(&manifest, &paths, &options);
+
+        let counts: Vec<_> = output.files[1..].iter().map(|f| f.content.matches("<url>").count()).collect();
+        assert_eq!(counts, vec![SITEMAP_MAX_URLS, 1]);
+    }
+
+    #[test]
+    fn test_change_frequency_from_revalidate() {
+        let config = |dynamic: Option<&str>, revalidate: Option<Revalidate>| SegmentConfig {
+            dynamic: dynamic.map(str::to_string),
+            revalidate,
+            ..Default::default()
+        };
+        assert_eq!(revalidate_change_frequency(&config(None, None)), None);
+        assert_eq!(revalidate_change_frequency(&config(None, Some(Revalidate::Never(false)))), None);
+        assert_eq!(revalidate_change_frequency(&config(None, Some(Revalidate::Seconds(0)))), Some("always"));
+        assert_eq!(revalidate_change_frequency(&config(None, Some(Revalidate::Seconds(600)))), Some("hourly"));
+        assert_eq!(revalidate_change_frequency(&config(None, Some(Revalidate::Seconds(86_400)))), Some("daily"));
+        assert_eq!(revalidate_change_frequency(&config(Some("force-dynamic"), None)), Some("always"));
+    }
+}
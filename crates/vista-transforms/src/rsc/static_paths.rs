@@ -0,0 +1,355 @@
+//! RSC Static Path Planning
+//!
+//! Expands routes into the concrete paths to prerender. Static routes map to
+//! themselves; dynamic routes whose page exports `generateStaticParams` are
+//! expanded with the params JS collected from it, after checking them against
+//! the route's segments. Values are percent-encoded and duplicate paths are
+//! reported, as are dynamic routes left without params. Routes with
+//! `dynamicParams = false` are listed in the plan, since the server must 404
+//! on any of their params that weren't prerendered.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use super::graph::relative_path_from;
+use super::manifest::RouteEntry;
+use super::matcher::RouteParam;
+use crate::diagnostics::{Diagnostic, SourceSpan};
+
+/// Diagnostic code for params that don't fit the route's segments
+pub const INVALID_STATIC_PARAMS: &str = "INVALID_STATIC_PARAMS";
+
+/// Diagnostic code for two param sets producing the same path
+pub const DUPLICATE_STATIC_PATH: &str = "DUPLICATE_STATIC_PATH";
+
+/// Diagnostic code for a dynamic route that should have static params but has none
+pub const MISSING_STATIC_PARAMS: &str = "MISSING_STATIC_PARAMS";
+
+/// A concrete path to prerender
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaticPath {
+    /// Route pattern the path was expanded from
+    pub pattern: String,
+    /// URL path with percent-encoded params
+    pub path: String,
+    pub page_path: String,
+    /// Params as returned by `generateStaticParams` (not encoded)
+    pub params: BTreeMap<String, RouteParam>,
+}
+
+/// Paths to prerender, in route order
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StaticPathPlan {
+    pub paths: Vec<StaticPath>,
+    /// Patterns of dynamic routes with `dynamicParams = false`: only their
+    /// paths in `paths` are served, any other params 404
+    pub static_only_patterns: Vec<String>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Plan the static paths of `routes`. `static_params` maps a route pattern
+/// to the values its `generateStaticParams` returned. Slot, intercepting
+/// and `dynamic = 'force-dynamic'` routes are skipped. Dynamic routes without
+/// params are skipped too, with a warning when the page exports
+/// `generateStaticParams` and an error when `dynamicParams = false` would
+/// leave the route without any page.
+pub fn plan_static_paths(
+    app_dir: &str,
+    routes: &[RouteEntry],
+    static_params: &HashMap<String, Vec<Value>>,
+) -> StaticPathPlan {
+    let mut plan = StaticPathPlan::default();
+    let mut seen: HashSet<String> = HashSet::new();
+
+    for route in routes {
+        if route.slot.is_some()
+            || route.interception.is_some()
+            || route.segment_config.dynamic.as_deref() == Some("force-dynamic")
+        {
+            continue;
+        }
+
+        let relative_path = relative_path_from(Path::new(&route.page_path), Path::new(app_dir));
+        let span = || SourceSpan::new(&route.page_path, &relative_path, 1, 1);
+
+        let is_dynamic = route.pattern.split('/').any(|segment| segment.starts_with(':'));
+        let static_only = is_dynamic && route.segment_config.dynamic_params == Some(false);
+        if static_only {
+            plan.static_only_patterns.push(route.pattern.clone());
+        }
+
+        let param_sets = if is_dynamic {
+            static_params.get(&route.pattern).cloned()
+        } else {
+            Some(vec![Value::Object(Default::default())])
+        };
+        if static_only && param_sets.as_ref().is_none_or(Vec::is_empty) {
+            plan.diagnostics.push(
+                Diagnostic::error(
+                    MISSING_STATIC_PARAMS,
+                    format!(
+                        "\"{}\" sets dynamicParams = false but has no static params, so every request to it returns 404.",
+                        route.pattern
+                    ),
+                )
+                .with_primary_span(span()),
+            );
+            continue;
+        }
+        let Some(param_sets) = param_sets else {
+            if route.has_generate_static_params {
+                plan.diagnostics.push(
+                    Diagnostic::warning(
+                        MISSING_STATIC_PARAMS,
+                        format!(
+                            "The page of \"{}\" exports generateStaticParams() but no params were collected for it; its paths will render on demand.",
+                            route.pattern
+                        ),
+                    )
+                    .with_primary_span(span()),
+                );
+            }
+            continue;
+        };
+
+        for (index, params) in param_sets.iter().enumerate() {
+            let expanded = match expand_route(&route.pattern, params) {
+                Ok(expanded) => expanded,
+                Err(problem) => {
+                    plan.diagnostics.push(
+                        Diagnostic::error(
+                            INVALID_STATIC_PARAMS,
+                            format!(
+                                "generateStaticParams() for \"{}\" returned invalid params at index {index}: {problem}",
+                                route.pattern
+                            ),
+                        )
+                        .with_primary_span(span()),
+                    );
+                    continue;
+                }
+            };
+
+            let (path, params) = expanded;
+            if !seen.insert(path.clone()) {
+                plan.diagnostics.push(
+                    Diagnostic::warning(
+                        DUPLICATE_STATIC_PATH,
+                        format!("Static path \"{path}\" of \"{}\" is generated more than once.", route.pattern),
+                    )
+                    .with_primary_span(span()),
+                );
+                continue;
+            }
+            plan.paths.push(StaticPath {
+                pattern: route.pattern.clone(),
+                path,
+                page_path: route.page_path.clone(),
+                params,
+            });
+        }
+    }
+
+    plan
+}
+
+/// Fill a route pattern with one param set, returning the encoded path and
+/// the params. Errors describe the first mismatch.
+fn expand_route(pattern: &str, params: &Value) -> Result<(String, BTreeMap<String, RouteParam>), String> {
+    let Value::Object(params) = params else {
+        return Err(format!("expected an object, got {params}"));
+    };
+
+    let mut path = String::new();
+    let mut names = Vec::new();
+    let mut values = BTreeMap::new();
+
+    for segment in pattern.split('/').filter(|s| !s.is_empty()) {
+        let Some(param) = segment.strip_prefix(':') else {
+            path.push('/');
+            path.push_str(segment);
+            continue;
+        };
+
+        let (name, catch_all, optional) = if let Some(name) = param.strip_suffix("*?") {
+            (name, true, true)
+        } else if let Some(name) = param.strip_suffix('*') {
+            (name, true, false)
+        } else {
+            (param, false, false)
+        };
+        names.push(name);
+
+        let value = params.get(name).filter(|v| !v.is_null());
+        if !catch_all {
+            match value {
+                Some(Value::String(value)) if !value.is_empty() => {
+                    path.push('/');
+                    path.push_str(&encode_segment(value));
+                    values.insert(name.to_string(), RouteParam::Single(value.clone()));
+                }
+                Some(value) => return Err(format!("\"{name}\" must be a non-empty string, got {value}")),
+                None => return Err(format!("missing \"{name}\"")),
+            }
+            continue;
+        }
+
+        let parts = match value {
+            Some(Value::Array(parts)) => parts
+                .iter()
+                .map(|part| part.as_str().filter(|p| !p.is_empty()).map(str::to_string))
+                .collect::<Option<Vec<_>>>(),
+            None if optional => Some(Vec::new()),
+            _ => None,
+        };
+        match parts {
+            Some(parts) if !parts.is_empty() || optional => {
+                for part in &parts {
+                    path.push('/');
+                    path.push_str(&encode_segment(part));
+                }
+                if !parts.is_empty() {
+                    values.insert(name.to_string(), RouteParam::Multiple(parts));
+                }
+            }
+            _ => {
+                let expected = if optional { "an array" } else { "a non-empty array" };
+                return Err(format!(
+                    "\"{name}\" must be {expected} of non-empty strings, got {}",
+                    value.map_or_else(|| "nothing".to_string(), Value::to_string)
+                ));
+            }
+        }
+    }
+
+    if let Some(unknown) = params.keys().find(|key| !names.contains(&key.as_str())) {
+        return Err(format!("unknown param \"{unknown}\""));
+    }
+
+    if path.is_empty() {
+        path.push('/');
+    }
+    Ok((path, values))
+}
+
+/// Percent-encode a param value like `encodeURIComponent`
+fn encode_segment(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9'
+            | b'-' | b'_' | b'.' | b'!' | b'~' | b'*' | b'\'' | b'(' | b')' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn route(pattern: &str) -> RouteEntry {
+        RouteEntry {
+            pattern: pattern.to_string(),
+            page_path: format!("/app{pattern}/page.tsx"),
+            layout_paths: vec![],
+            loading_path: None,
+            error_path: None,
+            not_found_path: None,
+            route_type: String::new(),
+            slot: None,
+            interception: None,
+            slots: vec![],
+            boundaries: Default::default(),
+            has_generate_static_params: true,
+            segment_config: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_plan_static_paths() {
+        let mut forced = route("/live");
+        forced.segment_config.dynamic = Some("force-dynamic".to_string());
+        let mut on_demand = route("/user/:id");
+        on_demand.has_generate_static_params = false;
+        let routes = vec![
+            route("/about"),
+            route("/blog/:slug"),
+            route("/docs/:path*"),
+            route("/wiki/:page*?"),
+            route("/shop/:item"),
+            on_demand,
+            forced,
+        ];
+        let params = HashMap::from([
+            ("/blog/:slug".to_string(), vec![
+                json!({ "slug": "hello world" }),
+                json!({ "slug": "a/b" }),
+                json!({ "slug": "hello world" }),
+            ]),
+            ("/docs/:path*".to_string(), vec![
+                json!({ "path": ["guide", "intro"] }),
+                json!({ "path": [] }),
+                json!({ "path": "guide" }),
+            ]),
+            ("/wiki/:page*?".to_string(), vec![json!({}), json!({ "page": ["x"] })]),
+            ("/live".to_string(), vec![json!({})]),
+        ]);
+
+        let plan = plan_static_paths("/app", &routes, &params);
+        let paths: Vec<_> = plan.paths.iter().map(|p| p.path.as_str()).collect();
+        assert_eq!(paths, vec![
+            "/about",
+            "/blog/hello%20world",
+            "/blog/a%2Fb",
+            "/docs/guide/intro",
+            "/wiki",
+            "/wiki/x",
+        ]);
+        assert_eq!(plan.paths[2].params["slug"], RouteParam::Single("a/b".to_string()));
+        assert!(plan.paths[4].params.is_empty());
+
+        let diagnostics: Vec<_> = plan.diagnostics.iter().map(|d| (d.code.as_str(), d.message.as_str())).collect();
+        assert_eq!(diagnostics.len(), 4);
+        assert_eq!(diagnostics[0].0, DUPLICATE_STATIC_PATH);
+        assert!(diagnostics[1].1.contains("index 1: \"path\" must be a non-empty array"));
+        assert!(diagnostics[2].1.contains("got \"guide\""));
+        // generateStaticParams without collected params is reported, a plain
+        // dynamic route isn't
+        assert_eq!(diagnostics[3].0, MISSING_STATIC_PARAMS);
+        assert!(diagnostics[3].1.contains("\"/shop/:item\" exports generateStaticParams()"));
+        assert_eq!(plan.diagnostics[3].severity, crate::diagnostics::Severity::Warning);
+        assert!(plan.static_only_patterns.is_empty());
+        assert_eq!(plan.diagnostics[0].primary_span.as_ref().unwrap().relative_path, "blog/:slug/page.tsx");
+    }
+
+    #[test]
+    fn test_plan_static_paths_with_dynamic_params_false() {
+        let mut listed = route("/blog/:slug");
+        listed.segment_config.dynamic_params = Some(false);
+        let mut empty = route("/tag/:name");
+        empty.segment_config.dynamic_params = Some(false);
+        empty.has_generate_static_params = false;
+        let params = HashMap::from([("/blog/:slug".to_string(), vec![json!({ "slug": "a" })])]);
+
+        let plan = plan_static_paths("/app", &[listed, empty], &params);
+        assert_eq!(plan.paths.len(), 1);
+        assert_eq!(plan.static_only_patterns, vec!["/blog/:slug", "/tag/:name"]);
+        assert_eq!(plan.diagnostics.len(), 1);
+        assert_eq!(plan.diagnostics[0].code, MISSING_STATIC_PARAMS);
+        assert_eq!(plan.diagnostics[0].severity, crate::diagnostics::Severity::Error);
+        assert!(plan.diagnostics[0].message.starts_with("\"/tag/:name\" sets dynamicParams = false"));
+    }
+
+    #[test]
+    fn test_expand_route_rejects_missing_and_unknown_params() {
+        assert_eq!(expand_route("/a/:id", &json!({})).unwrap_err(), "missing \"id\"");
+        assert_eq!(expand_route("/a/:id", &json!({ "id": "1", "x": "2" })).unwrap_err(), "unknown param \"x\"");
+        assert!(expand_route("/a/:id", &json!({ "id": 1 })).unwrap_err().contains("non-empty string"));
+        assert_eq!(expand_route("/:rest*?", &json!({ "rest": null })).unwrap().0, "/");
+        assert_eq!(encode_segment("caf\u{e9} & co"), "caf%C3%A9%20%26%20co");
+    }
+}
@@ -0,0 +1,526 @@
+//! RSC App Structure Validator
+//!
+//! Validates the app directory against Vista's conventions (root entry,
+//! segment names, route conflicts, not-found sources, metadata shape) and
+//! builds the route graph. Issues are diagnostics with stable codes,
+//! consumed by the dev overlay and build output.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Serialize, Deserialize};
+use super::routes::{
+    apply_segment, is_valid_segment_name, join_pattern, route_conflict_keys, SegmentKind,
+};
+use super::graph::relative_path_from;
+use super::manifest::ROUTE_PATTERN_CONFLICT;
+use super::scanner::{
+    scan_app_directory_with_options, ComponentType, ScanErrorKind, ScanFilter, ScanOptions,
+    ScannedComponent,
+};
+use crate::diagnostics::{Diagnostic, Severity, SourceSpan};
+
+/// Diagnostic code for an app directory without a root entry
+pub const ROOT_MISSING: &str = "ROOT_MISSING";
+
+/// Diagnostic code for a root entry without a default export
+pub const ROOT_EXPORT_MISSING: &str = "ROOT_EXPORT_MISSING";
+
+/// Diagnostic code for `app/layout` standing in for `app/root`
+pub const LAYOUT_FALLBACK_USED: &str = "LAYOUT_FALLBACK_USED";
+
+/// Diagnostic code for a page inside the reserved `[not-found]` segment
+pub const RESERVED_NOT_FOUND_PUBLIC: &str = "RESERVED_NOT_FOUND_PUBLIC";
+
+/// Diagnostic code for a directory name that isn't a valid route segment
+pub const INVALID_SEGMENT_NAME: &str = "INVALID_SEGMENT_NAME";
+
+/// Diagnostic code for a `notFoundRoute` without a page
+pub const INVALID_NOT_FOUND_OVERRIDE_TARGET: &str = "INVALID_NOT_FOUND_OVERRIDE_TARGET";
+
+/// Diagnostic code for both not-found conventions being present
+pub const MULTIPLE_NOT_FOUND_SOURCES: &str = "MULTIPLE_NOT_FOUND_SOURCES";
+
+/// Diagnostic code for a primitive `metadata` export
+pub const METADATA_EXPORT_SHAPE_INVALID: &str = "METADATA_EXPORT_SHAPE_INVALID";
+
+/// A directory in the route graph
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteGraphNode {
+    /// Directory name
+    pub segment: String,
+    /// URL pattern of the directory (e.g. "/blog/:slug")
+    pub pattern: String,
+    pub kind: SegmentKind,
+    pub has_page: bool,
+    pub has_layout: bool,
+    pub children: Vec<RouteGraphNode>,
+    /// Absolute path of the page, if any
+    pub file_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StructureState {
+    Ok,
+    Error,
+}
+
+/// Result of validating the app directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructureValidation {
+    /// `Error` when any issue has error severity
+    pub state: StructureState,
+    pub issues: Vec<Diagnostic>,
+    /// Directories below the app directory, sorted by name
+    pub route_graph: Vec<RouteGraphNode>,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+/// Options for validating the app directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StructureOptions {
+    /// Explicit not-found route from the root module export
+    pub not_found_route: Option<String>,
+    /// Ignore rules and cache settings for the underlying scan
+    pub scan: ScanOptions,
+}
+
+/// Page and layout files of one directory, keyed by its path relative to the app dir
+#[derive(Default)]
+struct DirectoryFiles<'a> {
+    page: Option<&'a ScannedComponent>,
+    layout: Option<&'a ScannedComponent>,
+}
+
+/// Validate the app directory structure
+pub fn validate_app_structure(app_dir: &str, options: &StructureOptions) -> StructureValidation {
+    let app_path = Path::new(app_dir);
+    let scan = scan_app_directory_with_options(app_dir, &options.scan);
+    let filter = ScanFilter::new(app_path, &options.scan);
+
+    let mut components: Vec<&ScannedComponent> = scan.client_components.iter()
+        .chain(scan.server_components.iter().filter(|c| !c.is_client))
+        .chain(scan.api_routes.iter().filter(|c| !c.is_client))
+        .collect();
+    components.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+
+    let mut directories: HashMap<String, DirectoryFiles> = HashMap::new();
+    for component in &components {
+        let (dir, _) = split_relative_path(&component.relative_path);
+        let files = directories.entry(dir.to_string()).or_default();
+        match component.component_type {
+            ComponentType::Page => {
+                files.page.get_or_insert(component);
+            }
+            ComponentType::Layout if file_stem(&component.relative_path) == "layout" => {
+                files.layout.get_or_insert(component);
+            }
+            _ => {}
+        }
+    }
+
+    let mut issues = Vec::new();
+
+    // Parse failures make the export check meaningless
+    let unparsable: Vec<&str> = scan.errors.iter()
+        .filter(|e| e.kind == ScanErrorKind::ParseError)
+        .map(|e| e.file.as_str())
+        .collect();
+    check_root_exists(&components, &unparsable, &mut issues);
+
+    let route_graph = build_route_graph(app_path, "", "", &filter, &directories);
+
+    check_reserved_not_found_public(app_path, &route_graph, &mut issues);
+    check_route_conflicts(app_path, &route_graph, &mut issues);
+    check_invalid_segment_names(app_path, app_path, &filter, &mut issues);
+    check_not_found_resolution(
+        app_path,
+        options.not_found_route.as_deref(),
+        &directories,
+        &components,
+        &mut issues,
+    );
+    check_metadata_export_shape(&components, &mut issues);
+
+    let state = if issues.iter().any(|i| i.severity == Severity::Error) {
+        StructureState::Error
+    } else {
+        StructureState::Ok
+    };
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default();
+
+    StructureValidation {
+        state,
+        issues,
+        route_graph,
+        timestamp,
+    }
+}
+
+/// Span at the start of a file or directory of the app
+fn path_span(app_dir: &Path, path: &str) -> SourceSpan {
+    SourceSpan::new(path, &relative_path_from(Path::new(path), app_dir), 1, 1)
+}
+
+fn component_span(component: &ScannedComponent) -> SourceSpan {
+    SourceSpan::new(&component.absolute_path, &component.relative_path, 1, 1)
+}
+
+/// Split "blog/[slug]/page.tsx" into ("blog/[slug]", "page.tsx")
+fn split_relative_path(relative_path: &str) -> (&str, &str) {
+    relative_path.rsplit_once('/').unwrap_or(("", relative_path))
+}
+
+fn file_stem(relative_path: &str) -> &str {
+    let (_, file_name) = split_relative_path(relative_path);
+    file_name.split_once('.').map_or(file_name, |(stem, _)| stem)
+}
+
+/// Sorted subdirectories of `dir` that aren't ignored by the scanner
+fn route_directories(dir: &Path, filter: &ScanFilter) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|e| e.path())
+        .filter(|path| !filter.is_ignored(path, true))
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn build_route_graph(
+    dir: &Path,
+    relative_dir: &str,
+    parent_pattern: &str,
+    filter: &ScanFilter,
+    directories: &HashMap<String, DirectoryFiles>,
+) -> Vec<RouteGraphNode> {
+    route_directories(dir, filter)
+        .into_iter()
+        .map(|child| {
+            let segment = child.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let kind = SegmentKind::from_segment(&segment);
+            let mut parts: Vec<String> = parent_pattern
+                .split('/')
+                .filter(|part| !part.is_empty())
+                .map(str::to_string)
+                .collect();
+            apply_segment(&mut parts, &segment);
+            let pattern = join_pattern(&parts);
+            let relative = if relative_dir.is_empty() {
+                segment.clone()
+            } else {
+                format!("{relative_dir}/{segment}")
+            };
+
+            let files = directories.get(&relative);
+            let page = files.and_then(|f| f.page);
+            let children = build_route_graph(&child, &relative, &pattern, filter, directories);
+
+            RouteGraphNode {
+                segment,
+                pattern,
+                kind,
+                has_page: page.is_some(),
+                has_layout: files.is_some_and(|f| f.layout.is_some()),
+                children,
+                file_path: page.map(|p| p.absolute_path.clone()),
+            }
+        })
+        .collect()
+}
+
+fn check_root_exists(
+    components: &[&ScannedComponent],
+    unparsable: &[&str],
+    issues: &mut Vec<Diagnostic>,
+) {
+    let at_root = |stem: &str| {
+        components
+            .iter()
+            .find(|c| !c.relative_path.contains('/') && file_stem(&c.relative_path) == stem)
+            .copied()
+    };
+    let root = at_root("root");
+    let layout = at_root("layout");
+
+    let Some(selected) = root.or(layout) else {
+        issues.push(
+            Diagnostic::error(ROOT_MISSING, "Missing app/root.(tsx|ts|jsx|js). No root entry point found.")
+                .with_fix("Create app/root.tsx with a default export component wrapping <html> and <body>."),
+        );
+        return;
+    };
+
+    if root.is_none() {
+        issues.push(
+            Diagnostic::warning(
+                LAYOUT_FALLBACK_USED,
+                "Using app/layout as fallback. Migrate to app/root.tsx (canonical Vista root).",
+            )
+            .with_primary_span(component_span(selected))
+            .with_fix("Rename app/layout.tsx to app/root.tsx."),
+        );
+    }
+
+    let has_default = selected.exports.iter().any(|e| e == "default");
+    if !has_default && !unparsable.contains(&selected.relative_path.as_str()) {
+        issues.push(
+            Diagnostic::error(
+                ROOT_EXPORT_MISSING,
+                format!("Root layout must export a default component: {}", selected.relative_path),
+            )
+            .with_primary_span(component_span(selected))
+            .with_code_frame_from_disk()
+            .with_fix("Add \"export default function Root({ children }) { ... }\" to your root file."),
+        );
+    }
+}
+
+fn check_reserved_not_found_public(app_dir: &Path, nodes: &[RouteGraphNode], issues: &mut Vec<Diagnostic>) {
+    for node in nodes {
+        if node.kind == SegmentKind::ReservedInternal && node.has_page {
+            let mut issue = Diagnostic::error(
+                RESERVED_NOT_FOUND_PUBLIC,
+                format!(
+                    "Reserved segment \"{}\" must not be publicly routable. Remove page file from {}/.",
+                    node.segment, node.segment
+                ),
+            )
+            .with_fix("The [not-found] segment is reserved for Vista's 404 handling. Do not add a page file inside it.");
+            if let Some(page) = &node.file_path {
+                issue = issue.with_primary_span(path_span(app_dir, page));
+            }
+            issues.push(issue);
+        }
+        check_reserved_not_found_public(app_dir, &node.children, issues);
+    }
+}
+
+fn collect_patterns(nodes: &[RouteGraphNode], patterns: &mut Vec<(String, Vec<String>)>) {
+    for node in nodes {
+        // Slot and intercepting pages render alongside the route they match
+        if matches!(node.kind, SegmentKind::Slot | SegmentKind::Intercepting) {
+            continue;
+        }
+        if node.has_page && node.kind != SegmentKind::ReservedInternal {
+            let source = node.file_path.clone().unwrap_or_else(|| node.segment.clone());
+            for key in route_conflict_keys(&node.pattern) {
+                match patterns.iter_mut().find(|(pattern, _)| *pattern == key) {
+                    Some((_, sources)) => sources.push(source.clone()),
+                    None => patterns.push((key, vec![source.clone()])),
+                }
+            }
+        }
+        collect_patterns(&node.children, patterns);
+    }
+}
+
+fn check_route_conflicts(app_dir: &Path, route_graph: &[RouteGraphNode], issues: &mut Vec<Diagnostic>) {
+    let mut patterns = Vec::new();
+    collect_patterns(route_graph, &mut patterns);
+
+    // Two optional catch-alls collide on both of their keys; report them once
+    let mut reported = HashSet::new();
+    for (pattern, sources) in patterns {
+        if sources.len() > 1 && reported.insert(sources.clone()) {
+            let mut spans = sources.iter().map(|source| path_span(app_dir, source).with_label("serves this pattern"));
+            let mut issue = Diagnostic::error(
+                ROUTE_PATTERN_CONFLICT,
+                format!(
+                    "Route pattern \"{pattern}\" is served by multiple sources: {}",
+                    sources.join(", ")
+                ),
+            )
+            .with_fix("Remove or rename one of the conflicting route segments.");
+            if let Some(first) = spans.next() {
+                issue = issue.with_primary_span(first).with_secondary_spans(spans);
+            }
+            issues.push(issue);
+        }
+    }
+}
+
+fn check_invalid_segment_names(app_dir: &Path, dir: &Path, filter: &ScanFilter, issues: &mut Vec<Diagnostic>) {
+    for child in route_directories(dir, filter) {
+        let name = child.file_name().unwrap_or_default().to_string_lossy().to_string();
+        if !is_valid_segment_name(&name) && SegmentKind::from_segment(&name) != SegmentKind::ReservedInternal {
+            issues.push(
+                Diagnostic::error(
+                    INVALID_SEGMENT_NAME,
+                    format!(
+                        "Invalid route segment name: \"{name}\". Segments must be alphanumeric/dashes, [param], [...param], (group), @slot, or an intercepting (.)segment."
+                    ),
+                )
+                .with_primary_span(path_span(app_dir, &child.to_string_lossy()))
+                .with_fix(format!(
+                    "Rename \"{name}\" to a valid segment pattern (e.g., lowercase-dashed, [dynamic], (group))."
+                )),
+            );
+        }
+        check_invalid_segment_names(app_dir, &child, filter, issues);
+    }
+}
+
+fn check_not_found_resolution(
+    app_dir: &Path,
+    not_found_route: Option<&str>,
+    directories: &HashMap<String, DirectoryFiles>,
+    components: &[&ScannedComponent],
+    issues: &mut Vec<Diagnostic>,
+) {
+    let has_page = |dir: &str| directories.get(dir).is_some_and(|f| f.page.is_some());
+
+    if let Some(route) = not_found_route {
+        let target = route.trim_matches('/');
+        if !has_page(target) {
+            issues.push(
+                Diagnostic::error(
+                    INVALID_NOT_FOUND_OVERRIDE_TARGET,
+                    format!(
+                        "notFoundRoute \"{route}\" was configured but no page was found at {}.",
+                        app_dir.join(target).to_string_lossy()
+                    ),
+                )
+                .with_fix(format!(
+                    "Create a page file at app/{target}/page.tsx or correct the notFoundRoute value."
+                )),
+            );
+        }
+    }
+
+    let has_auto_detect = has_page("[not-found]");
+    let has_legacy = components
+        .iter()
+        .any(|c| !c.relative_path.contains('/') && c.component_type == ComponentType::NotFound);
+    if has_auto_detect && has_legacy && not_found_route.is_none() {
+        issues.push(
+            Diagnostic::warning(
+                MULTIPLE_NOT_FOUND_SOURCES,
+                "Both app/[not-found]/page.* and app/not-found.* exist without an explicit notFoundRoute. Auto-detect (app/[not-found]/page.*) takes precedence.",
+            )
+            .with_fix("Remove one of the duplicates or set notFoundRoute in your root export to pick explicitly."),
+        );
+    }
+}
+
+fn check_metadata_export_shape(components: &[&ScannedComponent], issues: &mut Vec<Diagnostic>) {
+    let routed = components.iter().filter(|c| {
+        matches!(c.component_type, ComponentType::Page | ComponentType::Layout)
+    });
+    for component in routed.filter(|c| c.metadata_is_primitive) {
+        issues.push(
+            Diagnostic::error(
+                METADATA_EXPORT_SHAPE_INVALID,
+                format!(
+                    "metadata export in {} must be an object, not a primitive.",
+                    component.relative_path
+                ),
+            )
+            .with_primary_span(component_span(component))
+            .with_fix("Export metadata as an object: export const metadata = { title: \"...\" };"),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_app_structure() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        let write = |relative: &str, contents: &str| {
+            let path = app.join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        };
+        write("layout.tsx", "export default function Root({ children }) { return children; }");
+        write("not-found.tsx", "export default function NotFound() {}");
+        write("[not-found]/page.tsx", "export default function NotFound() {}");
+        write("about/page.tsx", "export const metadata = 'About';\nexport default function About() {}");
+        write("(marketing)/about/page.tsx", "export default function About() {}");
+        write("blog/[id]/page.tsx", "export default function Post() {}");
+        write("blog/[slug]/edit/page.tsx", "export default function Edit() {}");
+        write("my blog/page.tsx", "export default function Bad() {}");
+
+        let result = validate_app_structure(&app.to_string_lossy(), &StructureOptions::default());
+        let codes: Vec<&str> = result.issues.iter().map(|i| i.code.as_str()).collect();
+        assert_eq!(codes, vec![
+            "LAYOUT_FALLBACK_USED",
+            "RESERVED_NOT_FOUND_PUBLIC",
+            "ROUTE_PATTERN_CONFLICT",
+            "INVALID_SEGMENT_NAME",
+            "MULTIPLE_NOT_FOUND_SOURCES",
+            "METADATA_EXPORT_SHAPE_INVALID",
+        ]);
+        assert_eq!(result.state, StructureState::Error);
+        assert!(result.issues[2].message.contains("\"/about\""));
+        let conflict = &result.issues[2];
+        assert_eq!(conflict.primary_span.as_ref().unwrap().relative_path, "(marketing)/about/page.tsx");
+        assert_eq!(conflict.secondary_spans[0].relative_path, "about/page.tsx");
+        assert_eq!(result.issues[3].primary_span.as_ref().unwrap().relative_path, "my blog");
+        assert!(result.issues[3].fix.as_deref().unwrap().starts_with("Rename \"my blog\""));
+
+        let blog = result.route_graph.iter().find(|n| n.segment == "blog").unwrap();
+        let patterns: Vec<&str> = blog.children.iter().map(|n| n.pattern.as_str()).collect();
+        assert_eq!(patterns, vec!["/blog/:id", "/blog/:slug"]);
+        assert_eq!(blog.children[1].children[0].pattern, "/blog/:slug/edit");
+        assert_eq!(blog.children[0].kind, SegmentKind::Dynamic);
+
+        let options = StructureOptions {
+            not_found_route: Some("/missing".to_string()),
+            ..StructureOptions::default()
+        };
+        let result = validate_app_structure(&app.to_string_lossy(), &options);
+        assert!(result.issues.iter().any(|i| i.code == INVALID_NOT_FOUND_OVERRIDE_TARGET));
+        assert!(result.issues.iter().all(|i| i.code != MULTIPLE_NOT_FOUND_SOURCES));
+    }
+
+    #[test]
+    fn test_optional_catch_all_conflicts_with_parent_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        fs::create_dir_all(&app).unwrap();
+        fs::write(app.join("root.tsx"), "export default function Root({ children }) { return children; }").unwrap();
+        for page in ["docs", "docs/[[...slug]]", "shop/[[...slug]]", "shop/[[...path]]"] {
+            fs::create_dir_all(app.join(page)).unwrap();
+            fs::write(app.join(page).join("page.tsx"), "export default function Page() {}").unwrap();
+        }
+
+        let result = validate_app_structure(&app.to_string_lossy(), &StructureOptions::default());
+        let conflicts: Vec<&str> = result.issues.iter()
+            .filter(|i| i.code == ROUTE_PATTERN_CONFLICT)
+            .map(|i| i.message.as_str())
+            .collect();
+        assert_eq!(conflicts.len(), 2);
+        assert!(conflicts[0].starts_with("Route pattern \"/docs\""));
+        assert!(conflicts[1].starts_with("Route pattern \"/shop/:param*?\""));
+    }
+
+    #[test]
+    fn test_missing_root() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("app/blog")).unwrap();
+        fs::write(dir.path().join("app/root.tsx"), "export const Root = () => null;").unwrap();
+
+        let result = validate_app_structure(&dir.path().join("app").to_string_lossy(), &StructureOptions::default());
+        assert_eq!(result.issues[0].code, ROOT_EXPORT_MISSING);
+        assert!(result.issues[0].code_frame.is_some());
+        assert_eq!(result.route_graph[0].pattern, "/blog");
+
+        let empty = tempfile::tempdir().unwrap();
+        let result = validate_app_structure(&empty.path().join("app").to_string_lossy(), &StructureOptions::default());
+        assert_eq!(result.issues[0].code, ROOT_MISSING);
+    }
+}
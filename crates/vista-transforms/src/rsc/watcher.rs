@@ -0,0 +1,571 @@
+//! RSC App Watcher
+//!
+//! Native file watcher for the app directory built on `notify`. Raw events
+//! are debounced, coalesced per path and filtered through the scanner's
+//! extension and ignore rules before being delivered as one batch.
+//!
+//! Extra roots such as `components/` or `vista.config.ts` can be watched
+//! next to the app directory, which is what the dev server uses in place of
+//! chokidar. Directory events are expanded into events for the files they
+//! contain, so removing or renaming a route folder is reported per file.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use notify::event::{EventKind, ModifyKind, RenameMode};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+
+use super::graph::relative_path_from;
+use super::scanner::{ScanFilter, ScanOptions, SCANNED_EXTENSIONS};
+
+/// Kind of change reported for a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchEventKind {
+    Add,
+    Change,
+    Remove,
+}
+
+impl WatchEventKind {
+    /// Combine an earlier pending change with a later one for the same path.
+    /// Returns `None` when the two cancel out (added then removed).
+    fn merge(earlier: Self, later: Self) -> Option<Self> {
+        match (earlier, later) {
+            (Self::Add, Self::Remove) => None,
+            (Self::Add, _) => Some(Self::Add),
+            (Self::Remove, Self::Add) | (Self::Remove, Self::Change) => Some(Self::Change),
+            (_, later) => Some(later),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Change => "change",
+            Self::Remove => "remove",
+        }
+    }
+}
+
+/// A debounced change to a scanned file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchEvent {
+    pub kind: WatchEventKind,
+    pub absolute_path: String,
+    /// Path relative to the app directory, with forward slashes
+    pub relative_path: String,
+}
+
+/// Options for watching the app directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchOptions {
+    /// Ignore rules shared with the scanner
+    pub scan: ScanOptions,
+    /// Files and directories watched next to the app directory, such as
+    /// `components/` or `vista.config.ts`. Paths that don't exist are skipped.
+    pub extra_paths: Vec<String>,
+    /// Extensions of reported files, without the dot (the scanner's by default)
+    pub extensions: Vec<String>,
+    /// Quiet period before a batch is delivered
+    pub debounce_ms: u64,
+    /// Longest a batch waits after its first event, so a steady stream of
+    /// changes can't keep postponing it
+    pub max_wait_ms: u64,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            scan: ScanOptions::default(),
+            extra_paths: Vec::new(),
+            extensions: SCANNED_EXTENSIONS.iter().map(|ext| ext.to_string()).collect(),
+            debounce_ms: 120,
+            max_wait_ms: 1000,
+        }
+    }
+}
+
+/// Running watcher. Dropping it (or calling [`AppWatcher::close`]) stops
+/// watching; a pending batch is still delivered.
+pub struct AppWatcher {
+    watcher: Option<RecommendedWatcher>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl AppWatcher {
+    /// Watch `app_dir` and `options.extra_paths` and call `on_change` with
+    /// each debounced batch. Batches are sorted by relative path and never empty.
+    pub fn start<F>(app_dir: &str, options: &WatchOptions, on_change: F) -> notify::Result<Self>
+    where
+        F: Fn(Vec<WatchEvent>) + Send + 'static,
+    {
+        let mut files = WatchedFiles::new(Path::new(app_dir), options);
+        let debounce = Duration::from_millis(options.debounce_ms);
+        let max_wait = Duration::from_millis(options.max_wait_ms.max(options.debounce_ms));
+
+        let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+        let mut watcher = notify::recommended_watcher(tx)?;
+        for (path, root) in &files.roots {
+            match root {
+                WatchRoot::Dir(_) => watcher.watch(path, RecursiveMode::Recursive)?,
+                // Watch the parent so editors that save by renaming over the
+                // file don't end the watch
+                WatchRoot::File => {
+                    watcher.watch(path.parent().unwrap_or(path), RecursiveMode::NonRecursive)?
+                }
+            }
+        }
+        // Files created from here on are reported by notify
+        files.track_existing();
+
+        let worker = std::thread::spawn(move || {
+            // The channel disconnects once the notify watcher is dropped
+            while let Ok(first) = rx.recv() {
+                let pending = collect_batch(&rx, first, debounce, max_wait);
+                let batch = pending.into_batch(&mut files);
+                if !batch.is_empty() {
+                    on_change(batch);
+                }
+            }
+        });
+
+        Ok(Self {
+            watcher: Some(watcher),
+            worker: Some(worker),
+        })
+    }
+
+    /// Stop watching and wait for the last batch to be delivered
+    pub fn close(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        drop(self.watcher.take());
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for AppWatcher {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Collect events after `first` until `debounce` passes without one or
+/// `max_wait` has passed since `first`
+fn collect_batch(
+    rx: &mpsc::Receiver<notify::Result<Event>>,
+    first: notify::Result<Event>,
+    debounce: Duration,
+    max_wait: Duration,
+) -> PendingChanges {
+    let deadline = Instant::now() + max_wait;
+    let mut pending = PendingChanges::default();
+    pending.push(first);
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        match rx.recv_timeout(debounce.min(remaining)) {
+            Ok(event) => pending.push(event),
+            Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+    pending
+}
+
+/// A watched directory, with its ignore rules, or a single watched file
+enum WatchRoot {
+    Dir(ScanFilter),
+    File,
+}
+
+/// Watch roots and the accepted files currently under them. Tracking the
+/// files lets a removed or renamed-away directory be reported as removals
+/// of the files it held, since notify only reports the directory itself.
+struct WatchedFiles {
+    app_dir: PathBuf,
+    roots: Vec<(PathBuf, WatchRoot)>,
+    tracked: BTreeSet<PathBuf>,
+}
+
+impl WatchedFiles {
+    fn new(app_dir: &Path, options: &WatchOptions) -> Self {
+        // Event paths are canonical (e.g. through symlinked temp dirs)
+        let canonical = |path: &Path| fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let app_dir = canonical(app_dir);
+
+        let extra = options
+            .extra_paths
+            .iter()
+            .map(Path::new)
+            .filter(|path| path.exists())
+            .map(canonical);
+        let roots = std::iter::once(app_dir.clone())
+            .chain(extra)
+            .map(|path| {
+                let root = if path == app_dir || path.is_dir() {
+                    WatchRoot::Dir(ScanFilter::new(&path, &options.scan).with_extensions(&options.extensions))
+                } else {
+                    WatchRoot::File
+                };
+                (path, root)
+            })
+            .collect();
+
+        Self {
+            app_dir,
+            roots,
+            tracked: BTreeSet::new(),
+        }
+    }
+
+    /// Start tracking the accepted files that exist under the roots
+    fn track_existing(&mut self) {
+        for (path, root) in &self.roots {
+            match root {
+                WatchRoot::Dir(filter) => collect_files(path, filter, &mut self.tracked),
+                WatchRoot::File => {
+                    self.tracked.insert(path.clone());
+                }
+            }
+        }
+    }
+
+    /// Whether `path` is a file the watcher reports
+    fn accepts(&self, path: &Path) -> bool {
+        self.roots.iter().any(|(root, kind)| match kind {
+            WatchRoot::Dir(filter) => path.starts_with(root) && filter.accepts_file(path),
+            WatchRoot::File => path == root,
+        })
+    }
+
+    /// Accepted files under a directory that appeared in a watched root
+    fn files_under(&self, dir: &Path) -> BTreeSet<PathBuf> {
+        let mut files = BTreeSet::new();
+        for (root, kind) in &self.roots {
+            if let WatchRoot::Dir(filter) = kind {
+                if dir.starts_with(root) && !filter.is_ignored(dir, true) {
+                    collect_files(dir, filter, &mut files);
+                }
+            }
+        }
+        files
+    }
+
+    /// Stop tracking `path` and everything under it, returning what was tracked
+    fn untrack_under(&mut self, path: &Path) -> Vec<PathBuf> {
+        // Paths under `path` sort right after it
+        let removed: Vec<PathBuf> = self
+            .tracked
+            .range(path.to_path_buf()..)
+            .take_while(|tracked| tracked.starts_with(path))
+            .cloned()
+            .collect();
+        for file in &removed {
+            self.tracked.remove(file);
+        }
+        removed
+    }
+}
+
+/// Add the accepted files under `dir` to `files`, skipping ignored directories
+fn collect_files(dir: &Path, filter: &ScanFilter, files: &mut BTreeSet<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            if !filter.is_ignored(&path, true) {
+                collect_files(&path, filter, files);
+            }
+        } else if file_type.is_file() && filter.accepts_file(&path) {
+            files.insert(path);
+        }
+    }
+}
+
+/// Changes collected during one debounce window, coalesced per path
+#[derive(Default)]
+struct PendingChanges {
+    changes: BTreeMap<PathBuf, WatchEventKind>,
+}
+
+impl PendingChanges {
+    fn push(&mut self, event: notify::Result<Event>) {
+        let Ok(event) = event else {
+            return;
+        };
+        match event.kind {
+            EventKind::Create(_) => self.record_all(&event.paths, WatchEventKind::Add),
+            EventKind::Remove(_) => self.record_all(&event.paths, WatchEventKind::Remove),
+            EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                self.record_all(&event.paths, WatchEventKind::Remove)
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+                self.record_all(&event.paths, WatchEventKind::Add)
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if event.paths.len() == 2 => {
+                self.record(&event.paths[0], WatchEventKind::Remove);
+                self.record(&event.paths[1], WatchEventKind::Add);
+            }
+            EventKind::Modify(ModifyKind::Name(_)) => {
+                // Unknown rename side: decide by whether the path still exists
+                for path in &event.paths {
+                    let kind = if path.exists() {
+                        WatchEventKind::Add
+                    } else {
+                        WatchEventKind::Remove
+                    };
+                    self.record(path, kind);
+                }
+            }
+            EventKind::Modify(ModifyKind::Metadata(_)) | EventKind::Access(_) => {}
+            EventKind::Modify(_) | EventKind::Any | EventKind::Other => {
+                self.record_all(&event.paths, WatchEventKind::Change)
+            }
+        }
+    }
+
+    fn record_all(&mut self, paths: &[PathBuf], kind: WatchEventKind) {
+        for path in paths {
+            self.record(path, kind);
+        }
+    }
+
+    fn record(&mut self, path: &Path, kind: WatchEventKind) {
+        match self.changes.remove(path) {
+            Some(earlier) => {
+                if let Some(merged) = WatchEventKind::merge(earlier, kind) {
+                    self.changes.insert(path.to_path_buf(), merged);
+                }
+            }
+            None => {
+                self.changes.insert(path.to_path_buf(), kind);
+            }
+        }
+    }
+
+    /// Resolve the collected changes into file events, expanding directory
+    /// events into the files they added or removed and updating `files`
+    fn into_batch(self, files: &mut WatchedFiles) -> Vec<WatchEvent> {
+        // Directories sort before their contents, so a file's own event is
+        // merged after the one expanded from its directory
+        let mut resolved = PendingChanges::default();
+        for (path, kind) in self.changes {
+            if kind == WatchEventKind::Remove {
+                let removed = files.untrack_under(&path);
+                if removed.is_empty() && files.accepts(&path) {
+                    resolved.record(&path, kind);
+                }
+                resolved.record_all(&removed, kind);
+            } else if path.is_dir() {
+                if kind == WatchEventKind::Add {
+                    for file in files.files_under(&path) {
+                        if files.tracked.insert(file.clone()) {
+                            resolved.record(&file, kind);
+                        }
+                    }
+                }
+            } else if files.accepts(&path) {
+                files.tracked.insert(path.clone());
+                resolved.record(&path, kind);
+            }
+        }
+
+        let mut batch: Vec<WatchEvent> = resolved
+            .changes
+            .into_iter()
+            .map(|(path, kind)| WatchEvent {
+                kind,
+                relative_path: relative_path_from(&path, &files.app_dir),
+                absolute_path: path.to_string_lossy().to_string(),
+            })
+            .collect();
+        batch.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+        batch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, DataChange, RemoveKind};
+    use std::fs;
+
+    fn event(kind: EventKind, path: &Path) -> notify::Result<Event> {
+        Ok(Event::new(kind).add_path(path.to_path_buf()))
+    }
+
+    #[test]
+    fn test_merge_event_kinds() {
+        use WatchEventKind::*;
+        assert_eq!(WatchEventKind::merge(Add, Change), Some(Add));
+        assert_eq!(WatchEventKind::merge(Add, Remove), None);
+        assert_eq!(WatchEventKind::merge(Remove, Add), Some(Change));
+        assert_eq!(WatchEventKind::merge(Change, Remove), Some(Remove));
+    }
+
+    #[test]
+    fn test_pending_changes_are_coalesced_and_filtered() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        fs::create_dir_all(app.join("node_modules")).unwrap();
+
+        let mut pending = PendingChanges::default();
+        let page = app.join("page.tsx");
+        let temp = app.join("draft.tsx");
+        pending.push(event(EventKind::Create(CreateKind::File), &page));
+        pending.push(event(EventKind::Modify(ModifyKind::Data(DataChange::Content)), &page));
+        pending.push(event(EventKind::Create(CreateKind::File), &temp));
+        pending.push(event(EventKind::Remove(RemoveKind::File), &temp));
+        pending.push(event(EventKind::Modify(ModifyKind::Any), &app.join("layout.tsx")));
+        pending.push(event(EventKind::Create(CreateKind::File), &app.join("styles.css")));
+        pending.push(event(EventKind::Create(CreateKind::File), &app.join("node_modules/x.js")));
+
+        let mut files = WatchedFiles::new(&app, &WatchOptions::default());
+        let batch = pending.into_batch(&mut files);
+        let summary: Vec<_> = batch
+            .iter()
+            .map(|e| (e.kind, e.relative_path.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![(WatchEventKind::Change, "layout.tsx"), (WatchEventKind::Add, "page.tsx")]
+        );
+    }
+
+    #[test]
+    fn test_directory_events_are_expanded_to_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = fs::canonicalize(dir.path()).unwrap().join("app");
+        for file in ["blog/page.tsx", "blog/[slug]/page.tsx", "blog/notes.md", "about/page.tsx"] {
+            let path = app.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "export default function Page() {}").unwrap();
+        }
+        let mut files = WatchedFiles::new(&app, &WatchOptions::default());
+        files.track_existing();
+
+        // `mv app/blog app/posts` is reported for the folders only
+        fs::rename(app.join("blog"), app.join("posts")).unwrap();
+        let mut pending = PendingChanges::default();
+        pending.push(Ok(Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+            .add_path(app.join("blog"))
+            .add_path(app.join("posts"))));
+        let batch = pending.into_batch(&mut files);
+        let summary: Vec<_> = batch
+            .iter()
+            .map(|e| (e.kind, e.relative_path.as_str()))
+            .collect();
+        assert_eq!(summary, vec![
+            (WatchEventKind::Remove, "blog/[slug]/page.tsx"),
+            (WatchEventKind::Remove, "blog/page.tsx"),
+            (WatchEventKind::Add, "posts/[slug]/page.tsx"),
+            (WatchEventKind::Add, "posts/page.tsx"),
+        ]);
+
+        fs::remove_dir_all(app.join("posts")).unwrap();
+        let mut pending = PendingChanges::default();
+        pending.push(event(EventKind::Remove(RemoveKind::Folder), &app.join("posts")));
+        let removed: Vec<_> = pending
+            .into_batch(&mut files)
+            .into_iter()
+            .map(|e| (e.kind, e.relative_path))
+            .collect();
+        assert_eq!(removed, vec![
+            (WatchEventKind::Remove, "posts/[slug]/page.tsx".to_string()),
+            (WatchEventKind::Remove, "posts/page.tsx".to_string()),
+        ]);
+        assert_eq!(files.tracked.len(), 1);
+    }
+
+    #[test]
+    fn test_extra_paths_and_extensions() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = fs::canonicalize(dir.path()).unwrap();
+        fs::create_dir_all(root.join("app")).unwrap();
+        fs::create_dir_all(root.join("components")).unwrap();
+        fs::write(root.join("vista.config.ts"), "export default {}").unwrap();
+
+        let options = WatchOptions {
+            extra_paths: vec![
+                root.join("components").to_string_lossy().to_string(),
+                root.join("vista.config.ts").to_string_lossy().to_string(),
+                root.join("missing").to_string_lossy().to_string(),
+            ],
+            extensions: vec!["tsx".to_string(), "css".to_string()],
+            ..WatchOptions::default()
+        };
+        let files = WatchedFiles::new(&root.join("app"), &options);
+        assert_eq!(files.roots.len(), 3);
+        assert!(files.accepts(&root.join("app/globals.css")));
+        assert!(files.accepts(&root.join("components/nav.tsx")));
+        assert!(!files.accepts(&root.join("components/nav.ts")));
+        assert!(files.accepts(&root.join("vista.config.ts")));
+        assert!(!files.accepts(&root.join("package.json")));
+    }
+
+    #[test]
+    fn test_steady_events_are_flushed_after_max_wait() {
+        let (tx, rx) = mpsc::channel();
+        let path = PathBuf::from("/app/page.tsx");
+        let sender = std::thread::spawn(move || {
+            for _ in 0..200 {
+                if tx.send(event(EventKind::Modify(ModifyKind::Any), &path)).is_err() {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        });
+
+        let first = rx.recv().unwrap();
+        let started = Instant::now();
+        let pending = collect_batch(&rx, first, Duration::from_millis(50), Duration::from_millis(150));
+        let elapsed = started.elapsed();
+        assert!(elapsed < Duration::from_millis(600), "batch took {elapsed:?}");
+        assert_eq!(pending.changes.len(), 1);
+
+        drop(rx);
+        sender.join().unwrap();
+    }
+
+    #[test]
+    fn test_watcher_delivers_batches() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        fs::create_dir_all(&app).unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let options = WatchOptions {
+            debounce_ms: 50,
+            ..WatchOptions::default()
+        };
+        let watcher = AppWatcher::start(&app.to_string_lossy(), &options, move |batch| {
+            let _ = tx.send(batch);
+        })
+        .unwrap();
+
+        fs::write(app.join("page.tsx"), "export default function Page() {}").unwrap();
+        fs::write(app.join("notes.md"), "ignored").unwrap();
+
+        let batch = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(batch.iter().any(|e| e.relative_path == "page.tsx"));
+        assert!(batch.iter().all(|e| e.relative_path != "notes.md"));
+        watcher.close();
+    }
+}
@@ -30,6 +30,7 @@ import {
   type StructureValidationResult,
 } from '../server/structure-validator';
 import { generateStaticPages } from '../server/static-generator';
+import { watchProject } from '../server/native-binding';
 import { logValidationResult, formatBuildFailTable } from '../server/structure-log';
 import { getDevToolsIndicatorBootstrapSource } from './devtools-indicator-snippet';
 import { getDevErrorOverlayBootstrapSource } from './dev-error-overlay-snippet';
@@ -545,37 +546,17 @@ export async function buildRSC(watch: boolean = false): Promise<{
     const clientCompiler = webpack(clientConfig);
     syncReactServerManifests(vistaDirs.root);
 
-    // Watch for CSS + source changes that can affect Tailwind output.
-    try {
-      const chokidar = require('chokidar');
-      const styleWatchRoots = ['app', 'components', 'content', 'lib', 'ctx', 'data']
-        .map((entry) => path.join(cwd, entry))
-        .filter((entry) => fs.existsSync(entry));
-      let cssTimer: ReturnType<typeof setTimeout> | null = null;
-      const scheduleCSSBuild = () => {
-        if (cssTimer) clearTimeout(cssTimer);
-        cssTimer = setTimeout(() => {
-          if (_debug) console.log('[Vista JS RSC] Style source changed, rebuilding CSS...');
-          runPostCSS(cwd, vistaDirs.root);
-        }, 120);
-      };
-
-      chokidar
-        .watch(styleWatchRoots, {
-          ignoreInitial: true,
-          ignored: (watchedPath: string) =>
-            watchedPath.includes(`${path.sep}node_modules${path.sep}`) ||
-            watchedPath.includes(`${path.sep}.git${path.sep}`) ||
-            watchedPath.includes(`${path.sep}.vista${path.sep}`),
-        })
-        .on('all', (_event: string, changedPath: string) => {
-          if (/\.(?:css|[cm]?[jt]sx?|md|mdx)$/i.test(changedPath)) {
-            scheduleCSSBuild();
-          }
-        });
-    } catch (e) {
-      // chokidar not installed
-    }
+    // Watch CSS + source changes that can affect Tailwind output.
+    watchProject({
+      cwd,
+      entries: ['app', 'components', 'content', 'lib', 'ctx', 'data'],
+      extensions: ['css', 'js', 'jsx', 'ts', 'tsx', 'mjs', 'cjs', 'mts', 'cts', 'md', 'mdx'],
+      debounceMs: 120,
+      onChange: () => {
+        if (_debug) console.log('[Vista JS RSC] Style source changed, rebuilding CSS...');
+        runPostCSS(cwd, vistaDirs.root);
+      },
+    });
 
     if (_debug) {
       console.log('[Vista JS RSC] Ready for development');
@@ -15,6 +15,7 @@ import {
   type StructureValidationResult,
 } from '../server/structure-validator';
 import { logValidationResult, formatBuildFailTable } from '../server/structure-log';
+import { watchProject } from '../server/native-binding';
 import { getDevToolsIndicatorBootstrapSource } from './devtools-indicator-snippet';
 import { getDevErrorOverlayBootstrapSource } from './dev-error-overlay-snippet';
 import { generateDeploymentOutputs } from './deploy-output';
@@ -428,36 +429,16 @@ export async function buildClient(
     runPostCSS(cwd, vistaDir);
 
     // Watch CSS + source files that may affect Tailwind output.
-    const chokidar = require('chokidar');
-    try {
-      const styleWatchRoots = ['app', 'components', 'content', 'lib', 'ctx', 'data']
-        .map((entry) => path.join(cwd, entry))
-        .filter((entry) => fs.existsSync(entry));
-      let cssTimer: ReturnType<typeof setTimeout> | null = null;
-      const scheduleCSSBuild = () => {
-        if (cssTimer) clearTimeout(cssTimer);
-        cssTimer = setTimeout(() => {
-          if (_debug) console.log('Style source changed, rebuilding CSS...');
-          runPostCSS(cwd, vistaDir);
-        }, 120);
-      };
-
-      chokidar
-        .watch(styleWatchRoots, {
-          ignoreInitial: true,
-          ignored: (watchedPath: string) =>
-            watchedPath.includes(`${path.sep}node_modules${path.sep}`) ||
-            watchedPath.includes(`${path.sep}.git${path.sep}`) ||
-            watchedPath.includes(`${path.sep}.vista${path.sep}`),
-        })
-        .on('all', (_event: string, changedPath: string) => {
-          if (/\.(?:css|[cm]?[jt]sx?|md|mdx)$/i.test(changedPath)) {
-            scheduleCSSBuild();
-          }
-        });
-    } catch (e) {
-      // chokidar not installed, skip CSS watch
-    }
+    watchProject({
+      cwd,
+      entries: ['app', 'components', 'content', 'lib', 'ctx', 'data'],
+      extensions: ['css', 'js', 'jsx', 'ts', 'tsx', 'mjs', 'cjs', 'mts', 'cts', 'md', 'mdx'],
+      debounceMs: 120,
+      onChange: () => {
+        if (_debug) console.log('Style source changed, rebuilding CSS...');
+        runPostCSS(cwd, vistaDir);
+      },
+    });
 
     if (_debug) console.log('Webpack compiler ready for dev middleware.');
     return compiler;
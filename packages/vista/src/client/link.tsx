@@ -23,7 +23,31 @@ import { RSCRouterContext, useRSCRouter } from './rsc-router';
  *  - Cmd/Ctrl+click native new-tab behaviour
  */
 
-type Url = string | { href: string; query?: Record<string, string>; hash?: string };
+/**
+ * Route types of the app. Empty here; the generated `.vista/types/routes.d.ts`
+ * augments it with `RoutePattern`, `RouteParams`, `Route` and `Href`, which
+ * narrows `Link`, `useIsActive` and `useParams` to the app's routes.
+ */
+export interface VistaRouteRegistry {}
+
+/** Registered route type `K`, or `Fallback` before route types are generated */
+type Registered<K extends string, Fallback> = K extends keyof VistaRouteRegistry
+  ? VistaRouteRegistry[K]
+  : Fallback;
+
+/** Every route pattern of the app, e.g. `/blog/:slug` */
+export type RoutePattern = Registered<'RoutePattern', string>;
+/** Params of each route pattern */
+export type RouteParams = Registered<
+  'RouteParams',
+  Record<string, Record<string, string | string[]>>
+>;
+/** URL paths served by the app */
+export type Route = Registered<'Route', string>;
+/** Hrefs accepted by `Link` */
+export type Href = Registered<'Href', string>;
+
+type Url = Href | { href: Href; query?: Record<string, string>; hash?: string };
 
 export interface LinkProps extends Omit<AnchorHTMLAttributes<HTMLAnchorElement>, 'href'> {
   /** Destination URL (string or URL object) */
@@ -337,7 +361,7 @@ export const useLinkStatus = () => {
 /**
  * Hook to check if a path is active
  */
-export const useIsActive = (path: string): boolean => {
+export const useIsActive = (path: Route): boolean => {
   const pathname = usePathname();
   return pathname === path;
 };
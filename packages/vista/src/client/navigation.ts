@@ -14,6 +14,9 @@
 import * as React from 'react';
 import { useRouterContext } from '../router/context';
 import { RSCRouterContext } from './rsc-router';
+import type { RouteParams } from './link';
+
+export type { VistaRouteRegistry, RouteParams, RoutePattern } from './link';
 
 /**
  * Returns the current pathname.
@@ -69,8 +72,11 @@ export function useSearchParams(): URLSearchParams {
 /**
  * Returns dynamic route parameters.
  * For route /users/[id]/posts/[postId], returns { id: '123', postId: '456' }
+ * Pass the route pattern (`useParams<'/users/:id'>()`) to get its params type.
  */
-export function useParams<T extends Record<string, string> = Record<string, string>>(): T {
+export function useParams<T extends Record<string, string> = Record<string, string>>(): T;
+export function useParams<P extends keyof RouteParams>(): RouteParams[P];
+export function useParams(): Record<string, string> {
   const context = useRouterContext();
   const pathname = usePathname();
 
@@ -86,7 +92,7 @@ export function useParams<T extends Record<string, string> = Record<string, stri
     }
   });
 
-  return params as T;
+  return params;
 }
 
 /**
@@ -39,17 +39,17 @@ function resolveTitle(
     return title;
   }
 
-  // TemplateString object
+  // TemplateString object. Its own template only applies to the titles of
+  // nested segments, so `default` goes through the parent's template.
   if (title.absolute) {
     return title.absolute;
   }
 
-  const baseTitle = title.default;
-  if (title.template) {
-    return title.template.replace('%s', baseTitle);
+  if (template) {
+    return template.replace('%s', title.default);
   }
 
-  return baseTitle;
+  return title.default;
 }
 
 function resolveUrl(
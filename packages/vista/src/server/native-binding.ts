@@ -0,0 +1,97 @@
+/**
+ * Vista Native Binding
+ *
+ * Locates the vista-napi binding for the dev server and exposes the native
+ * file watcher used in place of chokidar.
+ */
+
+import fs from 'fs';
+import path from 'path';
+
+const _debug = !!process.env.VISTA_DEBUG;
+const bindings = new Map<string, any>();
+// A collected RscWatcher stops watching, so keep open ones reachable
+const activeWatchers = new Set<{ close: () => void }>();
+
+/**
+ * Load the vista-napi binding if it exports `requiredExport`, or return null.
+ */
+export function loadNativeBinding(requiredExport: string): any {
+  if (bindings.has(requiredExport)) return bindings.get(requiredExport);
+
+  // Try multiple paths since we might be running from src or dist
+  const possiblePaths = [
+    // From compiled dist/server or src/server
+    path.resolve(__dirname, '../../../../crates/vista-napi'),
+    // From workspace root
+    path.resolve(process.cwd(), '../crates/vista-napi'),
+  ];
+
+  let binding: any = null;
+  for (const p of possiblePaths) {
+    try {
+      const native = require(p);
+      if (typeof native[requiredExport] === 'function') {
+        binding = native;
+        if (_debug) console.log(`[Vista JS] Loaded Rust native bindings from ${p}`);
+        break;
+      }
+    } catch (e) {
+      // Try next path
+    }
+  }
+
+  bindings.set(requiredExport, binding);
+  return binding;
+}
+
+export interface NativeWatchEvent {
+  kind: 'add' | 'change' | 'remove';
+  absolutePath: string;
+  relativePath: string;
+}
+
+export interface ProjectWatchOptions {
+  cwd: string;
+  /** Files and directories relative to cwd; `app` is always watched */
+  entries: string[];
+  /** Extensions of reported files, without the dot */
+  extensions?: string[];
+  ignore?: string[];
+  debounceMs?: number;
+  onChange: (events: NativeWatchEvent[]) => void;
+}
+
+/**
+ * Watch `app/` and the existing `entries` with the native watcher. Returns
+ * null when the binding is unavailable or the app directory is missing.
+ */
+export function watchProject(options: ProjectWatchOptions): { close: () => void } | null {
+  const native = loadNativeBinding('rscWatchApp');
+  const appDir = path.join(options.cwd, 'app');
+  if (!native || !fs.existsSync(appDir)) return null;
+
+  const extraPaths = options.entries
+    .filter((entry) => entry !== 'app')
+    .map((entry) => path.join(options.cwd, entry))
+    .filter((entry) => fs.existsSync(entry));
+
+  try {
+    const watcher = native.rscWatchApp(appDir, options.onChange, {
+      extraPaths,
+      extensions: options.extensions,
+      ignore: options.ignore,
+      debounceMs: options.debounceMs,
+    });
+    activeWatchers.add(watcher);
+    return {
+      close: () => {
+        activeWatchers.delete(watcher);
+        watcher.close();
+      },
+    };
+  } catch (err) {
+    if (_debug) console.log('[Vista JS] Native watcher failed to start:', err);
+    return null;
+  }
+}
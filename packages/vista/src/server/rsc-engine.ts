@@ -107,6 +107,7 @@ import type { RouteEntry, ServerManifest } from '../build/rsc/server-manifest';
 import { assertVistaArtifacts } from './artifact-validator';
 import { resolveNotFoundComponent, resolveRootLayout, type RootRenderMode } from './root-resolver';
 import { StructureWatcher, type StructureWatchEvent } from './structure-watch';
+import { watchProject } from './native-binding';
 import type { StructureValidationResult } from './structure-validator';
 import {
   logValidationResult,
@@ -1127,7 +1128,7 @@ export function startRSCServer(options: RSCEngineOptions = {}): void {
 
   // ========================================================================
   // Live-Reload SSE for RSC dev mode
-  // - Pushes reload events when server components change (fs.watch)
+  // - Pushes reload events when server components change (native watcher)
   // - Pushes compile errors/success from webpack client build
   // ========================================================================
   const sseReloadClients: Set<express.Response> = new Set();
@@ -1151,8 +1152,9 @@ export function startRSCServer(options: RSCEngineOptions = {}): void {
       sseReloadClients.forEach((c) => c.write(`data: ${payload}\n\n`));
     };
 
+    const watchExtensions = ['js', 'jsx', 'ts', 'tsx', 'mjs', 'cjs', 'mts', 'cts', 'css', 'md', 'mdx', 'json'];
     const watchExtPattern = /\.(?:[cm]?[jt]sx?|css|md|mdx|json)$/i;
-    const watchRoots = [
+    const watchEntries = [
       'app',
       'components',
       'content',
@@ -1162,9 +1164,7 @@ export function startRSCServer(options: RSCEngineOptions = {}): void {
       'middleware.ts',
       'vista.config.ts',
       'content-collections.ts',
-    ]
-      .map((entry) => path.join(cwd, entry))
-      .filter((entry) => fs.existsSync(entry));
+    ];
 
     let reloadTimer: ReturnType<typeof setTimeout> | null = null;
     const scheduleReload = () => {
@@ -1175,24 +1175,20 @@ export function startRSCServer(options: RSCEngineOptions = {}): void {
       }, 140);
     };
 
-    try {
-      const chokidar = require('chokidar');
-      const watcher = chokidar.watch(watchRoots, {
-        ignoreInitial: true,
-        ignored: (watchedPath: string) =>
-          watchedPath.includes(`${path.sep}node_modules${path.sep}`) ||
-          watchedPath.includes(`${path.sep}.git${path.sep}`) ||
-          watchedPath.includes(`${path.sep}${BUILD_DIR}${path.sep}`),
-      });
-
-      watcher.on('all', (_event: string, filePath: string) => {
-        if (filePath && watchExtPattern.test(filePath)) {
-          scheduleReload();
-        }
-      });
+    // The native watcher debounces, skips node_modules and hidden directories
+    // (.git, .vista) and reports folder renames per file
+    fsWatcher = watchProject({
+      cwd,
+      entries: watchEntries,
+      extensions: watchExtensions,
+      debounceMs: 140,
+      onChange: () => {
+        logEvent('Source changed, reloading...');
+        pushSSE('reload');
+      },
+    });
 
-      fsWatcher = watcher;
-    } catch {
+    if (!fsWatcher) {
       const nativeWatchers: fs.FSWatcher[] = [];
       const onChange = (_event: string, filePath?: string) => {
         if (filePath && watchExtPattern.test(filePath)) {
@@ -1200,6 +1196,9 @@ export function startRSCServer(options: RSCEngineOptions = {}): void {
         }
       };
 
+      const watchRoots = watchEntries
+        .map((entry) => path.join(cwd, entry))
+        .filter((entry) => fs.existsSync(entry));
       for (const watchPath of watchRoots) {
         try {
           const stat = fs.statSync(watchPath);
@@ -41,6 +41,10 @@ function formatIssue(issue: StructureIssue, verbose: boolean): string {
     line += `\n    ${DIM}→ ${issue.filePath}${RESET}`;
   }
 
+  if (issue.codeFrame && verbose) {
+    line += `\n${DIM}${issue.codeFrame.replace(/^/gm, '    ')}${RESET}`;
+  }
+
   if (issue.fix && verbose) {
     line += `\n    ${CYAN}fix:${RESET} ${issue.fix}`;
   }
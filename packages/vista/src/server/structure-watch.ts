@@ -1,21 +1,20 @@
 /**
  * Vista Structure Watch Service
  *
- * Watches app/, components/, and vista.config.* for file changes
- * and re-validates the structure on each batch of events.
+ * Watches app/, components/, and vista.config.* with the native watcher
+ * and re-validates the structure on each debounced batch of events.
  *
  * Emits normalized validation results that can be consumed by
  * the dev server SSE channel and build pipeline.
  */
 
-import path from 'path';
 import { EventEmitter } from 'events';
 import {
   validateAppStructure,
   type StructureValidationResult,
   type ValidateAppStructureInput,
 } from './structure-validator';
-import { BUILD_DIR } from '../constants';
+import { watchProject } from './native-binding';
 
 // ============================================================================
 // Types
@@ -42,9 +41,7 @@ export class StructureWatcher extends EventEmitter {
   private cwd: string;
   private debounceMs: number;
   private notFoundRoute: string | undefined;
-  private watcher: any = null;
-  private debounceTimer: ReturnType<typeof setTimeout> | null = null;
-  private pendingChanges: string[] = [];
+  private watcher: { close: () => void } | null = null;
   private currentState: StructureValidationResult | null = null;
 
   constructor(options: StructureWatchOptions) {
@@ -62,29 +59,18 @@ export class StructureWatcher extends EventEmitter {
     const initialResult = this.validate([]);
     this.currentState = initialResult;
 
-    // Set up chokidar watcher
-    try {
-      const chokidar = require('chokidar');
-      const watchPaths = [
-        path.join(this.cwd, 'app', '**', '*'),
-        path.join(this.cwd, 'components', '**', '*'),
-        path.join(this.cwd, 'vista.config.ts'),
-        path.join(this.cwd, 'vista.config.js'),
-      ];
-
-      this.watcher = chokidar.watch(watchPaths, {
-        ignoreInitial: true,
-        ignored: ['**/node_modules/**', `**/${BUILD_DIR}/**`, '**/dist/**'],
-        persistent: true,
-      });
-
-      this.watcher.on('all', (event: string, filePath: string) => {
-        this.pendingChanges.push(filePath);
-        this.scheduleBatch();
-      });
-    } catch {
+    // Folder renames arrive as per-file remove/add events, so moving a
+    // route directory re-validates like any other change
+    this.watcher = watchProject({
+      cwd: this.cwd,
+      entries: ['app', 'components', 'vista.config.ts', 'vista.config.js'],
+      ignore: ['node_modules', 'dist'],
+      debounceMs: this.debounceMs,
+      onChange: (events) => this.flushBatch(events.map((event) => event.absolutePath)),
+    });
+    if (!this.watcher) {
       console.warn(
-        '[vista:validate] chokidar not available. Structure watching is disabled. Install chokidar for live validation.'
+        '[vista:validate] Native watcher not available. Structure watching is disabled. Build the vista-napi binding for live validation.'
       );
     }
 
@@ -95,15 +81,10 @@ export class StructureWatcher extends EventEmitter {
    * Stop the watcher and clean up resources.
    */
   stop(): void {
-    if (this.debounceTimer) {
-      clearTimeout(this.debounceTimer);
-      this.debounceTimer = null;
-    }
     if (this.watcher) {
       this.watcher.close();
       this.watcher = null;
     }
-    this.pendingChanges = [];
   }
 
   /**
@@ -133,20 +114,7 @@ export class StructureWatcher extends EventEmitter {
   // Internals
   // --------------------------------------------------------------------------
 
-  private scheduleBatch(): void {
-    if (this.debounceTimer) {
-      clearTimeout(this.debounceTimer);
-    }
-    this.debounceTimer = setTimeout(() => {
-      this.flushBatch();
-    }, this.debounceMs);
-  }
-
-  private flushBatch(): void {
-    const changedPaths = [...this.pendingChanges];
-    this.pendingChanges = [];
-    this.debounceTimer = null;
-
+  private flushBatch(changedPaths: string[]): void {
     const result = this.validate(changedPaths);
     this.currentState = result;
 
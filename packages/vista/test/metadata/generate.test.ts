@@ -0,0 +1,17 @@
+import assert from 'node:assert/strict';
+import test from 'node:test';
+
+import { generateMetadataHtml } from '../../src/metadata/generate';
+
+test('a title template applies to nested titles, not its own default', () => {
+  const title = { default: 'Acme', template: '%s | Acme' };
+
+  // Same result as merge_metadata in vista-transforms
+  assert.equal(generateMetadataHtml({ title }), '<title>Acme</title>');
+  assert.equal(generateMetadataHtml({ title }, 'Blog - %s'), '<title>Blog - Acme</title>');
+  assert.equal(generateMetadataHtml({ title: 'About' }, title.template), '<title>About | Acme</title>');
+  assert.equal(
+    generateMetadataHtml({ title: { default: 'Acme', absolute: 'Standalone' } }, title.template),
+    '<title>Standalone</title>'
+  );
+});